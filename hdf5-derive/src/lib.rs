@@ -8,8 +8,8 @@ use proc_macro2::{Ident, Span, TokenStream};
 use proc_macro_error::{abort, proc_macro_error};
 use quote::{quote, ToTokens};
 use syn::{
-    parse_macro_input, AttrStyle, Attribute, Data, DeriveInput, Expr, Fields, Index, LitStr, Type,
-    TypeGenerics, TypePath,
+    parse_macro_input, parse_quote, AttrStyle, Attribute, Data, DataStruct, DeriveInput, Expr,
+    Fields, Generics, Index, LitStr, Type, TypeGenerics, TypePath,
 };
 
 #[proc_macro_derive(H5Type, attributes(hdf5))]
@@ -37,14 +37,157 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     proc_macro::TokenStream::from(expanded)
 }
 
-fn impl_compound<F>(
-    ty: &Ident, ty_generics: &TypeGenerics, fields: &[F], names: &[String], types: &[Type],
-) -> TokenStream
+#[proc_macro_derive(H5Proxy, attributes(hdf5))]
+#[proc_macro_error]
+pub fn derive_h5proxy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let expanded = impl_proxy(&input.ident, &input.data, &input.attrs, &input.generics);
+    proc_macro::TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(H5Group, attributes(hdf5))]
+#[proc_macro_error]
+pub fn derive_group(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (write, read) = impl_group(&name, &input.data);
+    let dummy = Ident::new(&format!("_IMPL_H5GROUP_FOR_{}", name), Span::call_site());
+    let expanded = quote! {
+        #[allow(dead_code, unused_variables, unused_attributes)]
+        const #dummy: () = {
+            extern crate hdf5 as _h5;
+
+            #[automatically_derived]
+            impl #impl_generics _h5::H5Group for #name #ty_generics #where_clause {
+                fn write_to(&self, group: &_h5::Group) -> _h5::Result<()> {
+                    #(#write)*
+                    Ok(())
+                }
+
+                fn read_from(group: &_h5::Group) -> _h5::Result<Self> {
+                    Ok(Self { #(#read),* })
+                }
+            }
+        };
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+#[derive(PartialEq, Eq)]
+enum GroupFieldKind {
+    Dataset,
+    Attr,
+    Group,
+}
+
+struct GroupField {
+    kind: GroupFieldKind,
+    rename: Option<String>,
+    options: Vec<TokenStream>,
+}
+
+fn find_hdf5_group_field(field: &syn::Field) -> GroupField {
+    let (mut kind, mut rename, mut options) = (None, None, vec![]);
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("hdf5")) {
+        let res = attr.parse_nested_meta(|meta| {
+            let new_kind = if meta.path.is_ident("dataset") {
+                GroupFieldKind::Dataset
+            } else if meta.path.is_ident("attr") {
+                GroupFieldKind::Attr
+            } else if meta.path.is_ident("group") {
+                GroupFieldKind::Group
+            } else {
+                if meta.path.is_ident("rename") {
+                    rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("chunk") || meta.path.is_ident("deflate") {
+                    let method = meta.path.get_ident().cloned();
+                    let value = meta.value()?.parse::<Expr>()?;
+                    options.push(quote!(.#method(#value)));
+                } else if meta.path.is_ident("shuffle") || meta.path.is_ident("lzf") {
+                    let method = meta.path.get_ident().cloned();
+                    options.push(quote!(.#method()));
+                } else {
+                    return Err(meta.error("unknown `hdf5` field attribute"));
+                }
+                return Ok(());
+            };
+            if kind.is_some() {
+                return Err(meta.error("conflicting `hdf5` field kinds"));
+            }
+            kind = Some(new_kind);
+            Ok(())
+        });
+        if let Err(err) = res {
+            abort!(err.span(), "{}", err);
+        }
+    }
+    let kind = kind.unwrap_or_else(|| {
+        abort!(
+            field.ident,
+            "`H5Group` fields must be annotated with #[hdf5(dataset)], #[hdf5(attr)] or #[hdf5(group)]"
+        )
+    });
+    if kind != GroupFieldKind::Dataset && !options.is_empty() {
+        abort!(field.ident, "dataset creation options can only be used with #[hdf5(dataset)]");
+    }
+    GroupField { kind, rename, options }
+}
+
+fn impl_group(ty: &Ident, data: &Data) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let fields = match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => abort!(ty, "`H5Group` can only be derived for structs with named fields"),
+        },
+        _ => abort!(ty, "`H5Group` can only be derived for structs with named fields"),
+    };
+    let (mut write, mut read) = (vec![], vec![]);
+    for field in fields {
+        let GroupField { kind, rename, options } = find_hdf5_group_field(field);
+        let ident = field.ident.as_ref().unwrap();
+        let name = rename.unwrap_or_else(|| ident.to_string());
+        let ty = &field.ty;
+        match kind {
+            GroupFieldKind::Dataset => {
+                write.push(quote! {
+                    let builder = group.new_dataset_builder()#(#options)*;
+                    _h5::dataset::DatasetValue::write_dataset(&self.#ident, builder, #name)?;
+                });
+                read.push(quote! {
+                    #ident: <#ty as _h5::dataset::DatasetValue>::read_dataset(
+                        &group.dataset(#name)?
+                    )?
+                });
+            }
+            GroupFieldKind::Attr => {
+                write.push(quote! {
+                    group.new_attr::<#ty>().create(#name)?.write_scalar(&self.#ident)?;
+                });
+                read.push(quote! {
+                    #ident: group.attr(#name)?.read_scalar::<#ty>()?
+                });
+            }
+            GroupFieldKind::Group => {
+                write.push(quote! {
+                    _h5::H5Group::write_to(&self.#ident, &group.create_group(#name)?)?;
+                });
+                read.push(quote! {
+                    #ident: <#ty as _h5::H5Group>::read_from(&group.group(#name)?)?
+                });
+            }
+        }
+    }
+    (write, read)
+}
+
+fn impl_compound<F, T>(ty: &TokenStream, fields: &[F], names: &[String], types: &[T]) -> TokenStream
 where
     F: ToTokens,
+    T: ToTokens,
 {
     quote! {
-        let origin = ::std::mem::MaybeUninit::<#ty #ty_generics>::uninit();
+        let origin = ::std::mem::MaybeUninit::<#ty>::uninit();
         let origin_ptr = origin.as_ptr();
         let mut fields = vec![#(
             _h5::types::CompoundField {
@@ -60,7 +203,7 @@ where
         for i in 0..fields.len() {
             fields[i].index = i;
         }
-        let size = ::std::mem::size_of::<#ty #ty_generics>();
+        let size = ::std::mem::size_of::<#ty>();
         _h5::types::TypeDescriptor::Compound(_h5::types::CompoundType { fields, size })
     }
 }
@@ -71,7 +214,7 @@ fn impl_transparent(ty: &Type) -> TokenStream {
     }
 }
 
-fn impl_enum(names: &[String], values: &[Expr], repr: &Ident) -> TokenStream {
+fn impl_enum(ty: &Ident, names: &[String], variants: &[Ident], repr: &Ident) -> TokenStream {
     let size = Ident::new(
         &format!(
             "U{}",
@@ -80,8 +223,25 @@ fn impl_enum(names: &[String], values: &[Expr], repr: &Ident) -> TokenStream {
         Span::call_site(),
     );
     let signed = repr.to_string().starts_with('i');
+    let values = variants.iter().map(|v| quote!(#ty::#v)).collect::<Vec<_>>();
+    // enum member values are stored as 64-bit integers
+    let check = if size == "U16" {
+        let range = iter::repeat(if signed { quote!(i64) } else { quote!(u64) });
+        let repr = iter::repeat(repr);
+        quote! {
+            #(
+                const _: () = ::std::assert!(
+                    (#values) as #repr == (#values) as #range as #repr,
+                    "enum discriminants must fit in 64 bits"
+                );
+            )*
+        }
+    } else {
+        quote!()
+    };
     let repr = iter::repeat(repr);
     quote! {
+        #check
         _h5::types::TypeDescriptor::Enum(
             _h5::types::EnumType {
                 size: _h5::types::IntSize::#size,
@@ -130,17 +290,61 @@ fn find_repr(attrs: &[Attribute], expected: &[&str]) -> Option<Ident> {
     repr
 }
 
-fn find_hdf5_rename(attrs: &[Attribute]) -> Option<String> {
-    let mut rename = None;
-    let attr = attrs.iter().find(|a| a.path().is_ident("hdf5"))?;
-    attr.parse_nested_meta(|meta| {
-        if meta.path.is_ident("rename") && rename.is_none() {
-            rename = Some(meta.value()?.parse::<LitStr>()?.value());
+/// Options of `#[hdf5(...)]` attributes on struct fields and enum variants.
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+    with: Option<Type>,
+}
+
+impl FieldAttrs {
+    fn needs_proxy(&self) -> bool {
+        self.skip || self.with.is_some()
+    }
+}
+
+fn find_hdf5_attrs(attrs: &[Attribute]) -> FieldAttrs {
+    let mut out = FieldAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("hdf5")) {
+        let res = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                out.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                out.skip = true;
+            } else if meta.path.is_ident("with") || meta.path.is_ident("fixed_len") {
+                if out.with.is_some() {
+                    return Err(meta.error("conflicting `with` and `fixed_len` attributes"));
+                }
+                out.with = Some(if meta.path.is_ident("with") {
+                    meta.value()?.parse::<LitStr>()?.parse::<Type>()?
+                } else {
+                    let len = meta.value()?.parse::<Expr>()?;
+                    parse_quote!(_h5::types::FixedUnicode<{ #len }>)
+                });
+            } else {
+                return Err(meta.error("unknown `hdf5` attribute"));
+            }
+            Ok(())
+        });
+        if let Err(err) = res {
+            abort!(err.span(), "{}", err);
         }
-        Ok(())
-    })
-    .ok()?;
-    rename
+    }
+    out
+}
+
+/// Rejects field attributes that can only be used when deriving `H5Proxy`.
+fn check_no_proxy_attrs(fields: &[&syn::Field]) {
+    for field in fields {
+        if find_hdf5_attrs(&field.attrs).needs_proxy() {
+            abort!(
+                field,
+                "`skip`, `with` and `fixed_len` require a proxy type";
+                help = "use `#[derive(H5Proxy)]` instead of `#[derive(H5Type)]`"
+            );
+        }
+    }
 }
 
 fn pluck<'a, I, F, T, S>(iter: I, func: F) -> Vec<S>
@@ -152,6 +356,114 @@ where
     iter.map(func).collect()
 }
 
+/// Generates a packed proxy type and an `H5Proxy` impl for structs whose layout can't be
+/// described directly (no `repr(C)`/`repr(packed)`, or fields that are skipped or adapted).
+fn impl_proxy(ty: &Ident, data: &Data, attrs: &[Attribute], generics: &Generics) -> TokenStream {
+    let fields: Vec<_> = match *data {
+        Data::Struct(DataStruct { fields: Fields::Named(ref f), .. }) => f.named.iter().collect(),
+        Data::Struct(DataStruct { fields: Fields::Unnamed(ref f), .. }) => {
+            f.unnamed.iter().collect()
+        }
+        _ => abort!(ty, "`H5Proxy` can only be derived for structs with fields"),
+    };
+    let repr = find_repr(attrs, &["C", "packed", "transparent"]);
+    if repr.as_ref().map_or(false, |r| r == "transparent") {
+        abort!(ty, "`H5Proxy` cannot be derived for repr(transparent) structs");
+    }
+    if !generics.params.is_empty() {
+        abort!(ty, "`H5Proxy` cannot be derived for generic structs");
+    }
+    let field_attrs: Vec<_> = fields.iter().map(|f| find_hdf5_attrs(&f.attrs)).collect();
+    let packed = repr.map_or(false, |r| r == "packed");
+
+    let proxy = Ident::new(&format!("{}H5Proxy", ty), Span::call_site());
+    let (mut proxy_fields, mut names, mut types, mut to_proxy, mut from_proxy) =
+        (vec![], vec![], vec![], vec![], vec![]);
+    for (i, (field, attrs)) in fields.iter().zip(field_attrs).enumerate() {
+        if attrs.skip || is_phantom_data(&field.ty) {
+            from_proxy.push(quote!(::std::default::Default::default()));
+            continue;
+        }
+        let (member, name) = match field.ident {
+            Some(ref ident) => (quote!(#ident), ident.to_string()),
+            None => {
+                let index = Index::from(i);
+                (quote!(#index), i.to_string())
+            }
+        };
+        let proxy_field = Ident::new(&format!("field{}", i), Span::call_site());
+        let field_ty = &field.ty;
+        let convert = if let Some(adapter) = attrs.with {
+            let adapter = quote!(<#adapter as _h5::types::H5Adapter<#field_ty>>);
+            types.push(quote!(#adapter::Repr));
+            from_proxy.push(quote!(#adapter::from_repr(proxy.#proxy_field)));
+            quote!(#adapter::to_repr)
+        } else {
+            types.push(quote!(#field_ty));
+            from_proxy.push(quote!(proxy.#proxy_field));
+            quote!(::std::clone::Clone::clone)
+        };
+        to_proxy.push(if packed {
+            // fields of packed structs can't be borrowed, so a bitwise copy which is never
+            // dropped is passed instead (the field itself may not be `Copy`)
+            quote!({
+                let value = ::std::mem::ManuallyDrop::new(unsafe {
+                    ::std::ptr::read_unaligned(::std::ptr::addr_of!(self.#member))
+                });
+                #convert(&*value)
+            })
+        } else {
+            quote!(#convert(&self.#member))
+        });
+        names.push(attrs.rename.unwrap_or(name));
+        proxy_fields.push(proxy_field);
+    }
+    if proxy_fields.is_empty() {
+        abort!(ty, "cannot derive `H5Proxy` for structs with all fields skipped");
+    }
+    let from_proxy = match fields[0].ident {
+        Some(_) => {
+            let idents = fields.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!(Self { #(#idents: #from_proxy),* })
+        }
+        None => quote!(Self(#(#from_proxy),*)),
+    };
+    let body = impl_compound(&quote!(#proxy), &proxy_fields, &names, &types);
+    let dummy = Ident::new(&format!("_IMPL_H5PROXY_FOR_{}", ty), Span::call_site());
+    quote! {
+        #[allow(dead_code, unused_variables, unused_attributes, non_camel_case_types)]
+        const #dummy: () = {
+            extern crate hdf5 as _h5;
+
+            #[repr(packed)]
+            pub struct #proxy {
+                #(#proxy_fields: #types),*
+            }
+
+            #[automatically_derived]
+            unsafe impl _h5::types::H5Type for #proxy {
+                #[inline]
+                fn type_descriptor() -> _h5::types::TypeDescriptor {
+                    #body
+                }
+            }
+
+            #[automatically_derived]
+            impl _h5::types::H5Proxy for #ty {
+                type Proxy = #proxy;
+
+                fn to_proxy(&self) -> #proxy {
+                    #proxy { #(#proxy_fields: #to_proxy),* }
+                }
+
+                fn from_proxy(proxy: #proxy) -> Self {
+                    #from_proxy
+                }
+            }
+        };
+    }
+}
+
 fn impl_trait(
     ty: &Ident, data: &Data, attrs: &[Attribute], ty_generics: &TypeGenerics,
 ) -> TokenStream {
@@ -166,23 +478,25 @@ fn impl_trait(
                 if fields.is_empty() {
                     abort!(ty, "cannot derive `H5Type` for empty structs");
                 }
+                check_no_proxy_attrs(&fields);
 
-                let repr =
-                    find_repr(attrs, &["C", "packed", "transparent"]).unwrap_or_else(|| {
-                        abort!(ty,
-                    "`H5Type` requires repr(C), repr(packed) or repr(transparent) for structs")
-                    });
+                let repr = find_repr(attrs, &["C", "packed", "transparent"]).unwrap_or_else(|| {
+                    abort!(ty,
+                    "`H5Type` requires repr(C), repr(packed) or repr(transparent) for structs";
+                    help = "use `#[derive(H5Proxy)]` for repr(Rust) structs")
+                });
                 if repr == "transparent" {
                     assert_eq!(fields.len(), 1);
                     impl_transparent(&fields[0].ty)
                 } else {
                     let types = pluck(fields.iter(), |f| f.ty.clone());
                     let names = pluck(fields.iter(), |f| {
-                        find_hdf5_rename(&f.attrs)
+                        find_hdf5_attrs(&f.attrs)
+                            .rename
                             .unwrap_or_else(|| f.ident.as_ref().unwrap().to_string())
                     });
                     let fields = pluck(fields.iter(), |f| f.ident.clone().unwrap());
-                    impl_compound(ty, ty_generics, &fields, &names, &types)
+                    impl_compound(&quote!(#ty #ty_generics), &fields, &names, &types)
                 }
             }
             Fields::Unnamed(ref fields) => {
@@ -196,10 +510,12 @@ fn impl_trait(
                 if fields.is_empty() {
                     abort!(ty, "cannot derive `H5Type` for empty tuple structs")
                 }
+                check_no_proxy_attrs(&fields);
 
                 let repr =  find_repr(attrs, &["C", "packed", "transparent"]).unwrap_or_else(|| {
                         abort!(ty,
-                    "`H5Type` requires repr(C), repr(packed) or repr(transparent) for tuple structs")
+                    "`H5Type` requires repr(C), repr(packed) or repr(transparent) for tuple structs";
+                    help = "use `#[derive(H5Proxy)]` for repr(Rust) structs")
                     });
                 if repr == "transparent" {
                     assert_eq!(fields.len(), 1);
@@ -208,10 +524,12 @@ fn impl_trait(
                     let names = fields
                         .iter()
                         .enumerate()
-                        .map(|(n, f)| find_hdf5_rename(&f.attrs).unwrap_or_else(|| n.to_string()))
+                        .map(|(n, f)| {
+                            find_hdf5_attrs(&f.attrs).rename.unwrap_or_else(|| n.to_string())
+                        })
                         .collect::<Vec<_>>();
                     let types = pluck(fields.iter(), |f| f.ty.clone());
-                    impl_compound(ty, ty_generics, &index, &names, &types)
+                    impl_compound(&quote!(#ty #ty_generics), &index, &names, &types)
                 }
             }
         },
@@ -226,17 +544,28 @@ fn impl_trait(
                 abort!(ty, "cannot derive `H5Type` for empty enums")
             }
 
-            let enum_reprs =
-                &["i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "isize", "usize"];
+            let enum_reprs = &[
+                "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128", "isize",
+                "usize",
+            ];
             let repr = find_repr(attrs, enum_reprs).unwrap_or_else(|| {
                 abort!(ty, "`H5Type` can only be derived for enums with explicit representation")
             });
             let names = variants
                 .iter()
-                .map(|v| find_hdf5_rename(&v.attrs).unwrap_or_else(|| v.ident.to_string()))
+                .map(|v| {
+                    let attrs = find_hdf5_attrs(&v.attrs);
+                    if attrs.needs_proxy() {
+                        abort!(
+                            v.ident,
+                            "`skip`, `with` and `fixed_len` are not supported for enum variants"
+                        );
+                    }
+                    attrs.rename.unwrap_or_else(|| v.ident.to_string())
+                })
                 .collect::<Vec<_>>();
-            let values = pluck(variants.iter(), |v| v.discriminant.clone().unwrap().1);
-            impl_enum(&names, &values, &repr)
+            let variants = pluck(variants.iter(), |v| v.ident.clone());
+            impl_enum(ty, &names, &variants, &repr)
         }
         Data::Union(_) => {
             abort!(ty, "cannot derive `H5Type` for tagged unions");
@@ -0,0 +1,11 @@
+extern crate hdf5_derive;
+use hdf5_derive::H5Type;
+
+#[derive(H5Type)]
+#[repr(u8)]
+enum Foo {
+    #[hdf5(with = "u16")]
+    Bar = 1,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate hdf5_derive;
+use hdf5_derive::H5Type;
+
+#[derive(H5Type)]
+#[repr(C)]
+struct Foo {
+    #[hdf5(dataset)]
+    bar: i64,
+}
+
+fn main() {}
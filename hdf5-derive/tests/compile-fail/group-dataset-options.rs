@@ -0,0 +1,10 @@
+extern crate hdf5_derive;
+use hdf5_derive::H5Group;
+
+#[derive(H5Group)]
+struct Foo {
+    #[hdf5(attr, deflate = 4)]
+    bar: i64,
+}
+
+fn main() {}
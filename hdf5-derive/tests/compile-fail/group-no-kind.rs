@@ -0,0 +1,11 @@
+extern crate hdf5_derive;
+use hdf5_derive::H5Group;
+
+#[derive(H5Group)]
+struct Foo {
+    #[hdf5(attr)]
+    bar: i64,
+    baz: Vec<f64>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+extern crate hdf5_derive;
+use hdf5_derive::H5Group;
+
+#[derive(H5Group)]
+struct Foo(i64);
+
+fn main() {}
@@ -0,0 +1,9 @@
+extern crate hdf5_derive;
+use hdf5_derive::H5Proxy;
+
+#[derive(H5Proxy)]
+struct Foo<T> {
+    bar: T,
+}
+
+fn main() {}
@@ -103,10 +103,10 @@ fn test_compound_complex() {
         TD::Compound(CompoundType {
             fields: vec![
                 CompoundField::new("a", TD::FixedArray(Box::new(A::type_descriptor()), 4), 0, 0),
-                CompoundField::new("b", TD::FixedAscii(8), 64, 1),
+                CompoundField::new("b", TD::FixedAscii(8, StringPadding::NullPad), 64, 1),
                 CompoundField::new("c", TD::VarLenArray(Box::new(TD::Float(FloatSize::U8))), 72, 2),
                 CompoundField::new("d", TD::Boolean, 88, 3),
-                CompoundField::new("e", TD::FixedUnicode(7), 89, 4),
+                CompoundField::new("e", TD::FixedUnicode(7, StringPadding::NullPad), 89, 4),
                 CompoundField::new("f", TD::VarLenAscii, 96, 5),
                 CompoundField::new("g", TD::VarLenUnicode, 104, 6),
             ],
@@ -155,6 +155,30 @@ fn test_enum_simple() {
     assert_eq!(E1::type_descriptor().size(), 2);
 }
 
+#[derive(H5Type, Clone, Copy)]
+#[repr(i128)]
+#[allow(dead_code)]
+enum E2 {
+    X = -(1 << 40),
+    Y = i64::MAX as i128,
+}
+
+#[test]
+fn test_enum_i128() {
+    assert_eq!(
+        E2::type_descriptor(),
+        TD::Enum(EnumType {
+            size: IntSize::U16,
+            signed: true,
+            members: vec![
+                EnumMember { name: "X".into(), value: -(1i64 << 40) as _ },
+                EnumMember { name: "Y".into(), value: i64::MAX as _ },
+            ]
+        })
+    );
+    assert_eq!(E2::type_descriptor().size(), 16);
+}
+
 #[test]
 fn test_enum_base_type() {
     macro_rules! check_base_type {
@@ -189,6 +213,8 @@ fn test_enum_base_type() {
     check_base_type!(i16, true, 2);
     check_base_type!(i32, true, 4);
     check_base_type!(i64, true, 8);
+    check_base_type!(u128, false, 16);
+    check_base_type!(i128, true, 16);
     check_base_type!(usize, false, mem::size_of::<usize>());
     check_base_type!(isize, true, mem::size_of::<isize>());
 }
@@ -251,3 +277,118 @@ fn test_phantom_data() {
     assert_eq!(G3::<String>::type_descriptor(), C3::type_descriptor());
     assert_eq!(G4::<String>::type_descriptor(), C4::type_descriptor());
 }
+
+#[derive(H5Proxy, Clone, Debug, PartialEq)]
+struct R1 {
+    a: u8,
+    #[hdf5(rename = "bb")]
+    b: i64,
+    #[hdf5(skip)]
+    c: Option<Vec<u8>>,
+    #[hdf5(with = "VarLenUnicode")]
+    d: String,
+    #[hdf5(fixed_len = 4)]
+    e: String,
+}
+
+#[derive(H5Proxy, Clone, Debug, PartialEq)]
+struct R2(u16, #[hdf5(with = "VarLenArray<f32>")] Vec<f32>, #[hdf5(skip)] usize);
+
+#[derive(H5Proxy, Clone, Copy, Debug, PartialEq)]
+#[repr(packed)]
+struct R3 {
+    x: u8,
+    #[hdf5(skip)]
+    y: u32,
+    z: u64,
+}
+
+#[derive(H5Proxy)]
+#[repr(packed)]
+struct R4 {
+    x: u8,
+    #[hdf5(with = "VarLenUnicode")]
+    s: String,
+    v: VarLenArray<u16>,
+}
+
+#[test]
+fn test_compound_proxy() {
+    type P1 = <R1 as H5Proxy>::Proxy;
+    assert_eq!(
+        P1::type_descriptor(),
+        TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<u8>("a", 0, 0),
+                CompoundField::typed::<i64>("bb", 1, 1),
+                CompoundField::typed::<VarLenUnicode>("d", 9, 2),
+                CompoundField::typed::<FixedUnicode<4>>("e", 17, 3),
+            ],
+            size: 21,
+        })
+    );
+    let r1 = R1 { a: 1, b: -2, c: Some(vec![3]), d: "foo".into(), e: "αβγ".into() };
+    let r1 = R1::from_proxy(r1.to_proxy());
+    assert_eq!(r1, R1 { a: 1, b: -2, c: None, d: "foo".into(), e: "αβ".into() });
+
+    type P2 = <R2 as H5Proxy>::Proxy;
+    assert_eq!(
+        P2::type_descriptor(),
+        TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<u16>("0", 0, 0),
+                CompoundField::typed::<VarLenArray<f32>>("1", 2, 1),
+            ],
+            size: 2 + mem::size_of::<VarLenArray<f32>>(),
+        })
+    );
+    assert_eq!(R2::from_proxy(R2(1, vec![2.], 3).to_proxy()), R2(1, vec![2.], 0));
+
+    type P3 = <R3 as H5Proxy>::Proxy;
+    assert_eq!(P3::type_descriptor().size(), 9);
+    assert_eq!(R3::from_proxy(R3 { x: 1, y: 2, z: 3 }.to_proxy()), R3 { x: 1, y: 0, z: 3 });
+
+    // non-`Copy` fields of packed structs are neither moved nor dropped twice
+    let r4 = R4 { x: 1, s: "foo".into(), v: VarLenArray::from_slice(&[2, 3]) };
+    let proxy = r4.to_proxy();
+    let R4 { x, s, v } = r4;
+    assert_eq!((x, s.as_str(), v.as_slice()), (1, "foo", &[2, 3][..]));
+    let R4 { x, s, v } = R4::from_proxy(proxy);
+    assert_eq!((x, s.as_str(), v.as_slice()), (1, "foo", &[2, 3][..]));
+}
+
+#[derive(H5Type, Clone, Debug, PartialEq)]
+#[repr(C)]
+struct Tags {
+    names: VarLenArray<VarLenUnicode>,
+    nested: VarLenArray<VarLenArray<u8>>,
+}
+
+#[derive(H5Proxy, Clone, Debug, PartialEq)]
+struct RustTags {
+    id: u8,
+    names: VarLenArray<VarLenUnicode>,
+}
+
+#[test]
+fn test_varlen_non_copy() {
+    assert_eq!(
+        Tags::type_descriptor(),
+        TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::new("names", TD::VarLenArray(Box::new(TD::VarLenUnicode)), 0, 0),
+                CompoundField::new(
+                    "nested",
+                    TD::VarLenArray(Box::new(TD::VarLenArray(Box::new(TD::Unsigned(IntSize::U1))))),
+                    16,
+                    1
+                ),
+            ],
+            size: 32,
+        })
+    );
+    let names: VarLenArray<VarLenUnicode> =
+        VarLenArray::from_vec(vec!["x".parse().unwrap(), "yz".parse().unwrap()]);
+    let tags = RustTags { id: 1, names };
+    assert_eq!(RustTags::from_proxy(tags.to_proxy()), tags);
+}
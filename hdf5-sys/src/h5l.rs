@@ -3,8 +3,6 @@
 use std::fmt;
 use std::mem;
 
-pub use self::H5L_type_t::*;
-
 #[cfg(feature = "1.12.0")]
 use crate::h5o::H5O_token_t;
 use crate::internal_prelude::*;
@@ -15,16 +13,28 @@ pub const H5L_SAME_LOC: hid_t = 0;
 
 pub const H5L_LINK_CLASS_T_VERS: c_uint = 0;
 
-#[repr(C)]
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Debug)]
-pub enum H5L_type_t {
-    H5L_TYPE_ERROR = -1,
-    H5L_TYPE_HARD = 0,
-    H5L_TYPE_SOFT = 1,
-    H5L_TYPE_EXTERNAL = 64,
-    H5L_TYPE_MAX = 255,
+/// Link class identifier.
+///
+/// This is an integer newtype rather than an enum, since user-defined link classes may use
+/// any identifier between `H5L_TYPE_UD_MIN` and `H5L_TYPE_MAX`.
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct H5L_type_t(pub c_int);
+
+impl H5L_type_t {
+    pub const H5L_TYPE_ERROR: Self = Self(-1);
+    pub const H5L_TYPE_HARD: Self = Self(0);
+    pub const H5L_TYPE_SOFT: Self = Self(1);
+    pub const H5L_TYPE_EXTERNAL: Self = Self(64);
+    pub const H5L_TYPE_MAX: Self = Self(255);
 }
 
+pub const H5L_TYPE_ERROR: H5L_type_t = H5L_type_t::H5L_TYPE_ERROR;
+pub const H5L_TYPE_HARD: H5L_type_t = H5L_type_t::H5L_TYPE_HARD;
+pub const H5L_TYPE_SOFT: H5L_type_t = H5L_type_t::H5L_TYPE_SOFT;
+pub const H5L_TYPE_EXTERNAL: H5L_type_t = H5L_type_t::H5L_TYPE_EXTERNAL;
+pub const H5L_TYPE_MAX: H5L_type_t = H5L_type_t::H5L_TYPE_MAX;
+
 pub const H5L_TYPE_BUILTIN_MAX: H5L_type_t = H5L_TYPE_SOFT;
 pub const H5L_TYPE_UD_MIN: H5L_type_t = H5L_TYPE_EXTERNAL;
 
@@ -97,10 +107,10 @@ impl fmt::Debug for H5L_info2_t {
             H5L_TYPE_HARD => {
                 debug_struct.field("token", unsafe { &self.u.token });
             }
-            H5L_TYPE_SOFT | H5L_TYPE_EXTERNAL | H5L_TYPE_MAX => {
+            H5L_TYPE_ERROR => {}
+            _ => {
                 debug_struct.field("val_size", unsafe { &self.u.val_size });
             }
-            H5L_TYPE_ERROR => {}
         }
         debug_struct.finish()
     }
@@ -36,7 +36,12 @@ pub enum H5T_class_t {
     H5T_ENUM = 8,
     H5T_VLEN = 9,
     H5T_ARRAY = 10,
+    #[cfg(not(feature = "2.0.0"))]
     H5T_NCLASSES = 11,
+    #[cfg(feature = "2.0.0")]
+    H5T_COMPLEX = 11,
+    #[cfg(feature = "2.0.0")]
+    H5T_NCLASSES = 12,
 }
 
 #[cfg(feature = "1.8.6")]
@@ -567,3 +572,8 @@ extern "C" {
         name: *const c_char, tapl_id: hid_t, es_id: hid_t,
     ) -> hid_t;
 }
+
+#[cfg(feature = "2.0.0")]
+extern "C" {
+    pub fn H5Tcomplex_create(base_type_id: hid_t) -> hid_t;
+}
@@ -5,8 +5,14 @@ use std::ops::Deref;
 use std::ptr;
 use std::slice;
 
+/// Variable-length array of `H5Type` elements, with memory layout matching `hvl_t`.
+///
+/// The elements are owned: they are dropped along with the array, so arrays of types that
+/// own memory themselves (e.g. `VarLenArray<VarLenUnicode>` or nested `VarLenArray`s) free
+/// all nested allocations. Memory is allocated and freed with the `HDF5` allocator if
+/// `USING_H5_ALLOCATOR` is set, and with the C allocator otherwise.
 #[repr(C)]
-pub struct VarLenArray<T: Copy> {
+pub struct VarLenArray<T> {
     len: usize,
     ptr: *const T,
     tag: PhantomData<T>,
@@ -23,10 +29,35 @@ impl<T: Copy> VarLenArray<T> {
         };
         Self { len, ptr: ptr as *const _, tag: PhantomData }
     }
+}
 
+impl<T: Clone> VarLenArray<T> {
     #[inline]
     pub fn from_slice(arr: &[T]) -> Self {
-        unsafe { Self::from_parts(arr.as_ptr(), arr.len()) }
+        Self::from_iter_exact(arr.iter().cloned())
+    }
+}
+
+impl<T> VarLenArray<T> {
+    /// Creates an array by moving the elements out of a vector.
+    #[inline]
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        Self::from_iter_exact(vec.into_iter())
+    }
+
+    fn from_iter_exact<I: ExactSizeIterator<Item = T>>(iter: I) -> Self {
+        let len = iter.len();
+        if len == 0 || mem::size_of::<T>() == 0 {
+            return Self { len: 0, ptr: ptr::null(), tag: PhantomData };
+        }
+        let dst: *mut T = unsafe { crate::malloc(len * mem::size_of::<T>()).cast() };
+        let mut arr = Self { len: 0, ptr: dst, tag: PhantomData };
+        for item in iter.take(len) {
+            // the length is bumped as we go so a panicking iterator doesn't leak or double-drop
+            unsafe { dst.add(arr.len).write(item) };
+            arr.len += 1;
+        }
+        arr
     }
 
     #[inline]
@@ -48,12 +79,27 @@ impl<T: Copy> VarLenArray<T> {
     pub fn as_slice(&self) -> &[T] {
         self
     }
+
+    /// Moves the elements into a vector, freeing the array's own memory.
+    pub fn into_vec(self) -> Vec<T> {
+        let this = mem::ManuallyDrop::new(self);
+        let mut vec = Vec::with_capacity(this.len());
+        unsafe {
+            if !this.ptr.is_null() {
+                ptr::copy_nonoverlapping(this.ptr, vec.as_mut_ptr(), this.len());
+                vec.set_len(this.len());
+                crate::free(this.ptr as *mut _);
+            }
+        }
+        vec
+    }
 }
 
-impl<T: Copy> Drop for VarLenArray<T> {
+impl<T> Drop for VarLenArray<T> {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
             unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr as *mut T, self.len));
                 crate::free(self.ptr as *mut _);
             }
             self.ptr = ptr::null();
@@ -64,14 +110,14 @@ impl<T: Copy> Drop for VarLenArray<T> {
     }
 }
 
-impl<T: Copy> Clone for VarLenArray<T> {
+impl<T: Clone> Clone for VarLenArray<T> {
     #[inline]
     fn clone(&self) -> Self {
         Self::from_slice(self)
     }
 }
 
-impl<T: Copy> Deref for VarLenArray<T> {
+impl<T> Deref for VarLenArray<T> {
     type Target = [T];
 
     #[inline]
@@ -84,58 +130,65 @@ impl<T: Copy> Deref for VarLenArray<T> {
     }
 }
 
-impl<'a, T: Copy> From<&'a [T]> for VarLenArray<T> {
+impl<'a, T: Clone> From<&'a [T]> for VarLenArray<T> {
     #[inline]
     fn from(arr: &[T]) -> Self {
         Self::from_slice(arr)
     }
 }
 
-impl<T: Copy> From<VarLenArray<T>> for Vec<T> {
+impl<T> From<Vec<T>> for VarLenArray<T> {
+    #[inline]
+    fn from(vec: Vec<T>) -> Self {
+        Self::from_vec(vec)
+    }
+}
+
+impl<T> From<VarLenArray<T>> for Vec<T> {
     #[inline]
     fn from(v: VarLenArray<T>) -> Self {
-        v.iter().copied().collect()
+        v.into_vec()
     }
 }
 
-impl<T: Copy, const N: usize> From<[T; N]> for VarLenArray<T> {
+impl<T, const N: usize> From<[T; N]> for VarLenArray<T> {
     #[inline]
     fn from(arr: [T; N]) -> Self {
-        unsafe { Self::from_parts(arr.as_ptr(), arr.len()) }
+        Self::from_iter_exact(arr.into_iter())
     }
 }
 
-impl<T: Copy> Default for VarLenArray<T> {
+impl<T> Default for VarLenArray<T> {
     #[inline]
     fn default() -> Self {
-        unsafe { Self::from_parts(ptr::null(), 0) }
+        Self { len: 0, ptr: ptr::null(), tag: PhantomData }
     }
 }
 
-impl<T: Copy + PartialEq> PartialEq for VarLenArray<T> {
+impl<T: PartialEq> PartialEq for VarLenArray<T> {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         self.as_slice() == other.as_slice()
     }
 }
 
-impl<T: Copy + Eq> Eq for VarLenArray<T> {}
+impl<T: Eq> Eq for VarLenArray<T> {}
 
-impl<T: Copy + PartialEq> PartialEq<[T]> for VarLenArray<T> {
+impl<T: PartialEq> PartialEq<[T]> for VarLenArray<T> {
     #[inline]
     fn eq(&self, other: &[T]) -> bool {
         self.as_slice() == other
     }
 }
 
-impl<T: Copy + PartialEq, const N: usize> PartialEq<[T; N]> for VarLenArray<T> {
+impl<T: PartialEq, const N: usize> PartialEq<[T; N]> for VarLenArray<T> {
     #[inline]
     fn eq(&self, other: &[T; N]) -> bool {
         self.as_slice() == other
     }
 }
 
-impl<T: Copy + fmt::Debug> fmt::Debug for VarLenArray<T> {
+impl<T: fmt::Debug> fmt::Debug for VarLenArray<T> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.as_slice().fmt(f)
@@ -143,9 +196,9 @@ impl<T: Copy + fmt::Debug> fmt::Debug for VarLenArray<T> {
 }
 
 // Safety: Memory backed by `VarLenArray` can be accessed and freed from any thread
-unsafe impl<T: Copy + Send> Send for VarLenArray<T> {}
+unsafe impl<T: Send> Send for VarLenArray<T> {}
 // Safety: `VarLenArray` has no interior mutability
-unsafe impl<T: Copy + Sync> Sync for VarLenArray<T> {}
+unsafe impl<T: Sync> Sync for VarLenArray<T> {}
 
 #[cfg(test)]
 pub mod tests {
@@ -186,4 +239,31 @@ pub mod tests {
         let v: Vec<_> = a.iter().cloned().collect();
         assert_eq!(v, vec![1, 2, 3]);
     }
+
+    #[test]
+    pub fn test_vla_non_copy() {
+        use crate::string::VarLenUnicode;
+        use std::rc::Rc;
+
+        let s: Vec<VarLenUnicode> = vec!["a".parse().unwrap(), "βγ".parse().unwrap()];
+        let a = VarLenArray::from_slice(&s);
+        let nested = VarLenArray::from([a.clone(), VarLenArray::default(), a.clone()]);
+        assert_eq!(format!("{:?}", nested), r#"[["a", "βγ"], [], ["a", "βγ"]]"#);
+        assert_eq!(nested.clone(), nested);
+        let v: Vec<VarLenArray<VarLenUnicode>> = nested.into();
+        assert_eq!(v[2].as_slice(), s.as_slice());
+        assert_eq!(VarLenArray::from_vec(s).into_vec().len(), 2);
+
+        // elements are dropped exactly once
+        let rc = Rc::new(());
+        let a = VarLenArray::from(vec![rc.clone(), rc.clone()]);
+        assert_eq!(Rc::strong_count(&rc), 3);
+        let b = a.clone();
+        assert_eq!(Rc::strong_count(&rc), 5);
+        drop(a);
+        let v = b.into_vec();
+        assert_eq!(Rc::strong_count(&rc), 3);
+        drop(v);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 }
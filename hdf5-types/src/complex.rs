@@ -4,21 +4,53 @@ use std::mem::size_of;
 
 use num_complex::Complex;
 
-unsafe impl<T: H5Type> H5Type for Complex<T> {
-    fn type_descriptor() -> TypeDescriptor {
+/// Layout of complex numbers in a file.
+///
+/// The layout is chosen when writing, by passing [`type_descriptor`](Self::type_descriptor)
+/// to one of the `*_as()` builder methods. Once `hdf5::Datatype::register_complex_conversions()`
+/// has been called, `Complex<T>` can be read from data stored in any of these layouts (as well
+/// as from compound types whose fields are named `re`/`im` or ordered differently).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ComplexLayout {
+    /// Compound type with fields `r` and `i`, as used by h5py (default).
+    #[default]
+    RI,
+    /// Compound type with fields `real` and `imag`.
+    RealImag,
+    /// Two-element array of the real and imaginary parts.
+    Array,
+    /// Native complex datatype class (requires HDF5 2.0 or later and a float base type).
+    Native,
+}
+
+impl ComplexLayout {
+    /// Returns the descriptor of `Complex<T>` stored in this layout.
+    pub fn type_descriptor<T: H5Type>(self) -> TypeDescriptor {
+        let (re, im) = match self {
+            Self::RI => ("r", "i"),
+            Self::RealImag => ("real", "imag"),
+            Self::Array => return TypeDescriptor::FixedArray(Box::new(T::type_descriptor()), 2),
+            Self::Native => return TypeDescriptor::Complex(Box::new(T::type_descriptor())),
+        };
         // Complex<T> should be FFI-equivalent to [T; 2]
         // https://docs.rs/num-complex/0.4.3/num_complex/struct.Complex.html#representation-and-foreign-function-interface-compatibility
         TypeDescriptor::Compound(CompoundType {
             fields: vec![
-                // Compatible with h5py definition of complex
-                CompoundField::typed::<T>("r", 0, 0),
-                CompoundField::typed::<T>("i", size_of::<T>(), 1),
+                CompoundField::typed::<T>(re, 0, 0),
+                CompoundField::typed::<T>(im, size_of::<T>(), 1),
             ],
             size: size_of::<T>() * 2,
         })
     }
 }
 
+unsafe impl<T: H5Type> H5Type for Complex<T> {
+    fn type_descriptor() -> TypeDescriptor {
+        // Compatible with h5py definition of complex
+        ComplexLayout::RI.type_descriptor::<T>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,6 +65,27 @@ mod tests {
         assert_eq!(Complex64::type_descriptor().size(), size_of::<Complex64>());
     }
 
+    #[test]
+    fn layouts() {
+        use crate::h5type::FloatSize;
+
+        let f8 = || Box::new(TypeDescriptor::Float(FloatSize::U8));
+        assert_eq!(ComplexLayout::default().type_descriptor::<f64>(), Complex64::type_descriptor());
+        let td = ComplexLayout::RealImag.type_descriptor::<f64>();
+        let names = match td {
+            TypeDescriptor::Compound(ref tp) => tp.fields.iter().map(|f| &*f.name).collect(),
+            _ => vec![],
+        };
+        assert_eq!(names, ["real", "imag"]);
+        assert_eq!(td.size(), 16);
+        let td = ComplexLayout::Array.type_descriptor::<f64>();
+        assert_eq!(td, TypeDescriptor::FixedArray(f8(), 2));
+        let td = ComplexLayout::Native.type_descriptor::<f64>();
+        assert_eq!(td, TypeDescriptor::Complex(f8()));
+        assert_eq!(td.size(), size_of::<Complex64>());
+        assert_eq!(format!("{}", td), "complex float64");
+    }
+
     #[test]
     fn alignment() {
         use std::ptr::addr_of;
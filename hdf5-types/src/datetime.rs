@@ -0,0 +1,594 @@
+use std::fmt::{self, Display};
+use std::mem;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::h5type::{H5Type, TypeDescriptor};
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Unit of time of a CF-style `units` attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeUnit {
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl TimeUnit {
+    /// Length of the unit in nanoseconds.
+    pub fn nanos(self) -> i64 {
+        match self {
+            Self::Days => SECS_PER_DAY * 1_000_000_000,
+            Self::Hours => 3_600_000_000_000,
+            Self::Minutes => 60_000_000_000,
+            Self::Seconds => 1_000_000_000,
+            Self::Milliseconds => 1_000_000,
+            Self::Microseconds => 1_000,
+            Self::Nanoseconds => 1,
+        }
+    }
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Self::Days => "days",
+            Self::Hours => "hours",
+            Self::Minutes => "minutes",
+            Self::Seconds => "seconds",
+            Self::Milliseconds => "milliseconds",
+            Self::Microseconds => "microseconds",
+            Self::Nanoseconds => "nanoseconds",
+        })
+    }
+}
+
+impl FromStr for TimeUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "days" | "day" | "d" => Self::Days,
+            "hours" | "hour" | "hrs" | "hr" | "h" => Self::Hours,
+            "minutes" | "minute" | "mins" | "min" => Self::Minutes,
+            "seconds" | "second" | "secs" | "sec" | "s" => Self::Seconds,
+            "milliseconds" | "millisecond" | "msecs" | "msec" | "ms" => Self::Milliseconds,
+            "microseconds" | "microsecond" | "usecs" | "usec" | "us" => Self::Microseconds,
+            "nanoseconds" | "nanosecond" | "nsecs" | "nsec" | "ns" => Self::Nanoseconds,
+            _ => return Err(format!("invalid time unit: {s:?}")),
+        })
+    }
+}
+
+/// Units of a time variable following the CF conventions.
+///
+/// Time points are counted in `unit` since a reference time (`"seconds since 1970-01-01"`),
+/// durations are plain counts of `unit` (`"hours"`). Dates use the proleptic Gregorian
+/// calendar; the reference time may have a time zone offset, e.g.
+/// `"days since 2000-01-01T12:00:00+01:00"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimeUnits {
+    pub unit: TimeUnit,
+    /// Reference time in nanoseconds since the Unix epoch, or `None` for durations.
+    pub epoch: Option<i128>,
+}
+
+impl TimeUnits {
+    /// Units of time points counted since the Unix epoch.
+    pub const fn since_unix_epoch(unit: TimeUnit) -> Self {
+        Self { unit, epoch: Some(0) }
+    }
+
+    /// Units of durations.
+    pub const fn duration(unit: TimeUnit) -> Self {
+        Self { unit, epoch: None }
+    }
+
+    /// Whether these are units of time points rather than durations.
+    pub fn is_time_point(&self) -> bool {
+        self.epoch.is_some()
+    }
+
+    /// Converts a stored count into nanoseconds since the Unix epoch (or a duration).
+    pub fn to_nanos(&self, value: i64) -> i128 {
+        self.epoch.unwrap_or(0) + i128::from(value) * i128::from(self.unit.nanos())
+    }
+
+    /// Converts a stored floating-point count, rounding to the nearest nanosecond.
+    pub fn float_to_nanos(&self, value: f64) -> Option<i128> {
+        let nanos = (value * self.unit.nanos() as f64).round();
+        if !nanos.is_finite() || nanos.abs() >= i128::MAX as f64 / 2. {
+            return None;
+        }
+        Some(self.epoch.unwrap_or(0) + nanos as i128)
+    }
+
+    /// Converts nanoseconds since the Unix epoch (or a duration) into a stored count,
+    /// rounding towards negative infinity; returns `None` if it doesn't fit into `i64`.
+    pub fn from_nanos(&self, nanos: i128) -> Option<i64> {
+        let count = (nanos - self.epoch.unwrap_or(0)).div_euclid(i128::from(self.unit.nanos()));
+        i64::try_from(count).ok()
+    }
+
+    /// Converts nanoseconds since the Unix epoch (or a duration) into a floating-point count.
+    pub fn float_from_nanos(&self, nanos: i128) -> f64 {
+        (nanos - self.epoch.unwrap_or(0)) as f64 / self.unit.nanos() as f64
+    }
+}
+
+impl Display for TimeUnits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.unit)?;
+        if let Some(epoch) = self.epoch {
+            let (secs, nanos) = (epoch.div_euclid(NANOS_PER_SEC), epoch.rem_euclid(NANOS_PER_SEC));
+            let secs_per_day = i128::from(SECS_PER_DAY);
+            let (days, secs) = (secs.div_euclid(secs_per_day), secs.rem_euclid(secs_per_day));
+            let (year, month, day) = civil_from_days(days as i64);
+            let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+            write!(f, " since {year:04}-{month:02}-{day:02} {h:02}:{m:02}:{s:02}")?;
+            if nanos != 0 {
+                let frac = format!("{nanos:09}");
+                write!(f, ".{}", frac.trim_end_matches('0'))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TimeUnits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (unit, rest) = s.split_once(char::is_whitespace).unwrap_or((s, ""));
+        let unit = unit.parse()?;
+        let rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(Self::duration(unit));
+        }
+        let since = rest.get(..5).filter(|since| since.eq_ignore_ascii_case("since"));
+        let epoch = since
+            .and_then(|_| parse_epoch(&rest[5..]))
+            .ok_or_else(|| format!("invalid time units: {s:?}"))?;
+        Ok(Self { unit, epoch: Some(epoch) })
+    }
+}
+
+/// Parses a reference time like `1970-01-01`, `1970-1-1 0:0:0.5` or `2000-01-01T00:00:00Z`.
+fn parse_epoch(s: &str) -> Option<i128> {
+    fn int<T: FromStr>(s: &str) -> Option<T> {
+        s.bytes().all(|c| c.is_ascii_digit()).then(|| s.parse().ok()).flatten()
+    }
+
+    let s = s.trim();
+    let date_end = s.find(|c: char| c.is_whitespace() || c == 'T' || c == 't').unwrap_or(s.len());
+    let mut date = s[..date_end].splitn(3, '-');
+    let (year, month, day) = (date.next()?, date.next()?, date.next()?);
+    let (year, month, day): (i64, i64, i64) = (int(year)?, int(month)?, int(day)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let mut nanos = i128::from(days_from_civil(year, month, day) * SECS_PER_DAY) * NANOS_PER_SEC;
+
+    let mut rest =
+        s[date_end..].trim_start_matches(|c: char| c.is_whitespace() || c == 'T' || c == 't');
+    if rest.starts_with(|c: char| c.is_ascii_digit()) {
+        let time_end =
+            rest.find(|c: char| !c.is_ascii_digit() && c != ':' && c != '.').unwrap_or(rest.len());
+        let mut time = rest[..time_end].splitn(3, ':');
+        let hours: i128 = int(time.next()?)?;
+        let minutes: i128 = time.next().map_or(Some(0), int)?;
+        let secs = time.next().unwrap_or("0");
+        let (secs, frac) = secs.split_once('.').unwrap_or((secs, ""));
+        nanos += (hours * 3600 + minutes * 60 + int::<i128>(secs)?) * NANOS_PER_SEC;
+        if !frac.is_empty() {
+            let digits = &frac[..frac.len().min(9)];
+            nanos += int::<i128>(digits)? * 10_i128.pow(9 - digits.len() as u32);
+        }
+        rest = rest[time_end..].trim_start();
+    }
+
+    if !(rest.is_empty() || ["z", "utc", "gmt"].iter().any(|tz| rest.eq_ignore_ascii_case(tz))) {
+        let sign = match rest.as_bytes()[0] {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+        let offset = &rest[1..];
+        if !offset.bytes().all(|c| c.is_ascii_digit() || c == b':') {
+            return None;
+        }
+        let (hours, minutes) = match offset.split_once(':') {
+            Some((h, m)) => (h, m),
+            None if offset.len() > 2 => offset.split_at(offset.len() - 2),
+            None => (offset, "0"),
+        };
+        let offset: i128 = int::<i128>(hours)? * 3600 + int::<i128>(minutes)? * 60;
+        nanos -= sign * offset * NANOS_PER_SEC;
+    }
+    Some(nanos)
+}
+
+/// Number of days since 1970-01-01 in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of `days_from_civil()`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// Prefix of the tags of the opaque in-memory datatypes of `H5Time` types.
+const TIME_TAG_PREFIX: &str = "rust time: ";
+
+/// A point in time or a duration stored as a 64-bit integer count of time units, described
+/// by a CF-style `units` attribute (see `TimeUnits`).
+///
+/// The `H5Type` of a time type is an opaque datatype holding the value as is; the `hdf5`
+/// crate registers conversion paths between it and native `int64` counts of `Self::UNITS`,
+/// so that time values can be read and written like any other type. Datasets created from
+/// time values are stored as `int64` along with a `units` attribute. The conversion fails
+/// for values whose count doesn't fit into `i64`, or which are out of range of the type.
+/// Use `Dataset::read_times()` and `Dataset::write_times()` for datasets in other units.
+pub trait H5Time: H5Type + Copy {
+    /// Name of the type, used in the tag of its opaque datatype.
+    const NAME: &'static str;
+
+    /// Units used when writing values of this type.
+    const UNITS: TimeUnits;
+
+    /// Converts the value into nanoseconds since the Unix epoch (or a duration in nanoseconds).
+    fn to_nanos(&self) -> i128;
+
+    /// Creates a value from nanoseconds since the Unix epoch (or a duration in nanoseconds),
+    /// returning `None` if it's out of range.
+    fn from_nanos(nanos: i128) -> Option<Self>;
+}
+
+fn time_type_descriptor<T: H5Time>() -> TypeDescriptor {
+    TypeDescriptor::Opaque {
+        size: mem::size_of::<T>(),
+        tag: format!("{TIME_TAG_PREFIX}{}", T::NAME),
+    }
+}
+
+/// Conversion of an `H5Time` type from and to counts of its units, used by the `hdf5` crate
+/// to implement the conversion paths of its opaque datatype.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeConversion {
+    /// Name of the type, see `H5Time::NAME`.
+    pub name: &'static str,
+    /// Size of a value in bytes.
+    pub size: usize,
+    /// Units of the counts, see `H5Time::UNITS`.
+    pub units: TimeUnits,
+    /// Reads a (possibly unaligned) value and converts it into a count of `units`, returning
+    /// `None` if it doesn't fit into `i64`.
+    pub to_count: unsafe fn(*const u8) -> Option<i64>,
+    /// Converts a count of `units` into a value and writes it (possibly unaligned), returning
+    /// `None` if it's out of range of the type.
+    pub from_count: unsafe fn(i64, *mut u8) -> Option<()>,
+}
+
+unsafe fn to_count<T: H5Time>(ptr: *const u8) -> Option<i64> {
+    T::UNITS.from_nanos(ptr.cast::<T>().read_unaligned().to_nanos())
+}
+
+unsafe fn from_count<T: H5Time>(count: i64, ptr: *mut u8) -> Option<()> {
+    ptr.cast::<T>().write_unaligned(T::from_nanos(T::UNITS.to_nanos(count))?);
+    Some(())
+}
+
+impl TimeConversion {
+    const fn of<T: H5Time>() -> Self {
+        Self {
+            name: T::NAME,
+            size: mem::size_of::<T>(),
+            units: T::UNITS,
+            to_count: to_count::<T>,
+            from_count: from_count::<T>,
+        }
+    }
+
+    /// Returns the conversion of the time type with the given type descriptor, if any.
+    pub fn find(desc: &TypeDescriptor) -> Option<&'static Self> {
+        let (size, tag) = match desc {
+            TypeDescriptor::Opaque { size, tag } => (size, tag),
+            _ => return None,
+        };
+        let name = tag.strip_prefix(TIME_TAG_PREFIX)?;
+        STD_CONVERSIONS
+            .iter()
+            .chain(CHRONO_CONVERSIONS)
+            .chain(TIME_CONVERSIONS)
+            .find(|conv| conv.name == name && conv.size == *size)
+    }
+}
+
+const TIME_POINT_UNITS: TimeUnits = TimeUnits::since_unix_epoch(TimeUnit::Microseconds);
+const DURATION_UNITS: TimeUnits = TimeUnits::duration(TimeUnit::Microseconds);
+
+fn duration_from_nanos(nanos: i128) -> Option<Duration> {
+    let secs = u64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+    Some(Duration::new(secs, nanos.rem_euclid(NANOS_PER_SEC) as u32))
+}
+
+macro_rules! impl_h5time {
+    ($ty:ty, $name:expr, $units:expr, |$v:ident| $to_nanos:expr, |$n:ident| $from_nanos:expr) => {
+        unsafe impl H5Type for $ty {
+            fn type_descriptor() -> TypeDescriptor {
+                time_type_descriptor::<Self>()
+            }
+        }
+
+        impl H5Time for $ty {
+            const NAME: &'static str = $name;
+            const UNITS: TimeUnits = $units;
+
+            fn to_nanos(&self) -> i128 {
+                let $v = self;
+                $to_nanos
+            }
+
+            fn from_nanos($n: i128) -> Option<Self> {
+                $from_nanos
+            }
+        }
+    };
+}
+
+impl_h5time!(
+    SystemTime,
+    "std::time::SystemTime",
+    TIME_POINT_UNITS,
+    |t| match t.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_nanos() as i128,
+        Err(err) => -(err.duration().as_nanos() as i128),
+    },
+    |nanos| if nanos >= 0 {
+        UNIX_EPOCH.checked_add(duration_from_nanos(nanos)?)
+    } else {
+        UNIX_EPOCH.checked_sub(duration_from_nanos(-nanos)?)
+    }
+);
+
+impl_h5time!(Duration, "std::time::Duration", DURATION_UNITS, |d| d.as_nanos() as i128, |nanos| {
+    duration_from_nanos(nanos)
+});
+
+const STD_CONVERSIONS: &[TimeConversion] =
+    &[TimeConversion::of::<SystemTime>(), TimeConversion::of::<Duration>()];
+
+#[cfg(feature = "chrono")]
+use self::chrono_impls::CONVERSIONS as CHRONO_CONVERSIONS;
+#[cfg(not(feature = "chrono"))]
+const CHRONO_CONVERSIONS: &[TimeConversion] = &[];
+
+#[cfg(feature = "time")]
+use self::time_impls::CONVERSIONS as TIME_CONVERSIONS;
+#[cfg(not(feature = "time"))]
+const TIME_CONVERSIONS: &[TimeConversion] = &[];
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+
+    use super::{
+        time_type_descriptor, H5Time, H5Type, TimeConversion, TimeUnits, TypeDescriptor,
+        DURATION_UNITS, NANOS_PER_SEC, TIME_POINT_UNITS,
+    };
+
+    pub(super) const CONVERSIONS: &[TimeConversion] = &[
+        TimeConversion::of::<DateTime<Utc>>(),
+        TimeConversion::of::<NaiveDateTime>(),
+        TimeConversion::of::<TimeDelta>(),
+    ];
+
+    fn datetime_from_nanos(nanos: i128) -> Option<DateTime<Utc>> {
+        let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+        DateTime::from_timestamp(secs, nanos.rem_euclid(NANOS_PER_SEC) as u32)
+    }
+
+    impl_h5time!(
+        DateTime<Utc>,
+        "chrono::DateTime<Utc>",
+        TIME_POINT_UNITS,
+        |t| i128::from(t.timestamp()) * NANOS_PER_SEC + i128::from(t.timestamp_subsec_nanos()),
+        |nanos| datetime_from_nanos(nanos)
+    );
+
+    // naive date-times are assumed to be in UTC
+    impl_h5time!(
+        NaiveDateTime,
+        "chrono::NaiveDateTime",
+        TIME_POINT_UNITS,
+        |t| H5Time::to_nanos(&t.and_utc()),
+        |nanos| datetime_from_nanos(nanos).map(|t| t.naive_utc())
+    );
+
+    impl_h5time!(
+        TimeDelta,
+        "chrono::TimeDelta",
+        DURATION_UNITS,
+        |d| i128::from(d.num_seconds()) * NANOS_PER_SEC + i128::from(d.subsec_nanos()),
+        |nanos| {
+            let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+            TimeDelta::new(secs, nanos.rem_euclid(NANOS_PER_SEC) as u32)
+        }
+    );
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{Duration, OffsetDateTime, PrimitiveDateTime};
+
+    use super::{
+        time_type_descriptor, H5Time, H5Type, TimeConversion, TimeUnits, TypeDescriptor,
+        DURATION_UNITS, NANOS_PER_SEC, TIME_POINT_UNITS,
+    };
+
+    pub(super) const CONVERSIONS: &[TimeConversion] = &[
+        TimeConversion::of::<OffsetDateTime>(),
+        TimeConversion::of::<PrimitiveDateTime>(),
+        TimeConversion::of::<Duration>(),
+    ];
+
+    // values are read back with a UTC offset
+    impl_h5time!(
+        OffsetDateTime,
+        "time::OffsetDateTime",
+        TIME_POINT_UNITS,
+        |t| t.unix_timestamp_nanos(),
+        |nanos| OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+    );
+
+    // primitive date-times are assumed to be in UTC
+    impl_h5time!(
+        PrimitiveDateTime,
+        "time::PrimitiveDateTime",
+        TIME_POINT_UNITS,
+        |t| t.assume_utc().unix_timestamp_nanos(),
+        |nanos| {
+            let t = OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()?;
+            Some(PrimitiveDateTime::new(t.date(), t.time()))
+        }
+    );
+
+    impl_h5time!(Duration, "time::Duration", DURATION_UNITS, |d| d.whole_nanoseconds(), |nanos| {
+        let secs = i64::try_from(nanos / NANOS_PER_SEC).ok()?;
+        Some(Duration::new(secs, (nanos % NANOS_PER_SEC) as i32))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::{H5Time, TimeConversion, TimeUnit, TimeUnits, NANOS_PER_SEC, SECS_PER_DAY};
+
+    fn to_count<T: H5Time>(value: T) -> Option<i64> {
+        let conv = TimeConversion::find(&T::type_descriptor()).unwrap();
+        assert_eq!(conv.units, T::UNITS);
+        unsafe { (conv.to_count)((&value as *const T).cast()) }
+    }
+
+    fn from_count<T: H5Time>(count: i64) -> Option<T> {
+        let conv = TimeConversion::find(&T::type_descriptor()).unwrap();
+        let mut value = std::mem::MaybeUninit::<T>::uninit();
+        unsafe { (conv.from_count)(count, value.as_mut_ptr().cast()).map(|_| value.assume_init()) }
+    }
+
+    #[test]
+    pub fn test_time_units() {
+        let units: TimeUnits = "seconds since 1970-01-01".parse().unwrap();
+        assert_eq!(units, TimeUnits::since_unix_epoch(TimeUnit::Seconds));
+        assert_eq!(units.to_string(), "seconds since 1970-01-01 00:00:00");
+        assert_eq!("hours".parse(), Ok(TimeUnits::duration(TimeUnit::Hours)));
+        assert_eq!("  ms ".parse(), Ok(TimeUnits::duration(TimeUnit::Milliseconds)));
+
+        let day = i128::from(SECS_PER_DAY) * NANOS_PER_SEC;
+        for (s, epoch) in [
+            ("days since 2000-01-01", 10_957 * day),
+            ("days since 2000-1-1 0:0:0", 10_957 * day),
+            ("days since 1900-01-01 00:00:00.0", -25_567 * day),
+            ("Days Since 2000-01-01T12:00:00Z", 10_957 * day + day / 2),
+            ("days since 2000-01-01 12:00:00 UTC", 10_957 * day + day / 2),
+            ("days since 2000-01-01 12:00:00 +01:00", 10_957 * day + day / 2 - day / 24),
+            ("days since 2000-01-01T12:00:00-0130", 10_957 * day + day / 2 + day / 16),
+            ("days since 1970-01-01 00:00:01.25", 1_250_000_000),
+            ("days since 1969-12-31 23:59:59", -1_000_000_000),
+        ] {
+            let units: TimeUnits = s.parse().unwrap();
+            assert_eq!(units.epoch, Some(epoch), "{}", s);
+            assert_eq!(units.to_string().parse::<TimeUnits>().unwrap(), units, "{}", s);
+        }
+        assert_eq!(
+            "us since 1600-02-29 01:02:03.000004".parse::<TimeUnits>().unwrap().to_string(),
+            "microseconds since 1600-02-29 01:02:03.000004"
+        );
+        for s in [
+            "",
+            "weeks",
+            "days after 1970-01-01",
+            "days since",
+            "days since 1970-13-01",
+            "days since 2000-01-01 12:00:00 +0é0",
+        ] {
+            assert!(s.parse::<TimeUnits>().is_err(), "{}", s);
+        }
+
+        let units: TimeUnits = "hours since 1970-01-02".parse().unwrap();
+        assert_eq!(units.to_nanos(-24), 0);
+        assert_eq!(units.from_nanos(0), Some(-24));
+        assert_eq!(units.from_nanos(-1), Some(-25));
+        assert_eq!(units.float_to_nanos(-23.5), Some(1_800_000_000_000));
+        assert_eq!(units.float_from_nanos(1_800_000_000_000), -23.5);
+        assert_eq!(units.float_to_nanos(f64::NAN), None);
+    }
+
+    #[test]
+    pub fn test_std_times() {
+        let t = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+        assert_eq!(to_count(t), Some(1_700_000_000_123_456));
+        assert_eq!(from_count(1_700_000_000_123_456), Some(t - Duration::from_nanos(789)));
+        let t = UNIX_EPOCH - Duration::from_micros(5);
+        assert_eq!(to_count(t), Some(-5));
+        assert_eq!(from_count::<SystemTime>(-5), Some(t));
+        assert_eq!(SystemTime::UNITS.to_string(), "microseconds since 1970-01-01 00:00:00");
+
+        assert_eq!(to_count(Duration::from_millis(3)), Some(3000));
+        assert_eq!(from_count::<Duration>(3000), Some(Duration::from_millis(3)));
+        assert_eq!(from_count::<Duration>(-1), None);
+        assert_eq!(to_count(Duration::MAX), None);
+        assert_eq!(Duration::UNITS.to_string(), "microseconds");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_chrono_times() {
+        use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+
+        let t = DateTime::<Utc>::from_timestamp(-1, 500_000_000).unwrap();
+        assert_eq!(to_count(t), Some(-500_000));
+        assert_eq!(from_count::<DateTime<Utc>>(-500_000), Some(t));
+        assert_eq!(from_count::<NaiveDateTime>(-500_000), Some(t.naive_utc()));
+        let d = TimeDelta::milliseconds(-1500);
+        assert_eq!(to_count(d), Some(-1_500_000));
+        assert_eq!(from_count::<TimeDelta>(-1_500_000), Some(d));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    pub fn test_time_times() {
+        use time::{Duration, OffsetDateTime, PrimitiveDateTime};
+
+        let t = OffsetDateTime::from_unix_timestamp_nanos(-1_500_000_000).unwrap();
+        assert_eq!(to_count(t), Some(-1_500_000));
+        assert_eq!(from_count::<OffsetDateTime>(-1_500_000), Some(t));
+        let p = PrimitiveDateTime::new(t.date(), t.time());
+        assert_eq!(from_count::<PrimitiveDateTime>(to_count(p).unwrap()), Some(p));
+        let d = Duration::milliseconds(-1500);
+        assert_eq!(to_count(d), Some(-1_500_000));
+        assert_eq!(from_count::<Duration>(-1_500_000), Some(d));
+    }
+}
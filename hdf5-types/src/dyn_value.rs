@@ -3,7 +3,11 @@ use std::mem;
 use std::ptr;
 use std::slice;
 
-use crate::h5type::{hvl_t, CompoundType, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor};
+use crate::h5type::{
+    hvl_t, CompoundType, CustomFloatType, CustomIntType, EnumType, FloatSize, H5Type, IntSize,
+    StringPadding, TypeDescriptor,
+};
+use crate::opaque::write_hex;
 use crate::string::{VarLenAscii, VarLenUnicode};
 
 fn read_raw<T: Copy>(buf: &[u8]) -> T {
@@ -11,6 +15,25 @@ fn read_raw<T: Copy>(buf: &[u8]) -> T {
     unsafe { *(buf.as_ptr().cast::<T>()) }
 }
 
+/// Reads up to 16 bytes of a native-order value as an unsigned integer.
+fn read_bits(buf: &[u8]) -> u128 {
+    let mut bytes = [0; 16];
+    let n = buf.len().min(16);
+    if cfg!(target_endian = "little") {
+        bytes[..n].copy_from_slice(&buf[..n]);
+        u128::from_le_bytes(bytes)
+    } else {
+        bytes[16 - n..].copy_from_slice(&buf[buf.len() - n..]);
+        u128::from_be_bytes(bytes)
+    }
+}
+
+/// Extracts `size` bits starting at bit `pos`.
+fn bit_field(bits: u128, pos: usize, size: usize) -> u128 {
+    let value = bits.checked_shr(pos as _).unwrap_or(0);
+    value & 1_u128.checked_shl(size as _).map_or(u128::MAX, |x| x - 1)
+}
+
 fn write_raw<T: Copy>(out: &mut [u8], value: T) {
     debug_assert_eq!(mem::size_of::<T>(), out.len());
     unsafe {
@@ -32,10 +55,12 @@ pub enum DynInteger {
     Int16(i16),
     Int32(i32),
     Int64(i64),
+    Int128(i128),
     UInt8(u8),
     UInt16(u16),
     UInt32(u32),
     UInt64(u64),
+    UInt128(u128),
 }
 
 impl DynInteger {
@@ -45,25 +70,62 @@ impl DynInteger {
             (true, IntSize::U2) => Self::Int16(read_raw(buf)),
             (true, IntSize::U4) => Self::Int32(read_raw(buf)),
             (true, IntSize::U8) => Self::Int64(read_raw(buf)),
+            (true, IntSize::U16) => Self::Int128(read_raw(buf)),
             (false, IntSize::U1) => Self::UInt8(read_raw(buf)),
             (false, IntSize::U2) => Self::UInt16(read_raw(buf)),
             (false, IntSize::U4) => Self::UInt32(read_raw(buf)),
             (false, IntSize::U8) => Self::UInt64(read_raw(buf)),
+            (false, IntSize::U16) => Self::UInt128(read_raw(buf)),
         }
     }
 
-    pub(self) fn as_u64(self) -> u64 {
+    pub(self) fn read_custom(buf: &[u8], tp: &CustomIntType) -> Self {
+        let bits = bit_field(read_bits(buf), tp.offset, tp.precision);
+        // sign-extend from the most significant bit
+        let shift = 128_usize.saturating_sub(tp.precision) as u32;
+        let value = if tp.signed { ((bits << shift) as i128) >> shift } else { bits as i128 };
+        match tp.native_type() {
+            TypeDescriptor::Integer(IntSize::U1) => Self::Int8(value as _),
+            TypeDescriptor::Integer(IntSize::U2) => Self::Int16(value as _),
+            TypeDescriptor::Integer(IntSize::U4) => Self::Int32(value as _),
+            TypeDescriptor::Integer(IntSize::U8) => Self::Int64(value as _),
+            TypeDescriptor::Integer(_) => Self::Int128(value),
+            TypeDescriptor::Unsigned(IntSize::U1) => Self::UInt8(value as _),
+            TypeDescriptor::Unsigned(IntSize::U2) => Self::UInt16(value as _),
+            TypeDescriptor::Unsigned(IntSize::U4) => Self::UInt32(value as _),
+            TypeDescriptor::Unsigned(IntSize::U8) => Self::UInt64(value as _),
+            _ => Self::UInt128(value as _),
+        }
+    }
+
+    pub(self) fn size(self) -> IntSize {
+        match self {
+            Self::Int8(_) | Self::UInt8(_) => IntSize::U1,
+            Self::Int16(_) | Self::UInt16(_) => IntSize::U2,
+            Self::Int32(_) | Self::UInt32(_) => IntSize::U4,
+            Self::Int64(_) | Self::UInt64(_) => IntSize::U8,
+            Self::Int128(_) | Self::UInt128(_) => IntSize::U16,
+        }
+    }
+
+    pub(self) fn as_u128(self) -> u128 {
         match self {
             Self::Int8(x) => x as _,
             Self::Int16(x) => x as _,
             Self::Int32(x) => x as _,
             Self::Int64(x) => x as _,
+            Self::Int128(x) => x as _,
             Self::UInt8(x) => x as _,
             Self::UInt16(x) => x as _,
             Self::UInt32(x) => x as _,
             Self::UInt64(x) => x as _,
+            Self::UInt128(x) => x,
         }
     }
+
+    pub(self) fn as_u64(self) -> u64 {
+        self.as_u128() as _
+    }
 }
 
 unsafe impl DynClone for DynInteger {
@@ -73,10 +135,12 @@ unsafe impl DynClone for DynInteger {
             Self::Int16(x) => write_raw(out, *x),
             Self::Int32(x) => write_raw(out, *x),
             Self::Int64(x) => write_raw(out, *x),
+            Self::Int128(x) => write_raw(out, *x),
             Self::UInt8(x) => write_raw(out, *x),
             Self::UInt16(x) => write_raw(out, *x),
             Self::UInt32(x) => write_raw(out, *x),
             Self::UInt64(x) => write_raw(out, *x),
+            Self::UInt128(x) => write_raw(out, *x),
         }
     }
 }
@@ -88,10 +152,12 @@ impl Debug for DynInteger {
             Self::Int16(x) => Debug::fmt(&x, f),
             Self::Int32(x) => Debug::fmt(&x, f),
             Self::Int64(x) => Debug::fmt(&x, f),
+            Self::Int128(x) => Debug::fmt(&x, f),
             Self::UInt8(x) => Debug::fmt(&x, f),
             Self::UInt16(x) => Debug::fmt(&x, f),
             Self::UInt32(x) => Debug::fmt(&x, f),
             Self::UInt64(x) => Debug::fmt(&x, f),
+            Self::UInt128(x) => Debug::fmt(&x, f),
         }
     }
 }
@@ -131,15 +197,30 @@ impl DynFloat {
             FloatSize::U8 => Self::Float64(read_raw(buf)),
         }
     }
-}
 
-unsafe impl DynClone for DynFloat {
-    fn dyn_clone(&mut self, out: &mut [u8]) {
-        match self {
+    pub(self) fn read_custom(buf: &[u8], tp: &CustomFloatType) -> Self {
+        let bits = read_bits(buf);
+        let exp = bit_field(bits, tp.exp_pos, tp.exp_size) as i32;
+        let mant = bit_field(bits, tp.mant_pos, tp.mant_size) as f64;
+        let frac = mant / 2_f64.powi(tp.mant_size as _);
+        let bias = tp.exp_bias as i32;
+        let value = if exp == 0 {
+            frac * 2_f64.powi(1 - bias)
+        } else if u128::from(exp as u32) == bit_field(u128::MAX, 0, tp.exp_size) {
+            if mant == 0. {
+                f64::INFINITY
+            } else {
+                f64::NAN
+            }
+        } else {
+            (1. + frac) * 2_f64.powi(exp - bias)
+        };
+        let value = if bit_field(bits, tp.sign_pos, 1) == 1 { -value } else { value };
+        match tp.native_type() {
             #[cfg(feature = "f16")]
-            Self::Float16(x) => write_raw(out, *x),
-            Self::Float32(x) => write_raw(out, *x),
-            Self::Float64(x) => write_raw(out, *x),
+            TypeDescriptor::Float(FloatSize::U2) => Self::Float16(::half::f16::from_f64(value)),
+            TypeDescriptor::Float(FloatSize::U4) => Self::Float32(value as _),
+            _ => Self::Float64(value),
         }
     }
 }
@@ -178,16 +259,7 @@ pub enum DynScalar {
     Integer(DynInteger),
     Float(DynFloat),
     Boolean(bool),
-}
-
-unsafe impl DynClone for DynScalar {
-    fn dyn_clone(&mut self, out: &mut [u8]) {
-        match self {
-            Self::Integer(x) => x.dyn_clone(out),
-            Self::Float(x) => x.dyn_clone(out),
-            Self::Boolean(x) => write_raw(out, *x),
-        }
-    }
+    Bitfield(DynInteger),
 }
 
 impl Debug for DynScalar {
@@ -196,6 +268,10 @@ impl Debug for DynScalar {
             Self::Integer(x) => Debug::fmt(&x, f),
             Self::Float(x) => Debug::fmt(&x, f),
             Self::Boolean(x) => Debug::fmt(&x, f),
+            Self::Bitfield(x) => {
+                let width = 2 + 8 * x.size() as usize;
+                write!(f, "{:#0width$b}", x.as_u128(), width = width)
+            }
         }
     }
 }
@@ -473,15 +549,25 @@ impl<'a> From<DynArray<'a>> for DynValue<'a> {
 pub struct DynFixedString<'a> {
     buf: &'a [u8],
     unicode: bool,
+    padding: StringPadding,
 }
 
 impl<'a> DynFixedString<'a> {
     pub fn new(buf: &'a [u8], unicode: bool) -> Self {
-        Self { buf, unicode }
+        Self { buf, unicode, padding: StringPadding::NullPad }
+    }
+
+    /// Sets the padding of the string, which is stripped from its contents.
+    pub fn with_padding(mut self, padding: StringPadding) -> Self {
+        self.padding = padding;
+        self
     }
 
     pub fn raw_len(&self) -> usize {
-        self.buf.iter().rev().skip_while(|&c| *c == 0).count()
+        match self.padding {
+            StringPadding::NullPad => self.buf.iter().rev().skip_while(|&c| *c == 0).count(),
+            padding => padding.trim(self.buf).len(),
+        }
     }
 
     pub fn get_buf(&self) -> &[u8] {
@@ -555,6 +641,14 @@ impl<'a> DynVarLenString<'a> {
         }
     }
 
+    pub fn get_buf(&self) -> &[u8] {
+        if self.get_ptr().is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.get_ptr(), self.raw_len()) }
+        }
+    }
+
     fn as_ascii(&self) -> &VarLenAscii {
         // Alignment is always at least usize for pointers from `hdf5-c`
         unsafe { &*(self.buf.as_ptr().cast::<VarLenAscii>()) }
@@ -639,6 +733,15 @@ pub enum DynString<'a> {
     VarLen(DynVarLenString<'a>),
 }
 
+impl DynString<'_> {
+    pub fn get_buf(&self) -> &[u8] {
+        match self {
+            Self::Fixed(x) => x.get_buf(),
+            Self::VarLen(x) => x.get_buf(),
+        }
+    }
+}
+
 unsafe impl DynDrop for DynString<'_> {
     fn dyn_drop(&mut self) {
         if let DynString::VarLen(string) = self {
@@ -677,6 +780,58 @@ impl<'a> From<DynString<'a>> for DynValue<'a> {
     }
 }
 
+pub struct DynOpaque<'a> {
+    tag: &'a str,
+    buf: &'a [u8],
+}
+
+impl<'a> DynOpaque<'a> {
+    pub fn new(tag: &'a str, buf: &'a [u8]) -> Self {
+        Self { tag, buf }
+    }
+
+    pub fn tag(&self) -> &str {
+        self.tag
+    }
+
+    pub fn get_buf(&self) -> &[u8] {
+        self.buf
+    }
+}
+
+unsafe impl DynClone for DynOpaque<'_> {
+    fn dyn_clone(&mut self, out: &mut [u8]) {
+        debug_assert_eq!(self.buf.len(), out.len());
+        out.clone_from_slice(self.buf);
+    }
+}
+
+impl PartialEq for DynOpaque<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag && self.buf == other.buf
+    }
+}
+
+impl Eq for DynOpaque<'_> {}
+
+impl Debug for DynOpaque<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.buf)
+    }
+}
+
+impl Display for DynOpaque<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl<'a> From<DynOpaque<'a>> for DynValue<'a> {
+    fn from(value: DynOpaque<'a>) -> Self {
+        DynValue::Opaque(value)
+    }
+}
+
 #[derive(PartialEq)]
 pub enum DynValue<'a> {
     Scalar(DynScalar),
@@ -684,6 +839,7 @@ pub enum DynValue<'a> {
     Compound(DynCompound<'a>),
     Array(DynArray<'a>),
     String(DynString<'a>),
+    Opaque(DynOpaque<'a>),
 }
 
 impl<'a> DynValue<'a> {
@@ -692,17 +848,43 @@ impl<'a> DynValue<'a> {
         debug_assert_eq!(tp.size(), buf.len());
 
         match tp {
-            Integer(size) | Unsigned(size) => DynInteger::read(buf, true, *size).into(),
+            Integer(size) => DynInteger::read(buf, true, *size).into(),
+            Unsigned(size) => DynInteger::read(buf, false, *size).into(),
             Float(size) => DynFloat::read(buf, *size).into(),
             Boolean => DynScalar::Boolean(read_raw(buf)).into(),
             Enum(ref tp) => DynEnum::new(tp, DynInteger::read(buf, tp.signed, tp.size)).into(),
             Compound(ref tp) => DynCompound::new(tp, buf).into(),
             FixedArray(ref tp, n) => DynArray::new(tp, buf, Some(*n)).into(),
             VarLenArray(ref tp) => DynArray::new(tp, buf, None).into(),
-            FixedAscii(_) => DynFixedString::new(buf, false).into(),
-            FixedUnicode(_) => DynFixedString::new(buf, true).into(),
+            FixedAscii(_, pad) => DynFixedString::new(buf, false).with_padding(*pad).into(),
+            FixedUnicode(_, pad) => DynFixedString::new(buf, true).with_padding(*pad).into(),
             VarLenAscii => DynVarLenString::new(buf, false).into(),
             VarLenUnicode => DynVarLenString::new(buf, true).into(),
+            Opaque { ref tag, .. } => DynOpaque::new(tag, buf).into(),
+            Bitfield(size) => DynScalar::Bitfield(DynInteger::read(buf, false, *size)).into(),
+            CustomInteger(ref tp) => DynInteger::read_custom(buf, tp).into(),
+            CustomFloat(ref tp) => DynFloat::read_custom(buf, tp).into(),
+            Complex(ref tp) => DynArray::new(tp, buf, Some(2)).into(),
+            Endian(ref tp, order) => {
+                let mut native = buf.to_vec();
+                if !order.is_native() {
+                    native.reverse();
+                }
+                match **tp {
+                    Integer(size) => DynInteger::read(&native, true, size).into(),
+                    Unsigned(size) => DynInteger::read(&native, false, size).into(),
+                    Float(size) => DynFloat::read(&native, size).into(),
+                    Enum(ref tp) => {
+                        DynEnum::new(tp, DynInteger::read(&native, tp.signed, tp.size)).into()
+                    }
+                    Bitfield(size) => {
+                        DynScalar::Bitfield(DynInteger::read(&native, false, size)).into()
+                    }
+                    CustomInteger(ref tp) => DynInteger::read_custom(&native, tp).into(),
+                    CustomFloat(ref tp) => DynFloat::read_custom(&native, tp).into(),
+                    _ => Self::new(tp, buf),
+                }
+            }
         }
     }
 }
@@ -721,11 +903,13 @@ unsafe impl DynDrop for DynValue<'_> {
 unsafe impl DynClone for DynValue<'_> {
     fn dyn_clone(&mut self, out: &mut [u8]) {
         match self {
-            Self::Scalar(x) => x.dyn_clone(out),
+            // scalars are copied bytewise (they may be stored in non-native byte order)
+            Self::Scalar(_) => (),
             Self::Enum(x) => x.dyn_clone(out),
             Self::Compound(x) => x.dyn_clone(out),
             Self::Array(x) => x.dyn_clone(out),
             Self::String(x) => x.dyn_clone(out),
+            Self::Opaque(x) => x.dyn_clone(out),
         }
     }
 }
@@ -738,6 +922,7 @@ impl Debug for DynValue<'_> {
             Self::Compound(x) => Debug::fmt(&x, f),
             Self::Array(x) => Debug::fmt(&x, f),
             Self::String(x) => Debug::fmt(&x, f),
+            Self::Opaque(x) => Debug::fmt(&x, f),
         }
     }
 }
@@ -930,8 +1115,8 @@ mod tests {
             fields: Vec::from(
                 [
                     CompoundField::new("points", points, 0, 0),
-                    CompoundField::new("fa", TD::FixedAscii(5), 16, 1),
-                    CompoundField::new("fu", TD::FixedUnicode(5), 21, 2),
+                    CompoundField::new("fa", TD::FixedAscii(5, StringPadding::NullPad), 16, 1),
+                    CompoundField::new("fu", TD::FixedUnicode(5, StringPadding::NullPad), 21, 2),
                     CompoundField::new("va", TD::VarLenAscii, 32, 3),
                     CompoundField::new("vu", TD::VarLenUnicode, 40, 4),
                 ]
@@ -1169,4 +1354,117 @@ mod tests {
         assert_eq!(format!("{:?}", val2), val2_flat);
         assert_eq!(format!("{:#?}", val2.clone()), val2_nice);
     }
+
+    #[test]
+    fn test_dyn_value_opaque_bitfield() {
+        let tp = TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::new("o", TD::Opaque { size: 3, tag: "frame".into() }, 0, 0),
+                CompoundField::new("b", TD::Bitfield(IntSize::U1), 3, 1),
+            ],
+            size: 4,
+        });
+        let buf = [0x01, 0xab, 0xff, 0b0000_0101];
+        assert_eq!(format!("{}", DynValue::new(&tp, &buf)), "{o: 0x01abff, b: 0b00000101}");
+
+        let val = unsafe { OwnedDynValue::from_raw(tp.clone(), Box::new(buf)) };
+        assert_eq!(val.clone(), val);
+        let other = unsafe { OwnedDynValue::from_raw(tp, Box::new([0x01, 0xab, 0xff, 0])) };
+        assert_ne!(val, other);
+    }
+
+    #[test]
+    fn test_dyn_value_wide_ints() {
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        struct Ids {
+            id: u128,
+            delta: i128,
+            flag: u8,
+        }
+        let tp = TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<u128>("id", 0, 0),
+                CompoundField::typed::<i128>("delta", 16, 1),
+                CompoundField::typed::<u8>("flag", 32, 2),
+            ],
+            size: mem::size_of::<Ids>(),
+        });
+        let ids = Ids { id: u128::MAX - 1, delta: i128::MIN, flag: 255 };
+        let buf = unsafe {
+            slice::from_raw_parts((&ids as *const Ids).cast::<u8>(), mem::size_of::<Ids>())
+        };
+        assert_eq!(
+            format!("{}", DynValue::new(&tp, buf)),
+            format!("{{id: {}, delta: {}, flag: 255}}", u128::MAX - 1, i128::MIN)
+        );
+    }
+
+    #[test]
+    fn test_dyn_value_byte_order() {
+        let tp = TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::new("a", TD::Unsigned(IntSize::U2), 0, 0),
+                CompoundField::new(
+                    "b",
+                    TD::Endian(Box::new(TD::Unsigned(IntSize::U2)), Endianness::Big),
+                    2,
+                    1,
+                ),
+                CompoundField::new(
+                    "c",
+                    TD::Endian(Box::new(TD::Float(FloatSize::U4)), Endianness::Little),
+                    4,
+                    2,
+                ),
+            ],
+            size: 8,
+        });
+        let mut buf = [0; 8];
+        buf[..2].copy_from_slice(&1000_u16.to_ne_bytes());
+        buf[2..4].copy_from_slice(&1000_u16.to_be_bytes());
+        buf[4..].copy_from_slice(&1.5_f32.to_le_bytes());
+        assert_eq!(format!("{}", DynValue::new(&tp, &buf)), "{a: 1000, b: 1000, c: 1.5}");
+
+        let val = unsafe { OwnedDynValue::from_raw(tp, Box::new(buf)) };
+        let cloned = val.clone();
+        assert_eq!(cloned, val);
+        assert_eq!(unsafe { cloned.get_buf() }, &buf);
+    }
+
+    #[test]
+    fn test_dyn_value_custom_precision() {
+        let int12 = CustomIntType { size: 2, precision: 12, offset: 2, signed: true };
+        let uint24 = CustomIntType { size: 4, precision: 24, offset: 8, signed: false };
+        let tp = TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::new("a", TD::CustomInteger(int12), 0, 0),
+                CompoundField::new("b", TD::CustomInteger(uint24), 2, 1),
+                CompoundField::new("c", TD::CustomFloat(CustomFloatType::bfloat16()), 6, 2),
+                CompoundField::new("d", TD::CustomFloat(CustomFloatType::bfloat16()), 8, 3),
+            ],
+            size: 10,
+        });
+        let mut buf = [0; 10];
+        // -5 as 12-bit two's complement shifted by 2, with garbage in the padding bits
+        buf[..2].copy_from_slice(&0xffec_u16.to_ne_bytes());
+        buf[2..6].copy_from_slice(&0x1234_56ff_u32.to_ne_bytes());
+        buf[6..8].copy_from_slice(&((1.5_f32.to_bits() >> 16) as u16).to_ne_bytes());
+        buf[8..].copy_from_slice(&(((-0.125_f32).to_bits() >> 16) as u16).to_ne_bytes());
+        assert_eq!(
+            format!("{}", DynValue::new(&tp, &buf)),
+            "{a: -5, b: 1193046, c: 1.5, d: -0.125}"
+        );
+    }
+
+    #[test]
+    fn test_dyn_value_string_padding() {
+        let buf = *b"ab  \0c\0\0";
+        let tp = TD::FixedAscii(4, StringPadding::SpacePad);
+        assert_eq!(format!("{}", DynValue::new(&tp, &buf[..4])), "\"ab\"");
+        let tp = TD::FixedAscii(4, StringPadding::NullTerm);
+        assert_eq!(format!("{}", DynValue::new(&tp, &buf[4..])), "\"\"");
+        let tp = TD::FixedUnicode(4, StringPadding::NullPad);
+        assert_eq!(format!("{}", DynValue::new(&tp, &buf[..4])), "\"ab  \"");
+    }
 }
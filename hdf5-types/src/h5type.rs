@@ -4,6 +4,7 @@ use std::os::raw::c_void;
 use std::ptr;
 
 use crate::array::VarLenArray;
+use crate::opaque::Opaque;
 use crate::string::{FixedAscii, FixedUnicode, VarLenAscii, VarLenUnicode};
 
 #[allow(non_camel_case_types)]
@@ -20,6 +21,7 @@ pub enum IntSize {
     U2 = 2,
     U4 = 4,
     U8 = 8,
+    U16 = 16,
 }
 
 impl IntSize {
@@ -32,6 +34,8 @@ impl IntSize {
             Some(Self::U4)
         } else if size == 8 {
             Some(Self::U8)
+        } else if size == 16 {
+            Some(Self::U16)
         } else {
             None
         }
@@ -62,6 +66,190 @@ impl FloatSize {
     }
 }
 
+/// Byte order of a numeric type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Endianness {
+    /// Byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Self = Self::Little;
+    /// Byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Self = Self::Big;
+
+    #[inline]
+    pub fn is_native(self) -> bool {
+        self == Self::NATIVE
+    }
+}
+
+impl Display for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Self::Little => "little-endian",
+            Self::Big => "big-endian",
+        })
+    }
+}
+
+/// Padding of fixed-length strings that are shorter than their storage size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StringPadding {
+    /// Null-terminated, like C strings (a string can hold at most `size - 1` bytes).
+    NullTerm,
+    /// Padded with null bytes.
+    NullPad,
+    /// Padded with spaces, like Fortran strings.
+    SpacePad,
+}
+
+impl StringPadding {
+    /// The byte used to fill unused space.
+    pub fn pad_byte(self) -> u8 {
+        match self {
+            Self::NullTerm | Self::NullPad => b'\0',
+            Self::SpacePad => b' ',
+        }
+    }
+
+    /// Strips the padding (and anything following a null terminator) from raw bytes.
+    pub fn trim(self, bytes: &[u8]) -> &[u8] {
+        match self {
+            Self::NullTerm | Self::NullPad => {
+                &bytes[..bytes.iter().position(|&c| c == 0).unwrap_or(bytes.len())]
+            }
+            Self::SpacePad => &bytes[..bytes.iter().rposition(|&c| c != b' ').map_or(0, |i| i + 1)],
+        }
+    }
+}
+
+impl Display for StringPadding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Self::NullTerm => "null-terminated",
+            Self::NullPad => "null-padded",
+            Self::SpacePad => "space-padded",
+        })
+    }
+}
+
+/// Integer type with a custom number of significant bits stored at a bit offset,
+/// e.g. 12-bit detector samples padded to two bytes.
+///
+/// Values are converted to the nearest native integer type when read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CustomIntType {
+    /// Total size in bytes
+    pub size: usize,
+    /// Number of significant bits
+    pub precision: usize,
+    /// Bit offset of the least significant bit
+    pub offset: usize,
+    pub signed: bool,
+}
+
+impl CustomIntType {
+    /// Returns the smallest native integer type that can hold all values of this type.
+    pub fn native_type(&self) -> TypeDescriptor {
+        let size = match self.precision {
+            0..=8 => IntSize::U1,
+            9..=16 => IntSize::U2,
+            17..=32 => IntSize::U4,
+            33..=64 => IntSize::U8,
+            _ => IntSize::U16,
+        };
+        if self.signed {
+            TypeDescriptor::Integer(size)
+        } else {
+            TypeDescriptor::Unsigned(size)
+        }
+    }
+}
+
+/// Floating-point type with a custom layout of the sign, exponent and mantissa bits,
+/// e.g. `bfloat16`; bit positions are counted from the least significant bit of the value.
+///
+/// The mantissa is assumed to be normalized with an implied leading one, as in IEEE 754.
+/// Values are converted to the nearest native float type when read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CustomFloatType {
+    /// Total size in bytes
+    pub size: usize,
+    /// Number of significant bits
+    pub precision: usize,
+    /// Bit offset of the least significant bit
+    pub offset: usize,
+    pub sign_pos: usize,
+    pub exp_pos: usize,
+    pub exp_size: usize,
+    pub exp_bias: usize,
+    pub mant_pos: usize,
+    pub mant_size: usize,
+}
+
+impl CustomFloatType {
+    /// Returns the layout of a standard IEEE 754 float type of the given size.
+    pub const fn ieee(size: FloatSize) -> Self {
+        let (size, exp_size, mant_size) = match size {
+            #[cfg(feature = "f16")]
+            FloatSize::U2 => (2, 5, 10),
+            FloatSize::U4 => (4, 8, 23),
+            FloatSize::U8 => (8, 11, 52),
+        };
+        Self {
+            size,
+            precision: size * 8,
+            offset: 0,
+            sign_pos: size * 8 - 1,
+            exp_pos: mant_size,
+            exp_size,
+            exp_bias: (1 << (exp_size - 1)) - 1,
+            mant_pos: 0,
+            mant_size,
+        }
+    }
+
+    /// Returns the layout of `bfloat16` (a 32-bit IEEE 754 float with the mantissa
+    /// truncated to 7 bits).
+    pub const fn bfloat16() -> Self {
+        Self {
+            size: 2,
+            precision: 16,
+            offset: 0,
+            sign_pos: 15,
+            exp_pos: 7,
+            exp_size: 8,
+            exp_bias: 127,
+            mant_pos: 0,
+            mant_size: 7,
+        }
+    }
+
+    /// Returns the smallest native float type whose exponent and mantissa are at least as
+    /// wide as those of this type.
+    pub fn native_type(&self) -> TypeDescriptor {
+        let fits = |size| {
+            let ieee = Self::ieee(size);
+            self.exp_size <= ieee.exp_size && self.mant_size <= ieee.mant_size
+        };
+        #[cfg(feature = "f16")]
+        {
+            if fits(FloatSize::U2) {
+                return TypeDescriptor::Float(FloatSize::U2);
+            }
+        }
+        if fits(FloatSize::U4) {
+            TypeDescriptor::Float(FloatSize::U4)
+        } else {
+            TypeDescriptor::Float(FloatSize::U8)
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EnumMember {
     pub name: String,
@@ -155,11 +343,26 @@ pub enum TypeDescriptor {
     Enum(EnumType),
     Compound(CompoundType),
     FixedArray(Box<Self>, usize),
-    FixedAscii(usize),
-    FixedUnicode(usize),
+    /// Fixed-length ASCII string of the given size in bytes.
+    FixedAscii(usize, StringPadding),
+    /// Fixed-length UTF-8 string of the given size in bytes.
+    FixedUnicode(usize, StringPadding),
     VarLenArray(Box<Self>),
     VarLenAscii,
     VarLenUnicode,
+    Opaque {
+        size: usize,
+        tag: String,
+    },
+    Bitfield(IntSize),
+    /// Integer, unsigned, float, enum or bitfield type with an explicit byte order; values
+    /// of such types are laid out in memory in that byte order.
+    Endian(Box<Self>, Endianness),
+    CustomInteger(CustomIntType),
+    CustomFloat(CustomFloatType),
+    /// Complex number stored as a pair of floating-point values (the real part followed by
+    /// the imaginary part) using the complex datatype class introduced in HDF5 2.0.
+    Complex(Box<Self>),
 }
 
 impl Display for TypeDescriptor {
@@ -169,10 +372,12 @@ impl Display for TypeDescriptor {
             TypeDescriptor::Integer(IntSize::U2) => write!(f, "int16"),
             TypeDescriptor::Integer(IntSize::U4) => write!(f, "int32"),
             TypeDescriptor::Integer(IntSize::U8) => write!(f, "int64"),
+            TypeDescriptor::Integer(IntSize::U16) => write!(f, "int128"),
             TypeDescriptor::Unsigned(IntSize::U1) => write!(f, "uint8"),
             TypeDescriptor::Unsigned(IntSize::U2) => write!(f, "uint16"),
             TypeDescriptor::Unsigned(IntSize::U4) => write!(f, "uint32"),
             TypeDescriptor::Unsigned(IntSize::U8) => write!(f, "uint64"),
+            TypeDescriptor::Unsigned(IntSize::U16) => write!(f, "uint128"),
             #[cfg(feature = "f16")]
             TypeDescriptor::Float(FloatSize::U2) => write!(f, "float16"),
             TypeDescriptor::Float(FloatSize::U4) => write!(f, "float32"),
@@ -181,11 +386,33 @@ impl Display for TypeDescriptor {
             TypeDescriptor::Enum(ref tp) => write!(f, "enum ({})", tp.base_type()),
             TypeDescriptor::Compound(ref tp) => write!(f, "compound ({} fields)", tp.fields.len()),
             TypeDescriptor::FixedArray(ref tp, n) => write!(f, "[{}; {}]", tp, n),
-            TypeDescriptor::FixedAscii(n) => write!(f, "string (len {})", n),
-            TypeDescriptor::FixedUnicode(n) => write!(f, "unicode (len {})", n),
+            TypeDescriptor::FixedAscii(n, StringPadding::NullPad) => {
+                write!(f, "string (len {})", n)
+            }
+            TypeDescriptor::FixedAscii(n, pad) => write!(f, "string (len {}, {})", n, pad),
+            TypeDescriptor::FixedUnicode(n, StringPadding::NullPad) => {
+                write!(f, "unicode (len {})", n)
+            }
+            TypeDescriptor::FixedUnicode(n, pad) => write!(f, "unicode (len {}, {})", n, pad),
             TypeDescriptor::VarLenArray(ref tp) => write!(f, "[{}] (var len)", tp),
             TypeDescriptor::VarLenAscii => write!(f, "string (var len)"),
             TypeDescriptor::VarLenUnicode => write!(f, "unicode (var len)"),
+            TypeDescriptor::Opaque { size, ref tag } if tag.is_empty() => {
+                write!(f, "opaque (len {})", size)
+            }
+            TypeDescriptor::Opaque { size, ref tag } => {
+                write!(f, "opaque {:?} (len {})", tag, size)
+            }
+            TypeDescriptor::Bitfield(size) => write!(f, "bitfield{}", *size as usize * 8),
+            TypeDescriptor::Endian(ref tp, order) => write!(f, "{} ({})", tp, order),
+            TypeDescriptor::CustomInteger(ref tp) => {
+                let prefix = if tp.signed { "int" } else { "uint" };
+                write!(f, "{}{} (len {})", prefix, tp.precision, tp.size)
+            }
+            TypeDescriptor::CustomFloat(ref tp) => {
+                write!(f, "float{} (e{}m{})", tp.precision, tp.exp_size, tp.mant_size)
+            }
+            TypeDescriptor::Complex(ref tp) => write!(f, "complex {}", tp),
         }
     }
 }
@@ -193,15 +420,20 @@ impl Display for TypeDescriptor {
 impl TypeDescriptor {
     pub fn size(&self) -> usize {
         match *self {
-            Self::Integer(size) | Self::Unsigned(size) => size as _,
+            Self::Integer(size) | Self::Unsigned(size) | Self::Bitfield(size) => size as _,
             Self::Float(size) => size as _,
             Self::Boolean => 1,
             Self::Enum(ref enum_type) => enum_type.size as _,
             Self::Compound(ref compound) => compound.size,
             Self::FixedArray(ref ty, len) => ty.size() * len,
-            Self::FixedAscii(len) | Self::FixedUnicode(len) => len,
+            Self::FixedAscii(len, _) | Self::FixedUnicode(len, _) => len,
+            Self::Opaque { size, .. } => size,
             Self::VarLenArray(_) => mem::size_of::<hvl_t>(),
             Self::VarLenAscii | Self::VarLenUnicode => mem::size_of::<*const u8>(),
+            Self::Endian(ref ty, _) => ty.size(),
+            Self::CustomInteger(ref tp) => tp.size,
+            Self::CustomFloat(ref tp) => tp.size,
+            Self::Complex(ref ty) => ty.size() * 2,
         }
     }
 
@@ -210,9 +442,12 @@ impl TypeDescriptor {
             Self::Compound(ref compound) => {
                 compound.fields.iter().map(|f| f.ty.c_alignment()).max().unwrap_or(1)
             }
-            Self::FixedArray(ref ty, _) => ty.c_alignment(),
-            Self::FixedAscii(_) | Self::FixedUnicode(_) => 1,
+            Self::FixedArray(ref ty, _) | Self::Endian(ref ty, _) | Self::Complex(ref ty) => {
+                ty.c_alignment()
+            }
+            Self::FixedAscii(..) | Self::FixedUnicode(..) | Self::Opaque { .. } => 1,
             Self::VarLenArray(_) => mem::size_of::<usize>(),
+            Self::CustomInteger(_) | Self::CustomFloat(_) if !self.size().is_power_of_two() => 1,
             _ => self.size(),
         }
     }
@@ -234,6 +469,68 @@ impl TypeDescriptor {
             _ => self.clone(),
         }
     }
+
+    /// Returns a copy of the descriptor with all integer, float and bitfield types
+    /// (including those nested in compound and array types) set to the given byte order.
+    ///
+    /// Passing `Endianness::NATIVE` removes all explicit byte orders.
+    pub fn with_byte_order(&self, order: Endianness) -> Self {
+        match *self {
+            Self::Integer(_)
+            | Self::Unsigned(_)
+            | Self::Float(_)
+            | Self::Bitfield(_)
+            | Self::Enum(_)
+            | Self::CustomInteger(_)
+            | Self::CustomFloat(_) => {
+                if order.is_native() {
+                    self.clone()
+                } else {
+                    Self::Endian(Box::new(self.clone()), order)
+                }
+            }
+            Self::Endian(ref ty, _) => ty.with_byte_order(order),
+            Self::Compound(ref compound) => {
+                let mut compound = compound.clone();
+                for f in &mut compound.fields {
+                    f.ty = f.ty.with_byte_order(order);
+                }
+                Self::Compound(compound)
+            }
+            Self::FixedArray(ref ty, len) => {
+                Self::FixedArray(Box::new(ty.with_byte_order(order)), len)
+            }
+            Self::VarLenArray(ref ty) => Self::VarLenArray(Box::new(ty.with_byte_order(order))),
+            Self::Complex(ref ty) => Self::Complex(Box::new(ty.with_byte_order(order))),
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns the descriptor of the in-memory type that values of this type are naturally
+    /// read into: numeric types are converted to native byte order and custom-precision
+    /// types are replaced by the nearest native types.
+    pub fn to_native_repr(&self) -> Self {
+        match *self {
+            Self::Endian(ref ty, _) => ty.to_native_repr(),
+            Self::CustomInteger(ref tp) => tp.native_type(),
+            Self::CustomFloat(ref tp) => tp.native_type(),
+            Self::Compound(ref compound) => {
+                let mut compound = compound.clone();
+                let mut resized = false;
+                for f in &mut compound.fields {
+                    let ty = f.ty.to_native_repr();
+                    resized |= ty.size() != f.ty.size();
+                    f.ty = ty;
+                }
+                // the original layout is kept unless some of the fields have been resized
+                Self::Compound(if resized { compound.to_c_repr() } else { compound })
+            }
+            Self::FixedArray(ref ty, len) => Self::FixedArray(Box::new(ty.to_native_repr()), len),
+            Self::VarLenArray(ref ty) => Self::VarLenArray(Box::new(ty.to_native_repr())),
+            Self::Complex(ref ty) => Self::Complex(Box::new(ty.to_native_repr())),
+            _ => self.clone(),
+        }
+    }
 }
 
 pub unsafe trait H5Type: 'static {
@@ -255,12 +552,16 @@ impl_h5type!(i8, Integer, IntSize::U1);
 impl_h5type!(i16, Integer, IntSize::U2);
 impl_h5type!(i32, Integer, IntSize::U4);
 impl_h5type!(i64, Integer, IntSize::U8);
+impl_h5type!(i128, Integer, IntSize::U16);
 impl_h5type!(u8, Unsigned, IntSize::U1);
 impl_h5type!(u16, Unsigned, IntSize::U2);
 impl_h5type!(u32, Unsigned, IntSize::U4);
 impl_h5type!(u64, Unsigned, IntSize::U8);
+impl_h5type!(u128, Unsigned, IntSize::U16);
 #[cfg(feature = "f16")]
 impl_h5type!(::half::f16, Float, FloatSize::U2);
+#[cfg(feature = "f16")]
+impl_h5type!(::half::bf16, CustomFloat, CustomFloatType::bfloat16());
 impl_h5type!(f32, Float, FloatSize::U4);
 impl_h5type!(f64, Float, FloatSize::U8);
 
@@ -340,7 +641,7 @@ unsafe impl<T: H5Type, const N: usize> H5Type for [T; N] {
     }
 }
 
-unsafe impl<T: Copy + H5Type> H5Type for VarLenArray<T> {
+unsafe impl<T: H5Type> H5Type for VarLenArray<T> {
     #[inline]
     fn type_descriptor() -> TypeDescriptor {
         TypeDescriptor::VarLenArray(Box::new(<T as H5Type>::type_descriptor()))
@@ -350,14 +651,21 @@ unsafe impl<T: Copy + H5Type> H5Type for VarLenArray<T> {
 unsafe impl<const N: usize> H5Type for FixedAscii<N> {
     #[inline]
     fn type_descriptor() -> TypeDescriptor {
-        TypeDescriptor::FixedAscii(N)
+        TypeDescriptor::FixedAscii(N, StringPadding::NullPad)
     }
 }
 
 unsafe impl<const N: usize> H5Type for FixedUnicode<N> {
     #[inline]
     fn type_descriptor() -> TypeDescriptor {
-        TypeDescriptor::FixedUnicode(N)
+        TypeDescriptor::FixedUnicode(N, StringPadding::NullPad)
+    }
+}
+
+unsafe impl<const N: usize> H5Type for Opaque<N> {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Opaque { size: N, tag: String::new() }
     }
 }
 
@@ -378,7 +686,10 @@ unsafe impl H5Type for VarLenUnicode {
 #[cfg(test)]
 pub mod tests {
     use super::TypeDescriptor as TD;
-    use super::{hvl_t, CompoundField, CompoundType, FloatSize, H5Type, IntSize};
+    use super::{
+        hvl_t, CompoundField, CompoundType, CustomFloatType, CustomIntType, Endianness, EnumType,
+        FloatSize, H5Type, IntSize, StringPadding,
+    };
     use crate::array::VarLenArray;
     use crate::string::{FixedAscii, FixedUnicode, VarLenAscii, VarLenUnicode};
     use std::mem;
@@ -390,10 +701,12 @@ pub mod tests {
         assert_eq!(i16::type_descriptor(), TD::Integer(IntSize::U2));
         assert_eq!(i32::type_descriptor(), TD::Integer(IntSize::U4));
         assert_eq!(i64::type_descriptor(), TD::Integer(IntSize::U8));
+        assert_eq!(i128::type_descriptor(), TD::Integer(IntSize::U16));
         assert_eq!(u8::type_descriptor(), TD::Unsigned(IntSize::U1));
         assert_eq!(u16::type_descriptor(), TD::Unsigned(IntSize::U2));
         assert_eq!(u32::type_descriptor(), TD::Unsigned(IntSize::U4));
         assert_eq!(u64::type_descriptor(), TD::Unsigned(IntSize::U8));
+        assert_eq!(u128::type_descriptor(), TD::Unsigned(IntSize::U16));
         assert_eq!(f32::type_descriptor(), TD::Float(FloatSize::U4));
         assert_eq!(f64::type_descriptor(), TD::Float(FloatSize::U8));
 
@@ -401,6 +714,8 @@ pub mod tests {
         assert_eq!(i16::type_descriptor().size(), 2);
         assert_eq!(u32::type_descriptor().size(), 4);
         assert_eq!(f64::type_descriptor().size(), 8);
+        assert_eq!(u128::type_descriptor().size(), 16);
+        assert_eq!(format!("{}", i128::type_descriptor()), "int128");
     }
 
     #[test]
@@ -440,10 +755,18 @@ pub mod tests {
     pub fn test_string_types() {
         type FA = FixedAscii<16>;
         type FU = FixedUnicode<32>;
-        assert_eq!(FA::type_descriptor(), TD::FixedAscii(16));
-        assert_eq!(FU::type_descriptor(), TD::FixedUnicode(32));
+        assert_eq!(FA::type_descriptor(), TD::FixedAscii(16, StringPadding::NullPad));
+        assert_eq!(FU::type_descriptor(), TD::FixedUnicode(32, StringPadding::NullPad));
         assert_eq!(VarLenAscii::type_descriptor(), TD::VarLenAscii);
         assert_eq!(VarLenUnicode::type_descriptor(), TD::VarLenUnicode);
+        assert_eq!(
+            TD::FixedAscii(4, StringPadding::SpacePad).to_string(),
+            "string (len 4, space-padded)"
+        );
+        assert_eq!(StringPadding::NullTerm.trim(b"ab\0c"), b"ab");
+        assert_eq!(StringPadding::NullPad.trim(b"a b"), b"a b");
+        assert_eq!(StringPadding::SpacePad.trim(b"a b  "), b"a b");
+        assert_eq!(StringPadding::SpacePad.trim(b"   "), b"");
     }
 
     #[test]
@@ -535,4 +858,98 @@ pub mod tests {
         );
         assert_eq!(td.size(), 14);
     }
+
+    #[test]
+    pub fn test_byte_order() {
+        let other = match Endianness::NATIVE {
+            Endianness::Little => Endianness::Big,
+            Endianness::Big => Endianness::Little,
+        };
+        assert!(Endianness::NATIVE.is_native() && !other.is_native());
+
+        let td = f64::type_descriptor().with_byte_order(other);
+        assert_eq!(td, TD::Endian(Box::new(TD::Float(FloatSize::U8)), other));
+        assert_eq!(td.size(), 8);
+        assert_eq!(td.with_byte_order(Endianness::NATIVE), f64::type_descriptor());
+        assert_eq!(
+            format!("{}", TD::Endian(Box::new(TD::Integer(IntSize::U2)), Endianness::Big)),
+            "int16 (big-endian)"
+        );
+
+        let td = TD::Enum(EnumType { size: IntSize::U2, signed: false, members: vec![] });
+        assert_eq!(td.with_byte_order(other), TD::Endian(Box::new(td.clone()), other));
+        assert_eq!(td.with_byte_order(Endianness::NATIVE), td);
+
+        type T = (u16, [f32; 2], bool);
+        let td = T::type_descriptor().with_byte_order(other);
+        assert_eq!(td.size(), T::type_descriptor().size());
+        if let TD::Compound(ref compound) = td {
+            let types: Vec<_> = compound.fields.iter().map(|f| f.ty.clone()).collect();
+            assert_eq!(
+                types,
+                vec![
+                    TD::FixedArray(
+                        Box::new(TD::Endian(Box::new(TD::Float(FloatSize::U4)), other)),
+                        2
+                    ),
+                    TD::Endian(Box::new(TD::Unsigned(IntSize::U2)), other),
+                    TD::Boolean,
+                ]
+            );
+        } else {
+            panic!("expected compound type");
+        }
+        assert_eq!(td.with_byte_order(Endianness::NATIVE), T::type_descriptor());
+    }
+
+    #[test]
+    pub fn test_custom_precision() {
+        let int12 = CustomIntType { size: 2, precision: 12, offset: 0, signed: true };
+        let uint24 = CustomIntType { size: 4, precision: 24, offset: 8, signed: false };
+        assert_eq!(int12.native_type(), TD::Integer(IntSize::U2));
+        assert_eq!(uint24.native_type(), TD::Unsigned(IntSize::U4));
+        assert_eq!(TD::CustomInteger(uint24).size(), 4);
+        assert_eq!(format!("{}", TD::CustomInteger(int12)), "int12 (len 2)");
+        assert_eq!(format!("{}", TD::CustomInteger(uint24)), "uint24 (len 4)");
+
+        let f32_layout = CustomFloatType::ieee(FloatSize::U4);
+        assert_eq!((f32_layout.sign_pos, f32_layout.exp_pos, f32_layout.exp_bias), (31, 23, 127));
+        let f64_layout = CustomFloatType::ieee(FloatSize::U8);
+        assert_eq!((f64_layout.sign_pos, f64_layout.exp_pos, f64_layout.exp_bias), (63, 52, 1023));
+        assert_eq!(f64_layout.native_type(), TD::Float(FloatSize::U8));
+
+        let bf16 = TD::CustomFloat(CustomFloatType::bfloat16());
+        assert_eq!(bf16.size(), 2);
+        assert_eq!(format!("{}", bf16), "float16 (e8m7)");
+        assert_eq!(bf16.to_native_repr(), TD::Float(FloatSize::U4));
+        #[cfg(feature = "f16")]
+        {
+            assert_eq!(::half::bf16::type_descriptor(), bf16);
+            assert_ne!(::half::bf16::type_descriptor(), ::half::f16::type_descriptor());
+        }
+
+        let td = TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::new("0", TD::CustomInteger(int12), 0, 0),
+                CompoundField::new(
+                    "1",
+                    TD::Endian(Box::new(TD::CustomInteger(uint24)), Endianness::Big),
+                    2,
+                    1,
+                ),
+            ],
+            size: 6,
+        });
+        assert_eq!(td.to_native_repr().size(), 6);
+        assert_eq!(
+            td.to_native_repr().to_c_repr(),
+            TD::Compound(CompoundType {
+                fields: vec![
+                    CompoundField::typed::<i16>("0", 0, 0),
+                    CompoundField::typed::<u32>("1", 4, 1),
+                ],
+                size: 8,
+            })
+        );
+    }
 }
@@ -18,18 +18,27 @@
 extern crate quickcheck;
 
 mod array;
+mod datetime;
 pub mod dyn_value;
 mod h5type;
+mod opaque;
+mod proxy;
 mod string;
 
 #[cfg(feature = "complex")]
 mod complex;
 
 pub use self::array::VarLenArray;
+#[cfg(feature = "complex")]
+pub use self::complex::ComplexLayout;
+pub use self::datetime::{H5Time, TimeConversion, TimeUnit, TimeUnits};
 pub use self::dyn_value::{DynValue, OwnedDynValue};
 pub use self::h5type::{
-    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
+    CompoundField, CompoundType, CustomFloatType, CustomIntType, Endianness, EnumMember, EnumType,
+    FloatSize, H5Type, IntSize, StringPadding, TypeDescriptor,
 };
+pub use self::opaque::Opaque;
+pub use self::proxy::{AsH5Type, H5Adapter, H5Proxy, H5Slice};
 pub use self::string::{FixedAscii, FixedUnicode, StringError, VarLenAscii, VarLenUnicode};
 
 pub(crate) unsafe fn malloc(n: usize) -> *mut core::ffi::c_void {
@@ -0,0 +1,103 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// A fixed-size blob of uninterpreted bytes, stored as an HDF5 opaque type.
+///
+/// The in-memory type has no tag; opaque data with any tag (e.g. raw instrument frames)
+/// can be read into it as long as the size matches.
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Opaque<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Opaque<N> {
+    #[inline]
+    pub const fn new(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+
+    #[inline]
+    pub const fn capacity() -> usize {
+        N
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> [u8; N] {
+        self.0
+    }
+}
+
+impl<const N: usize> Default for Opaque<N> {
+    #[inline]
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Opaque<N> {
+    #[inline]
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<const N: usize> Deref for Opaque<N> {
+    type Target = [u8; N];
+
+    #[inline]
+    fn deref(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> DerefMut for Opaque<N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8; N] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Opaque<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> fmt::Debug for Opaque<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+pub(crate) fn write_hex(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    f.write_str("0x")?;
+    bytes.iter().try_for_each(|b| write!(f, "{:02x}", b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Opaque;
+    use crate::h5type::{H5Type, TypeDescriptor as TD};
+    use std::mem;
+
+    #[test]
+    pub fn test_opaque() {
+        type O = Opaque<5>;
+        assert_eq!(O::type_descriptor(), TD::Opaque { size: 5, tag: String::new() });
+        assert_eq!(O::type_descriptor().size(), 5);
+        assert_eq!(mem::size_of::<O>(), 5);
+        assert_eq!(O::capacity(), 5);
+        let o = O::from([0, 1, 0xab, 0x10, 0xff]);
+        assert_eq!(o.as_bytes(), &[0, 1, 0xab, 0x10, 0xff]);
+        assert_eq!(format!("{:?}", o), "0x0001ab10ff");
+        assert_eq!(format!("{:?}", O::default()), "0x0000000000");
+        assert_eq!(format!("{}", O::type_descriptor()), "opaque (len 5)");
+        let td = TD::Opaque { size: 3, tag: "frame".into() };
+        assert_eq!(format!("{}", td), "opaque \"frame\" (len 3)");
+    }
+}
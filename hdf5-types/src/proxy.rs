@@ -0,0 +1,186 @@
+use std::ops::Deref;
+
+use crate::array::VarLenArray;
+use crate::h5type::H5Type;
+use crate::string::{FixedUnicode, StringError, VarLenUnicode};
+
+/// A type stored in `HDF5` via an intermediate `H5Type` representation.
+///
+/// `#[derive(H5Proxy)]` implements this trait for structs whose memory layout can't be
+/// described directly by `#[derive(H5Type)]`: structs without `repr(C)`/`repr(packed)`, and
+/// structs with `#[hdf5(skip)]`, `#[hdf5(with = "...")]` or `#[hdf5(fixed_len = N)]` fields.
+/// The generated proxy is a packed struct; values are copied into it when writing and out of
+/// it when reading (see e.g. `Container::read_raw_proxy()` and `Container::write_proxy()`).
+pub trait H5Proxy: Sized {
+    /// The type that is actually stored.
+    type Proxy: H5Type;
+
+    /// Converts the value into its stored representation.
+    fn to_proxy(&self) -> Self::Proxy;
+
+    /// Converts the stored representation back into the value.
+    fn from_proxy(proxy: Self::Proxy) -> Self;
+}
+
+/// Conversion of a field of type `T` into an `H5Type` representation.
+///
+/// Adapters are used via `#[hdf5(with = "Adapter")]` field attributes in `#[derive(H5Proxy)]`,
+/// where the adapter is usually the representation type itself (e.g. `VarLenUnicode` for
+/// `String` fields).
+pub trait H5Adapter<T> {
+    /// The type that is actually stored.
+    type Repr: H5Type;
+
+    /// Converts the field value into its stored representation.
+    fn to_repr(value: &T) -> Self::Repr;
+
+    /// Converts the stored representation back into the field value.
+    fn from_repr(repr: Self::Repr) -> T;
+}
+
+/// Stores strings as variable-length unicode strings.
+///
+/// Strings with internal null characters are truncated at the first null when read back.
+impl H5Adapter<String> for VarLenUnicode {
+    type Repr = Self;
+
+    fn to_repr(value: &String) -> Self {
+        unsafe { Self::from_str_unchecked(value.as_str()) }
+    }
+
+    fn from_repr(repr: Self) -> String {
+        repr.as_str().to_owned()
+    }
+}
+
+/// Stores strings as fixed-length unicode strings, truncating them to at most `N` bytes.
+impl<const N: usize> H5Adapter<String> for FixedUnicode<N> {
+    type Repr = Self;
+
+    fn to_repr(value: &String) -> Self {
+        let mut len = value.len().min(N);
+        while !value.is_char_boundary(len) {
+            len -= 1;
+        }
+        unsafe { Self::from_str_unchecked(&value[..len]) }
+    }
+
+    fn from_repr(repr: Self) -> String {
+        repr.as_str().to_owned()
+    }
+}
+
+/// Stores vectors as variable-length arrays.
+impl<T: H5Type + Clone> H5Adapter<Vec<T>> for VarLenArray<T> {
+    type Repr = Self;
+
+    fn to_repr(value: &Vec<T>) -> Self {
+        Self::from_slice(value)
+    }
+
+    fn from_repr(repr: Self) -> Vec<T> {
+        repr.into()
+    }
+}
+
+/// Values in their stored representation, see `AsH5Type::as_h5_slice()`.
+#[derive(Debug)]
+pub enum H5Slice<'a, T> {
+    /// Values which are stored as is.
+    Borrowed(&'a [T]),
+    /// Values converted into their stored representation.
+    Owned(Vec<T>),
+}
+
+impl<T> Deref for H5Slice<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Self::Borrowed(values) => values,
+            Self::Owned(values) => values,
+        }
+    }
+}
+
+/// A type whose values can be written into a dataset/attribute, either as is or after
+/// conversion into an `H5Type` representation.
+///
+/// Implemented for all `H5Type`s, as well as for `String`/`&str` (stored as `VarLenUnicode`)
+/// and `Vec<T>` (stored as `VarLenArray<T>`), so that slices and arrays of these can be passed
+/// directly to e.g. `Writer::write()` and `DatasetBuilder::with_data()`.
+pub trait AsH5Type: Sized {
+    /// The type that is actually stored.
+    type Repr: H5Type;
+
+    /// Converts the values into their stored representation, borrowing them if possible.
+    fn as_h5_slice(values: &[Self]) -> Result<H5Slice<'_, Self::Repr>, StringError>;
+}
+
+impl<T: H5Type> AsH5Type for T {
+    type Repr = Self;
+
+    fn as_h5_slice(values: &[Self]) -> Result<H5Slice<'_, Self>, StringError> {
+        Ok(H5Slice::Borrowed(values))
+    }
+}
+
+/// Fails if any of the strings contain internal null bytes.
+impl AsH5Type for String {
+    type Repr = VarLenUnicode;
+
+    fn as_h5_slice(values: &[Self]) -> Result<H5Slice<'_, VarLenUnicode>, StringError> {
+        values.iter().map(|s| s.parse()).collect::<Result<_, _>>().map(H5Slice::Owned)
+    }
+}
+
+/// Fails if any of the strings contain internal null bytes.
+impl AsH5Type for &str {
+    type Repr = VarLenUnicode;
+
+    fn as_h5_slice(values: &[Self]) -> Result<H5Slice<'_, VarLenUnicode>, StringError> {
+        values.iter().map(|s| s.parse()).collect::<Result<_, _>>().map(H5Slice::Owned)
+    }
+}
+
+impl<T: H5Type + Clone> AsH5Type for Vec<T> {
+    type Repr = VarLenArray<T>;
+
+    fn as_h5_slice(values: &[Self]) -> Result<H5Slice<'_, VarLenArray<T>>, StringError> {
+        Ok(H5Slice::Owned(values.iter().map(|v| VarLenArray::from_slice(v)).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsH5Type, H5Adapter};
+    use crate::array::VarLenArray;
+    use crate::string::{FixedUnicode, VarLenUnicode};
+
+    #[test]
+    pub fn test_adapters() {
+        let s = "αβγ".to_owned();
+        assert_eq!(VarLenUnicode::to_repr(&s).as_str(), "αβγ");
+        assert_eq!(<VarLenUnicode as H5Adapter<String>>::from_repr("x".parse().unwrap()), "x");
+        assert_eq!(<FixedUnicode<5> as H5Adapter<String>>::to_repr(&s).as_str(), "αβ");
+        assert_eq!(<FixedUnicode<6> as H5Adapter<String>>::to_repr(&s).as_str(), "αβγ");
+        assert_eq!(<FixedUnicode<1> as H5Adapter<String>>::to_repr(&s).as_str(), "");
+        let v = vec![1, 2, 3];
+        assert_eq!(VarLenArray::to_repr(&v).as_slice(), &[1, 2, 3]);
+        assert_eq!(VarLenArray::from_repr(VarLenArray::from_slice(&v)), v);
+    }
+
+    #[test]
+    pub fn test_as_h5_type() {
+        let values = [1, 2, 3];
+        assert!(matches!(i32::as_h5_slice(&values), Ok(super::H5Slice::Borrowed(_))));
+        let strings = ["a".to_owned(), "βγ".to_owned()];
+        let repr = String::as_h5_slice(&strings).unwrap();
+        assert_eq!(repr.iter().map(VarLenUnicode::as_str).collect::<Vec<_>>(), ["a", "βγ"]);
+        assert!(<&str>::as_h5_slice(&["a\0b"]).is_err());
+        let vecs = [vec![1, 2], vec![]];
+        let repr = Vec::as_h5_slice(&vecs).unwrap();
+        assert_eq!(repr[0].as_slice(), &[1, 2]);
+        assert!(repr[1].is_empty());
+    }
+}
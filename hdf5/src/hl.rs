@@ -1,3 +1,5 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod attribute;
 pub mod chunks;
 pub mod container;
@@ -8,15 +10,19 @@ pub mod extents;
 pub mod file;
 pub mod filters;
 pub mod group;
+pub mod link_class;
 pub mod location;
 pub mod object;
 pub mod plist;
 pub mod selection;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod table;
 
 pub use self::{
     attribute::{
-        Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
-        AttributeBuilderEmptyShape,
+        AttrValue, Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
+        AttributeBuilderEmptyShape, AttributeInfo, AttributeIter,
     },
     container::{ByteReader, Container, Reader, Writer},
     dataset::{
@@ -25,8 +31,13 @@ pub use self::{
     dataspace::Dataspace,
     datatype::{Conversion, Datatype},
     file::{File, FileBuilder, OpenMode},
-    group::{Group, LinkInfo, LinkType},
+    group::{
+        Group, GroupWithLapl, H5Group, IterationOrder, LinkInfo, LinkTarget, LinkType,
+        TraversalOrder,
+    },
+    link_class::LinkClass,
     location::{Location, LocationInfo, LocationToken, LocationType},
     object::Object,
     plist::PropertyList,
+    table::Table,
 };
@@ -0,0 +1,576 @@
+//! Conversion between compound datasets and Apache Arrow record batches.
+//!
+//! Each field of a compound datatype becomes a column of the record batch, with the types
+//! mapped as follows:
+//!
+//! - integers, floats and booleans become the corresponding primitive arrays;
+//! - fixed-length and variable-length strings become `Utf8` arrays (the padding of
+//!   fixed-length strings is stripped);
+//! - enums become dictionary arrays with the member names as values;
+//! - fixed-size arrays become `FixedSizeList` arrays and variable-length arrays become
+//!   `List` arrays;
+//! - nested compound types become `Struct` arrays.
+//!
+//! HDF5 has no notion of missing values, so all fields are non-nullable and arrays
+//! containing nulls cannot be written. To create a dataset for record batches, use the
+//! datatype returned by [`record_batch_type_descriptor()`], e.g.
+//! `builder.empty_as(&desc).shape(0..).chunk(1024)`, and then append to it via
+//! [`Dataset::append_record_batch()`].
+
+use std::convert::TryInto;
+use std::ffi::{CStr, CString};
+use std::ptr;
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    ArrowDictionaryKeyType, ArrowPrimitiveType, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, DictionaryArray, FixedSizeListArray, ListArray, PrimitiveArray,
+    RecordBatch, StringArray, StructArray,
+};
+use arrow_buffer::{ArrowNativeType, OffsetBuffer};
+use arrow_schema::{ArrowError, DataType, Field, Fields, Schema};
+
+use hdf5_sys::h5t::hvl_t;
+use hdf5_types::{
+    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, IntSize, StringPadding,
+    TypeDescriptor as TD,
+};
+
+use crate::internal_prelude::*;
+
+impl From<ArrowError> for Error {
+    fn from(err: ArrowError) -> Self {
+        format!("arrow error: {err}").into()
+    }
+}
+
+fn int_type(size: IntSize, signed: bool) -> Result<DataType> {
+    Ok(match (size, signed) {
+        (IntSize::U1, true) => DataType::Int8,
+        (IntSize::U2, true) => DataType::Int16,
+        (IntSize::U4, true) => DataType::Int32,
+        (IntSize::U8, true) => DataType::Int64,
+        (IntSize::U1, false) => DataType::UInt8,
+        (IntSize::U2, false) => DataType::UInt16,
+        (IntSize::U4, false) => DataType::UInt32,
+        (IntSize::U8, false) => DataType::UInt64,
+        (IntSize::U16, _) => fail!("128-bit integers are not supported by Arrow"),
+    })
+}
+
+fn int_descriptor(dtype: &DataType) -> Option<TD> {
+    Some(match dtype {
+        DataType::Int8 => TD::Integer(IntSize::U1),
+        DataType::Int16 => TD::Integer(IntSize::U2),
+        DataType::Int32 => TD::Integer(IntSize::U4),
+        DataType::Int64 => TD::Integer(IntSize::U8),
+        DataType::UInt8 => TD::Unsigned(IntSize::U1),
+        DataType::UInt16 => TD::Unsigned(IntSize::U2),
+        DataType::UInt32 => TD::Unsigned(IntSize::U4),
+        DataType::UInt64 => TD::Unsigned(IntSize::U8),
+        _ => return None,
+    })
+}
+
+fn item_field(dtype: DataType) -> Arc<Field> {
+    Arc::new(Field::new("item", dtype, false))
+}
+
+fn compound_fields(compound: &CompoundType) -> Result<Fields> {
+    compound
+        .fields
+        .iter()
+        .map(|f| Ok(Field::new(f.name.as_str(), to_arrow_type(&f.ty)?, false)))
+        .collect()
+}
+
+/// Returns the Arrow type that values of the given datatype are read as.
+///
+/// The datatype is converted to its native representation first, so e.g. custom-precision
+/// and non-native byte order types are supported as well.
+pub fn to_arrow_type(desc: &TD) -> Result<DataType> {
+    Ok(match desc.to_native_repr() {
+        TD::Integer(size) => int_type(size, true)?,
+        TD::Unsigned(size) => int_type(size, false)?,
+        #[cfg(feature = "f16")]
+        TD::Float(FloatSize::U2) => DataType::Float16,
+        TD::Float(FloatSize::U4) => DataType::Float32,
+        TD::Float(FloatSize::U8) => DataType::Float64,
+        TD::Boolean => DataType::Boolean,
+        TD::Enum(ref tp) => {
+            DataType::Dictionary(Box::new(int_type(tp.size, tp.signed)?), Box::new(DataType::Utf8))
+        }
+        TD::Compound(ref tp) => DataType::Struct(compound_fields(tp)?),
+        TD::FixedArray(ref ty, len) => {
+            let len = len.try_into().map_err(|_| format!("array too long: {len}"))?;
+            DataType::FixedSizeList(item_field(to_arrow_type(ty)?), len)
+        }
+        TD::VarLenArray(ref ty) => DataType::List(item_field(to_arrow_type(ty)?)),
+        TD::FixedAscii(..) | TD::FixedUnicode(..) | TD::VarLenAscii | TD::VarLenUnicode => {
+            DataType::Utf8
+        }
+        desc => fail!("unsupported datatype for Arrow conversion: {}", desc),
+    })
+}
+
+/// Returns the Arrow schema of record batches read from datasets of the given compound
+/// datatype.
+pub fn to_arrow_schema(desc: &TD) -> Result<Schema> {
+    match desc.to_native_repr() {
+        TD::Compound(ref tp) => Ok(Schema::new(compound_fields(tp)?)),
+        desc => fail!("expected a compound datatype, got {}", desc),
+    }
+}
+
+/// Returns the datatype that the values of an Arrow array are written as.
+///
+/// Dictionary arrays with string values are mapped to enums whose members are the
+/// dictionary values (the base type is the key type of the dictionary).
+pub fn array_type_descriptor(array: &dyn Array) -> Result<TD> {
+    array_type_descriptor_for(array, None)
+}
+
+/// Same as `array_type_descriptor()`, but string arrays take on the string type of the
+/// corresponding part of `target` (if any) since the library can't convert between
+/// fixed-length and variable-length strings.
+fn array_type_descriptor_for(array: &dyn Array, target: Option<&TD>) -> Result<TD> {
+    if let Some(desc) = int_descriptor(array.data_type()) {
+        return Ok(desc);
+    }
+    Ok(match array.data_type() {
+        #[cfg(feature = "f16")]
+        DataType::Float16 => TD::Float(FloatSize::U2),
+        DataType::Float32 => TD::Float(FloatSize::U4),
+        DataType::Float64 => TD::Float(FloatSize::U8),
+        DataType::Boolean => TD::Boolean,
+        DataType::Utf8 => match target {
+            Some(
+                desc @ (TD::FixedAscii(..)
+                | TD::FixedUnicode(..)
+                | TD::VarLenAscii
+                | TD::VarLenUnicode),
+            ) => desc.clone(),
+            _ => TD::VarLenUnicode,
+        },
+        DataType::Dictionary(key, value) if **value == DataType::Utf8 => {
+            let (size, signed) = match int_descriptor(key) {
+                Some(TD::Integer(size)) => (size, true),
+                Some(TD::Unsigned(size)) => (size, false),
+                _ => fail!("unsupported dictionary key type: {}", key),
+            };
+            let names = array.as_any_dictionary().values().as_string::<i32>();
+            ensure!(names.null_count() == 0, "null values are not supported");
+            let mut members = Vec::with_capacity(names.len());
+            for (value, name) in names.iter().flatten().enumerate() {
+                ensure!(
+                    members.iter().all(|m: &EnumMember| m.name != name),
+                    "duplicate dictionary value: {:?}",
+                    name
+                );
+                members.push(EnumMember { name: name.into(), value: value as _ });
+            }
+            TD::Enum(EnumType { size, signed, members })
+        }
+        DataType::Struct(fields) => {
+            let columns = array.as_struct().columns();
+            ensure!(!columns.is_empty(), "struct arrays must have at least one field");
+            let target_fields = match target {
+                Some(TD::Compound(tp)) => tp.fields.as_slice(),
+                _ => &[],
+            };
+            let fields = fields
+                .iter()
+                .zip(columns)
+                .enumerate()
+                .map(|(i, (field, column))| {
+                    let target = target_fields.iter().find(|f| &f.name == field.name());
+                    let ty = array_type_descriptor_for(column, target.map(|f| &f.ty))?;
+                    Ok(CompoundField::new(field.name(), ty, 0, i))
+                })
+                .collect::<Result<_>>()?;
+            TD::Compound(CompoundType { fields, size: 0 }.to_c_repr())
+        }
+        DataType::FixedSizeList(_, len) => {
+            let target = match target {
+                Some(TD::FixedArray(ty, _)) => Some(&**ty),
+                _ => None,
+            };
+            let values = array.as_fixed_size_list().values();
+            TD::FixedArray(Box::new(array_type_descriptor_for(values, target)?), *len as _)
+        }
+        DataType::List(_) => {
+            let target = match target {
+                Some(TD::VarLenArray(ty)) => Some(&**ty),
+                _ => None,
+            };
+            let values = array.as_list::<i32>().values();
+            TD::VarLenArray(Box::new(array_type_descriptor_for(values, target)?))
+        }
+        dtype => fail!("unsupported Arrow type: {}", dtype),
+    })
+}
+
+/// Returns the compound datatype that the rows of a record batch are written as, with a
+/// field for each of the columns.
+pub fn record_batch_type_descriptor(batch: &RecordBatch) -> Result<TD> {
+    array_type_descriptor(&StructArray::from(batch.clone()))
+}
+
+/// Byte offsets of the elements of an array within a buffer.
+type Positions = [usize];
+
+fn read_primitive<T: ArrowPrimitiveType>(buf: &[u8], positions: &Positions) -> ArrayRef {
+    let size = std::mem::size_of::<T::Native>();
+    Arc::new(PrimitiveArray::<T>::from_iter_values(positions.iter().map(|&pos| {
+        let bytes = &buf[pos..pos + size];
+        unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<T::Native>()) }
+    })))
+}
+
+fn read_int(buf: &[u8], pos: usize, size: usize, signed: bool) -> u64 {
+    let mut bytes = [0; 8];
+    if cfg!(target_endian = "little") {
+        bytes[..size].copy_from_slice(&buf[pos..pos + size]);
+    } else {
+        bytes[8 - size..].copy_from_slice(&buf[pos..pos + size]);
+    }
+    let value = u64::from_ne_bytes(bytes);
+    let shift = 64 - 8 * size as u32;
+    if signed && shift < 64 {
+        (((value << shift) as i64) >> shift) as _
+    } else {
+        value
+    }
+}
+
+fn read_dictionary<K: ArrowDictionaryKeyType>(
+    buf: &[u8], positions: &Positions, tp: &EnumType,
+) -> Result<ArrayRef> {
+    let keys = positions
+        .iter()
+        .map(|&pos| {
+            let value = read_int(buf, pos, tp.size as _, tp.signed);
+            let index = tp.members.iter().position(|m| m.value == value);
+            index
+                .and_then(K::Native::from_usize)
+                .ok_or_else(|| format!("invalid enum value: {value}").into())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let names = StringArray::from_iter_values(tp.members.iter().map(|m| m.name.as_str()));
+    let keys = PrimitiveArray::<K>::from_iter_values(keys);
+    Ok(Arc::new(DictionaryArray::<K>::try_new(keys, Arc::new(names))?))
+}
+
+unsafe fn read_ptr<T>(buf: &[u8], pos: usize) -> T {
+    let bytes = &buf[pos..pos + std::mem::size_of::<T>()];
+    ptr::read_unaligned(bytes.as_ptr().cast())
+}
+
+/// Builds an array out of the elements at the given positions in a buffer holding values of
+/// the given (native, C-layout) datatype.
+fn read_array(desc: &TD, buf: &[u8], positions: &Positions) -> Result<ArrayRef> {
+    Ok(match *desc {
+        TD::Integer(IntSize::U1) => read_primitive::<Int8Type>(buf, positions),
+        TD::Integer(IntSize::U2) => read_primitive::<Int16Type>(buf, positions),
+        TD::Integer(IntSize::U4) => read_primitive::<Int32Type>(buf, positions),
+        TD::Integer(IntSize::U8) => read_primitive::<Int64Type>(buf, positions),
+        TD::Unsigned(IntSize::U1) => read_primitive::<UInt8Type>(buf, positions),
+        TD::Unsigned(IntSize::U2) => read_primitive::<UInt16Type>(buf, positions),
+        TD::Unsigned(IntSize::U4) => read_primitive::<UInt32Type>(buf, positions),
+        TD::Unsigned(IntSize::U8) => read_primitive::<UInt64Type>(buf, positions),
+        #[cfg(feature = "f16")]
+        TD::Float(FloatSize::U2) => {
+            read_primitive::<arrow_array::types::Float16Type>(buf, positions)
+        }
+        TD::Float(FloatSize::U4) => read_primitive::<Float32Type>(buf, positions),
+        TD::Float(FloatSize::U8) => read_primitive::<Float64Type>(buf, positions),
+        TD::Boolean => {
+            Arc::new(BooleanArray::from(positions.iter().map(|&p| buf[p] != 0).collect::<Vec<_>>()))
+        }
+        TD::Enum(ref tp) => match (tp.size, tp.signed) {
+            (IntSize::U1, true) => read_dictionary::<Int8Type>(buf, positions, tp)?,
+            (IntSize::U2, true) => read_dictionary::<Int16Type>(buf, positions, tp)?,
+            (IntSize::U4, true) => read_dictionary::<Int32Type>(buf, positions, tp)?,
+            (IntSize::U8, true) => read_dictionary::<Int64Type>(buf, positions, tp)?,
+            (IntSize::U1, false) => read_dictionary::<UInt8Type>(buf, positions, tp)?,
+            (IntSize::U2, false) => read_dictionary::<UInt16Type>(buf, positions, tp)?,
+            (IntSize::U4, false) => read_dictionary::<UInt32Type>(buf, positions, tp)?,
+            (IntSize::U8, false) => read_dictionary::<UInt64Type>(buf, positions, tp)?,
+            (IntSize::U16, _) => fail!("128-bit integers are not supported by Arrow"),
+        },
+        TD::FixedAscii(len, padding) | TD::FixedUnicode(len, padding) => {
+            Arc::new(StringArray::from_iter_values(positions.iter().map(|&pos| {
+                String::from_utf8_lossy(padding.trim(&buf[pos..pos + len])).into_owned()
+            })))
+        }
+        TD::VarLenAscii | TD::VarLenUnicode => {
+            Arc::new(StringArray::from_iter_values(positions.iter().map(|&pos| {
+                let ptr: *const c_char = unsafe { read_ptr(buf, pos) };
+                if ptr.is_null() {
+                    String::new()
+                } else {
+                    String::from_utf8_lossy(unsafe { CStr::from_ptr(ptr) }.to_bytes()).into_owned()
+                }
+            })))
+        }
+        TD::Compound(ref tp) => {
+            ensure!(!tp.fields.is_empty(), "compound types must have at least one field");
+            let fields = compound_fields(tp)?;
+            let columns = tp
+                .fields
+                .iter()
+                .map(|f| {
+                    let positions: Vec<_> = positions.iter().map(|&p| p + f.offset).collect();
+                    read_array(&f.ty, buf, &positions)
+                })
+                .collect::<Result<_>>()?;
+            Arc::new(StructArray::try_new(fields, columns, None)?)
+        }
+        TD::FixedArray(ref ty, len) => {
+            let size = ty.size();
+            let positions: Vec<_> =
+                positions.iter().flat_map(|&p| (0..len).map(move |i| p + i * size)).collect();
+            let values = read_array(ty, buf, &positions)?;
+            let field = item_field(values.data_type().clone());
+            Arc::new(FixedSizeListArray::try_new(field, len as _, values, None)?)
+        }
+        TD::VarLenArray(ref ty) => {
+            // gather the elements of all arrays into a single contiguous buffer
+            let size = ty.size();
+            let (mut items, mut lengths) = (Vec::new(), Vec::with_capacity(positions.len()));
+            for &pos in positions {
+                let vl: hvl_t = unsafe { read_ptr(buf, pos) };
+                let len = if vl.p.is_null() { 0 } else { vl.len };
+                if len > 0 {
+                    let data = unsafe { std::slice::from_raw_parts(vl.p.cast::<u8>(), len * size) };
+                    items.extend_from_slice(data);
+                }
+                lengths.push(len);
+            }
+            let positions: Vec<_> = (0..items.len() / size.max(1)).map(|i| i * size).collect();
+            let values = read_array(ty, &items, &positions)?;
+            let field = item_field(values.data_type().clone());
+            let offsets = OffsetBuffer::from_lengths(lengths);
+            Arc::new(ListArray::try_new(field, offsets, values, None)?)
+        }
+        ref desc => fail!("unsupported datatype for Arrow conversion: {}", desc),
+    })
+}
+
+/// Reads a selection of a one-dimensional compound dataset, see
+/// [`Dataset::read_record_batch_slice()`].
+pub(crate) fn read_record_batch<S>(ds: &Dataset, selection: S) -> Result<RecordBatch>
+where
+    S: TryInto<Selection>,
+    Error: From<S::Error>,
+{
+    ensure!(ds.ndim() == 1, "expected a one-dimensional dataset, got {} dimensions", ds.ndim());
+    let desc = ds.dtype()?.to_descriptor()?.to_native_repr().to_c_repr();
+    let schema = Arc::new(to_arrow_schema(&desc)?);
+    let compound = match desc {
+        TD::Compound(ref tp) => tp,
+        _ => unreachable!(),
+    };
+    ensure!(!compound.fields.is_empty(), "compound types must have at least one field");
+
+    let selection = selection.try_into()?;
+    let space = ds.space()?;
+    let out_shape = selection.out_shape(space.shape())?;
+    ensure!(out_shape.len() == 1, "expected a one-dimensional selection, got {:?}", out_shape);
+    let len = out_shape[0];
+    let (fspace, mspace) = (space.select(selection)?, Dataspace::try_new(len)?);
+    let mem_dtype = Datatype::from_descriptor(&desc)?;
+
+    let to_batch = |buf: &[u8]| -> Result<RecordBatch> {
+        let columns = compound
+            .fields
+            .iter()
+            .map(|f| {
+                let positions: Vec<_> = (0..len).map(|i| i * compound.size + f.offset).collect();
+                read_array(&f.ty, buf, &positions)
+            })
+            .collect::<Result<_>>()?;
+        Ok(RecordBatch::try_new(schema.clone(), columns)?)
+    };
+    if len == 0 {
+        return to_batch(&[]);
+    }
+    let reader = ds.as_reader();
+    reader.read_reclaim(&mem_dtype, len * compound.size, Some((&fspace, &mspace)), to_batch)?
+}
+
+/// Memory referenced by the buffer being written (var-len array elements and strings).
+#[derive(Default)]
+struct WriteArena {
+    items: Vec<Vec<u8>>,
+    strings: Vec<CString>,
+}
+
+fn write_primitive<T: ArrowPrimitiveType>(
+    array: &dyn Array, buf: &mut [u8], positions: &Positions,
+) {
+    let size = std::mem::size_of::<T::Native>();
+    for (&value, &pos) in array.as_primitive::<T>().values().iter().zip(positions) {
+        let bytes = &mut buf[pos..pos + size];
+        unsafe { ptr::write_unaligned(bytes.as_mut_ptr().cast::<T::Native>(), value) };
+    }
+}
+
+unsafe fn write_ptr<T>(buf: &mut [u8], pos: usize, value: T) {
+    let bytes = &mut buf[pos..pos + std::mem::size_of::<T>()];
+    ptr::write_unaligned(bytes.as_mut_ptr().cast(), value);
+}
+
+/// Writes the elements of an array to the given positions in a buffer holding values of the
+/// datatype returned by [`array_type_descriptor()`].
+fn write_array(
+    array: &dyn Array, desc: &TD, buf: &mut [u8], positions: &Positions, arena: &mut WriteArena,
+) -> Result<()> {
+    ensure!(array.logical_null_count() == 0, "null values are not supported");
+    match (array.data_type(), desc) {
+        (DataType::Int8, _) => write_primitive::<Int8Type>(array, buf, positions),
+        (DataType::Int16, _) => write_primitive::<Int16Type>(array, buf, positions),
+        (DataType::Int32, _) => write_primitive::<Int32Type>(array, buf, positions),
+        (DataType::Int64, _) => write_primitive::<Int64Type>(array, buf, positions),
+        (DataType::UInt8, _) => write_primitive::<UInt8Type>(array, buf, positions),
+        (DataType::UInt16, _) => write_primitive::<UInt16Type>(array, buf, positions),
+        (DataType::UInt32, _) => write_primitive::<UInt32Type>(array, buf, positions),
+        (DataType::UInt64, _) => write_primitive::<UInt64Type>(array, buf, positions),
+        #[cfg(feature = "f16")]
+        (DataType::Float16, _) => {
+            write_primitive::<arrow_array::types::Float16Type>(array, buf, positions);
+        }
+        (DataType::Float32, _) => write_primitive::<Float32Type>(array, buf, positions),
+        (DataType::Float64, _) => write_primitive::<Float64Type>(array, buf, positions),
+        (DataType::Boolean, _) => {
+            for (value, &pos) in array.as_boolean().values().iter().zip(positions) {
+                buf[pos] = value.into();
+            }
+        }
+        (DataType::Utf8, desc) => {
+            let ascii = matches!(desc, TD::FixedAscii(..) | TD::VarLenAscii);
+            for (value, &pos) in array.as_string::<i32>().iter().zip(positions) {
+                let value = value.unwrap_or_default();
+                ensure!(
+                    !ascii || value.is_ascii(),
+                    "cannot store non-ASCII string as ASCII: {:?}",
+                    value
+                );
+                if let TD::FixedAscii(len, padding) | TD::FixedUnicode(len, padding) = *desc {
+                    ensure!(!value.contains('\0'), "null byte in string: {:?}", value);
+                    let max_len = if padding == StringPadding::NullTerm {
+                        len.saturating_sub(1)
+                    } else {
+                        len
+                    };
+                    ensure!(
+                        value.len() <= max_len,
+                        "string of length {} exceeds fixed length {}: {:?}",
+                        value.len(),
+                        max_len,
+                        value
+                    );
+                    let chunk = &mut buf[pos..pos + len];
+                    chunk.fill(padding.pad_byte());
+                    chunk[..value.len()].copy_from_slice(value.as_bytes());
+                } else {
+                    let value = to_cstring(value)?;
+                    unsafe { write_ptr(buf, pos, value.as_ptr()) };
+                    arena.strings.push(value);
+                }
+            }
+        }
+        (DataType::Dictionary(..), TD::Enum(tp)) => {
+            let size = tp.size as usize;
+            for (index, &pos) in array.as_any_dictionary().normalized_keys().iter().zip(positions) {
+                let bytes = (*index as u64).to_ne_bytes();
+                let bytes = if cfg!(target_endian = "little") {
+                    &bytes[..size]
+                } else {
+                    &bytes[8 - size..]
+                };
+                buf[pos..pos + size].copy_from_slice(bytes);
+            }
+        }
+        (DataType::Struct(_), TD::Compound(tp)) => {
+            for (column, f) in array.as_struct().columns().iter().zip(&tp.fields) {
+                let positions: Vec<_> = positions.iter().map(|&p| p + f.offset).collect();
+                write_array(column, &f.ty, buf, &positions, arena)?;
+            }
+        }
+        (DataType::FixedSizeList(..), TD::FixedArray(ty, len)) => {
+            let array = array.as_fixed_size_list();
+            let size = ty.size();
+            for (i, &pos) in positions.iter().enumerate() {
+                let values = array.values().slice(array.value_offset(i) as _, *len);
+                let positions: Vec<_> = (0..*len).map(|j| pos + j * size).collect();
+                write_array(&values, ty, buf, &positions, arena)?;
+            }
+        }
+        (DataType::List(_), TD::VarLenArray(ty)) => {
+            // write the referenced elements into a separate contiguous buffer
+            let array = array.as_list::<i32>();
+            let offsets = array.value_offsets();
+            let (start, end) = (offsets[0] as usize, offsets[offsets.len() - 1] as usize);
+            let size = ty.size();
+            let mut items = vec![0_u8; (end - start) * size];
+            let values = array.values().slice(start, end - start);
+            let item_positions: Vec<_> = (0..end - start).map(|i| i * size).collect();
+            write_array(&values, ty, &mut items, &item_positions, arena)?;
+            for (i, &pos) in positions.iter().enumerate() {
+                let (lo, hi) = (offsets[i] as usize - start, offsets[i + 1] as usize - start);
+                let p =
+                    if hi > lo { items[lo * size..].as_mut_ptr().cast() } else { ptr::null_mut() };
+                unsafe { write_ptr(buf, pos, hvl_t { len: hi - lo, p }) };
+            }
+            arena.items.push(items);
+        }
+        (dtype, desc) => fail!("cannot write Arrow type {} as {}", dtype, desc),
+    }
+    Ok(())
+}
+
+/// Writes a record batch into a one-dimensional compound dataset starting at the given row,
+/// see [`Dataset::write_record_batch()`].
+pub(crate) fn write_record_batch(ds: &Dataset, batch: &RecordBatch, start: usize) -> Result<()> {
+    ensure!(ds.ndim() == 1, "expected a one-dimensional dataset, got {} dimensions", ds.ndim());
+    let file_desc = ds.dtype()?.to_descriptor()?.to_native_repr();
+    let file_fields = match file_desc {
+        TD::Compound(ref tp) => &tp.fields,
+        ref desc => fail!("expected a compound datatype, got {}", desc),
+    };
+    for field in batch.schema().fields() {
+        ensure!(
+            file_fields.iter().any(|f| &f.name == field.name()),
+            "field {:?} not found in the dataset",
+            field.name()
+        );
+    }
+    let len = batch.num_rows();
+    ensure!(
+        start + len <= ds.size(),
+        "record batch of {} rows doesn't fit into dataset of size {} at row {}",
+        len,
+        ds.size(),
+        start
+    );
+    if len == 0 {
+        return Ok(());
+    }
+
+    let array = StructArray::from(batch.clone());
+    let desc = array_type_descriptor_for(&array, Some(&file_desc))?;
+    let mut buf = vec![0_u8; len * desc.size()];
+    let positions: Vec<_> = (0..len).map(|i| i * desc.size()).collect();
+    let mut arena = WriteArena::default();
+    write_array(&array, &desc, &mut buf, &positions, &mut arena)?;
+
+    let mem_dtype = Datatype::from_descriptor(&desc)?;
+    let fspace = ds.space()?.select(start..start + len)?;
+    let mspace = Dataspace::try_new(len)?;
+    ds.as_writer().write_from_raw(&mem_dtype, buf.as_ptr().cast(), Some(&fspace), Some(&mspace))
+}
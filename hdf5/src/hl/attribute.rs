@@ -4,11 +4,16 @@ use std::ptr::addr_of_mut;
 
 use hdf5_sys::{
     h5::{H5_index_t, H5_iter_order_t},
-    h5a::{H5A_info_t, H5A_operator2_t, H5Acreate2, H5Adelete, H5Aiterate2},
+    h5a::{
+        H5A_info_t, H5A_operator2_t, H5Acreate2, H5Adelete, H5Aget_info, H5Aget_name, H5Aiterate2,
+        H5Aopen_by_idx,
+    },
+    h5t::H5T_cset_t,
 };
-use hdf5_types::TypeDescriptor;
-use ndarray::ArrayView;
+use hdf5_types::{OwnedDynValue, TypeDescriptor};
+use ndarray::{ArrayD, ArrayView};
 
+use crate::hl::group::{IterationOrder, TraversalOrder};
 use crate::internal_prelude::*;
 
 /// Represents the HDF5 attribute object.
@@ -77,8 +82,172 @@ impl Attribute {
 
         Ok(result)
     }
+
+    /// Returns the name of the attribute.
+    ///
+    /// Note that this differs from `Location::name()`, which would return the name of the
+    /// object the attribute is attached to.
+    pub fn name(&self) -> String {
+        h5lock!(get_h5_str(|m, s| H5Aget_name(self.id(), s, m)).unwrap_or_else(|_| String::new()))
+    }
+
+    /// Returns metadata information about the attribute.
+    pub fn info(&self) -> Result<AttributeInfo> {
+        let mut info = H5A_info_t::default();
+        h5call!(H5Aget_info(self.id(), &mut info)).map(|_| (&info).into())
+    }
+
+    /// Reads the attribute without knowing its type in advance.
+    ///
+    /// The in-memory type is derived from the stored datatype via `Datatype::to_descriptor()`
+    /// and converted via `TypeDescriptor::to_native_repr()` (i.e. numeric values are read in
+    /// native byte order, custom-precision values as the nearest native types).
+    pub fn read_value(&self) -> Result<AttrValue> {
+        self.read_dyn_value()
+    }
+
+    /// Writes a dynamically typed value into the attribute.
+    ///
+    /// The shape of the value must match the shape of the attribute, and all array elements
+    /// must share the same type.
+    pub fn write_value(&self, value: &AttrValue) -> Result<()> {
+        self.write_dyn_value(value)
+    }
+}
+
+/// A dynamically typed attribute value, see `Location::read_attrs()`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttrValue {
+    /// Value of an attribute with a scalar dataspace
+    Scalar(OwnedDynValue),
+    /// Values of an attribute with a simple dataspace
+    Array(ArrayD<OwnedDynValue>),
+    /// Attribute with a simple dataspace of the given shape containing no elements
+    Empty(TypeDescriptor, Vec<Ix>),
+    /// Attribute with a null dataspace
+    Null(TypeDescriptor),
 }
 
+impl AttrValue {
+    /// Returns the shape of the value; scalars and null values have an empty shape.
+    pub fn shape(&self) -> &[Ix] {
+        match self {
+            Self::Scalar(_) | Self::Null(_) => &[],
+            Self::Array(arr) => arr.shape(),
+            Self::Empty(_, shape) => shape,
+        }
+    }
+
+    /// Returns the extents of the dataspace holding the value.
+    pub fn extents(&self) -> Extents {
+        match self {
+            Self::Null(_) => Extents::Null,
+            _ => self.shape().into(),
+        }
+    }
+
+    /// Returns the total number of elements.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Scalar(_) => 1,
+            Self::Array(arr) => arr.len(),
+            Self::Empty(..) | Self::Null(_) => 0,
+        }
+    }
+
+    /// Returns `true` if the value has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the type of the value, or `None` if it is an array value with no elements.
+    pub fn type_descriptor(&self) -> Option<&TypeDescriptor> {
+        match self {
+            Self::Empty(desc, _) | Self::Null(desc) => Some(desc),
+            _ => self.iter().next().map(OwnedDynValue::type_descriptor),
+        }
+    }
+
+    /// Iterates over all elements in logical (row-major) order.
+    pub fn iter(&self) -> impl Iterator<Item = &OwnedDynValue> {
+        let (scalar, array) = match self {
+            Self::Scalar(value) => (Some(value), None),
+            Self::Array(arr) => (None, Some(arr.iter())),
+            Self::Empty(..) | Self::Null(_) => (None, None),
+        };
+        scalar.into_iter().chain(array.into_iter().flatten())
+    }
+}
+
+/// Metadata information describing an [`Attribute`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttributeInfo {
+    /// Creation order of the attribute, if it is being tracked
+    pub creation_order: Option<u32>,
+    /// Whether the attribute name is UTF-8 encoded
+    pub is_utf8: bool,
+    /// Size of the attribute data in bytes
+    pub data_size: u64,
+}
+
+impl From<&H5A_info_t> for AttributeInfo {
+    fn from(info: &H5A_info_t) -> Self {
+        let creation_order = if info.corder_valid == 1 { Some(info.corder) } else { None };
+        let is_utf8 = info.cset == H5T_cset_t::H5T_CSET_UTF8;
+        Self { creation_order, is_utf8, data_size: info.data_size as _ }
+    }
+}
+
+/// Iterator over the attributes attached to a [`Location`].
+///
+/// Attributes are opened lazily, one at a time, by their index in the requested order.
+#[derive(Debug)]
+pub struct AttributeIter {
+    loc: Location,
+    traversal_order: TraversalOrder,
+    iteration_order: IterationOrder,
+    pos: usize,
+    len: usize,
+}
+
+impl AttributeIter {
+    pub(crate) fn new(
+        loc: &Location, traversal_order: TraversalOrder, iteration_order: IterationOrder,
+    ) -> Result<Self> {
+        let len = loc.loc_info()?.num_attrs;
+        Ok(Self { loc: loc.clone(), traversal_order, iteration_order, pos: 0, len })
+    }
+}
+
+impl Iterator for AttributeIter {
+    type Item = Result<Attribute>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.len {
+            return None;
+        }
+        let n = self.pos as hsize_t;
+        self.pos += 1;
+        let id = h5call!(H5Aopen_by_idx(
+            self.loc.id(),
+            b".\0".as_ptr().cast(),
+            self.traversal_order.into(),
+            self.iteration_order.into(),
+            n,
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+        ));
+        Some(id.and_then(Attribute::from_id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for AttributeIter {}
+
 #[derive(Clone)]
 /// An attribute builder
 pub struct AttributeBuilder {
@@ -129,6 +298,14 @@ impl AttributeBuilder {
         self.builder.packed(packed);
         self
     }
+
+    /// Uses the given (typically committed) datatype as the in-file datatype of the attribute.
+    #[inline]
+    #[must_use]
+    pub fn committed_type(mut self, dtype: &Datatype) -> Self {
+        self.builder.committed_type(dtype);
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -156,6 +333,14 @@ impl AttributeBuilderEmpty {
         self.builder.packed(packed);
         self
     }
+
+    /// Uses the given (typically committed) datatype as the in-file datatype of the attribute.
+    #[inline]
+    #[must_use]
+    pub fn committed_type(mut self, dtype: &Datatype) -> Self {
+        self.builder.committed_type(dtype);
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -177,6 +362,14 @@ impl AttributeBuilderEmptyShape {
         self.builder.packed(packed);
         self
     }
+
+    /// Uses the given (typically committed) datatype as the in-file datatype of the attribute.
+    #[inline]
+    #[must_use]
+    pub fn committed_type(mut self, dtype: &Datatype) -> Self {
+        self.builder.committed_type(dtype);
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -215,7 +408,10 @@ where
 
         h5lock!({
             let dtype_src = Datatype::from_type::<T>()?;
-            let dtype_dst = Datatype::from_descriptor(&self.type_desc)?;
+            let dtype_dst = match self.builder.committed_type {
+                Some(ref dtype) => dtype.clone(),
+                None => Datatype::from_descriptor(&self.type_desc)?,
+            };
             dtype_src.ensure_convertible(&dtype_dst, self.conv)?;
             let ds = self.builder.create(&self.type_desc, name, &extents)?;
             if let Err(err) = ds.write(self.data.view()) {
@@ -233,6 +429,14 @@ where
         self.builder.packed(packed);
         self
     }
+
+    /// Uses the given (typically committed) datatype as the in-file datatype of the attribute.
+    #[inline]
+    #[must_use]
+    pub fn committed_type(mut self, dtype: &Datatype) -> Self {
+        self.builder.committed_type(dtype);
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -240,24 +444,33 @@ where
 struct AttributeBuilderInner {
     parent: Result<Handle>,
     packed: bool,
+    committed_type: Option<Datatype>,
 }
 
 impl AttributeBuilderInner {
     pub fn new(parent: &Location) -> Self {
-        Self { parent: parent.try_borrow(), packed: false }
+        Self { parent: parent.try_borrow(), packed: false, committed_type: None }
     }
 
     pub fn packed(&mut self, packed: bool) {
         self.packed = packed;
     }
 
+    pub fn committed_type(&mut self, dtype: &Datatype) {
+        self.committed_type = Some(dtype.clone());
+    }
+
     unsafe fn create(
         &self, desc: &TypeDescriptor, name: &str, extents: &Extents,
     ) -> Result<Attribute> {
-        // construct in-file type descriptor; convert to packed representation if needed
-        let desc = if self.packed { desc.to_packed_repr() } else { desc.to_c_repr() };
-
-        let datatype = Datatype::from_descriptor(&desc)?;
+        // use the committed datatype if provided, otherwise construct in-file type
+        // descriptor (converting it to packed representation if needed)
+        let datatype = if let Some(ref dtype) = self.committed_type {
+            dtype.clone()
+        } else {
+            let desc = if self.packed { desc.to_packed_repr() } else { desc.to_c_repr() };
+            Datatype::from_descriptor(&desc)?
+        };
         let parent = try_ref_clone!(self.parent);
 
         let dataspace = Dataspace::try_new(extents)?;
@@ -285,8 +498,12 @@ impl AttributeBuilderInner {
 
 #[cfg(test)]
 pub mod attribute_tests {
+    use crate::dataset::AttrCreationOrder;
     use crate::internal_prelude::*;
-    use ndarray::{arr2, Array2};
+    use crate::Extents;
+    use hdf5_types::OwnedDynValue;
+    use ndarray::{arr2, Array2, ArrayD};
+    use std::collections::BTreeMap;
     use std::str::FromStr;
     use types::VarLenUnicode;
 
@@ -368,9 +585,7 @@ pub mod attribute_tests {
             let attr = file.new_attr::<u32>().shape((1, 2)).create("foo").unwrap();
             assert!(attr.is_valid());
             assert_eq!(attr.shape(), vec![1, 2]);
-            // FIXME - attr.name() returns "/" here, which is the name the attribute is connected to,
-            // not the name of the attribute.
-            //assert_eq!(attr.name(), "foo");
+            assert_eq!(attr.name(), "foo");
             assert_eq!(file.attr("foo").unwrap().shape(), vec![1, 2]);
         })
     }
@@ -383,9 +598,7 @@ pub mod attribute_tests {
             let attr = file.new_attr_builder().with_data(&arr).create("foo").unwrap();
             assert!(attr.is_valid());
             assert_eq!(attr.shape(), vec![2, 3]);
-            // FIXME - attr.name() returns "/" here, which is the name the attribute is connected to,
-            // not the name of the attribute.
-            //assert_eq!(attr.name(), "foo");
+            assert_eq!(attr.name(), "foo");
             assert_eq!(file.attr("foo").unwrap().shape(), vec![2, 3]);
 
             let read_attr = file.attr("foo").unwrap();
@@ -429,4 +642,168 @@ pub mod attribute_tests {
             assert!(attr_names.contains(&"bar".to_string()));
         })
     }
+
+    #[test]
+    pub fn test_exists_rename_delete() {
+        with_tmp_file(|file| {
+            assert!(!file.attr_exists("foo"));
+            file.new_attr::<u32>().create("foo").unwrap();
+            assert!(file.attr_exists("foo"));
+            assert!(!file.attr_exists("bar"));
+
+            file.rename_attr("foo", "bar").unwrap();
+            assert!(!file.attr_exists("foo"));
+            assert!(file.attr_exists("bar"));
+            assert_err!(file.rename_attr("foo", "baz"), "can't locate attribute");
+
+            file.delete_attr("bar").unwrap();
+            assert!(!file.attr_exists("bar"));
+            assert!(file.attr_names().unwrap().is_empty());
+            assert!(file.delete_attr("bar").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_attrs_iter() {
+        with_tmp_file(|file| {
+            assert_eq!(file.attrs().unwrap().count(), 0);
+
+            let ds = file
+                .new_dataset::<u8>()
+                .attr_creation_order(AttrCreationOrder::TRACKED | AttrCreationOrder::INDEXED)
+                .create("ds")
+                .unwrap();
+            for name in ["c", "a", "b"] {
+                ds.new_attr::<u8>().create(name).unwrap();
+            }
+
+            let names = |iter: AttributeIter| -> Vec<String> {
+                iter.map(|attr| attr.unwrap().name()).collect()
+            };
+            let iter = ds.attrs().unwrap();
+            assert_eq!(iter.len(), 3);
+            assert_eq!(names(iter), vec!["a", "b", "c"]);
+            let iter = ds.attrs_by(TraversalOrder::Name, IterationOrder::Decreasing).unwrap();
+            assert_eq!(names(iter), vec!["c", "b", "a"]);
+            let iter = ds.attrs_by(TraversalOrder::Creation, IterationOrder::Increasing).unwrap();
+            assert_eq!(names(iter), vec!["c", "a", "b"]);
+
+            let attr = ds
+                .attrs_by(TraversalOrder::Creation, IterationOrder::Decreasing)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap();
+            attr.write_scalar(&7_u8).unwrap();
+            assert_eq!(ds.attr("b").unwrap().read_scalar::<u8>().unwrap(), 7);
+        })
+    }
+
+    #[test]
+    pub fn test_info() {
+        with_tmp_file(|file| {
+            let attr = file.new_attr::<f64>().shape((2, 5)).create("foo").unwrap();
+            let info = attr.info().unwrap();
+            assert_eq!(info.data_size, 80);
+            assert_eq!(info.creation_order, None);
+            assert!(!info.is_utf8);
+
+            let ds = file
+                .new_dataset::<u8>()
+                .attr_creation_order(AttrCreationOrder::TRACKED)
+                .create("ds")
+                .unwrap();
+            ds.new_attr::<u8>().create("a").unwrap();
+            let attr = ds.new_attr::<u16>().shape(3).create("b").unwrap();
+            let info = attr.info().unwrap();
+            assert_eq!(info.creation_order, Some(1));
+            assert_eq!(info.data_size, 6);
+        })
+    }
+
+    #[test]
+    pub fn test_set_attr() {
+        with_tmp_file(|file| {
+            let attr = file.set_attr("foo", &[1_i32, 2, 3]).unwrap();
+            assert_eq!(attr.read_raw::<i32>().unwrap(), vec![1, 2, 3]);
+
+            // same type and shape: written in place
+            file.set_attr("foo", &[4_i32, 5, 6]).unwrap();
+            assert_eq!(file.attr("foo").unwrap().read_raw::<i32>().unwrap(), vec![4, 5, 6]);
+
+            // different shape: replaced
+            file.set_attr("foo", &arr2(&[[1_i32, 2], [3, 4]])).unwrap();
+            let attr = file.attr("foo").unwrap();
+            assert_eq!(attr.shape(), vec![2, 2]);
+            assert_eq!(attr.read_2d::<i32>().unwrap(), arr2(&[[1, 2], [3, 4]]));
+
+            // different type: replaced
+            file.set_attr("foo", &[1.5_f64, 2.5]).unwrap();
+            let attr = file.attr("foo").unwrap();
+            assert!(attr.dtype().unwrap().is::<f64>());
+            assert_eq!(attr.read_raw::<f64>().unwrap(), vec![1.5, 2.5]);
+
+            // scalar
+            file.set_attr("bar", ndarray::arr0(42_u8).view()).unwrap();
+            assert_eq!(file.attr("bar").unwrap().read_scalar::<u8>().unwrap(), 42);
+            assert_eq!(file.attr_names().unwrap().len(), 2);
+        })
+    }
+
+    #[test]
+    pub fn test_read_write_attrs() {
+        with_tmp_file(|file| {
+            assert!(file.read_attrs().unwrap().is_empty());
+
+            let s = VarLenUnicode::from_str("hello").unwrap();
+            file.new_attr_builder().with_data(&arr2(&[[1_i16, 2], [3, 4]])).create("arr").unwrap();
+            file.new_attr::<f64>().create("float").unwrap().write_scalar(&1.5).unwrap();
+            file.new_attr::<VarLenUnicode>().create("str").unwrap().write_scalar(&s).unwrap();
+
+            let attrs = file.read_attrs().unwrap();
+            assert_eq!(attrs.keys().collect::<Vec<_>>(), vec!["arr", "float", "str"]);
+            match &attrs["arr"] {
+                AttrValue::Array(arr) => {
+                    assert_eq!(arr.shape(), &[2, 2]);
+                    assert_eq!(arr[[1, 0]].clone().cast::<i16>().unwrap(), 3);
+                }
+                value => panic!("unexpected value: {:?}", value),
+            }
+            assert_eq!(attrs["float"], AttrValue::Scalar(OwnedDynValue::new(1.5_f64)));
+            assert_eq!(attrs["str"], AttrValue::Scalar(OwnedDynValue::new(s.clone())));
+            assert_eq!(attrs["str"].shape(), &[] as &[usize]);
+
+            let group = file.create_group("foo").unwrap();
+            group.new_attr::<u8>().shape(5).create("arr").unwrap();
+            group.write_attrs(&attrs).unwrap();
+            assert_eq!(group.read_attrs().unwrap(), attrs);
+            assert_eq!(
+                group.attr("arr").unwrap().read_2d::<i16>().unwrap(),
+                arr2(&[[1, 2], [3, 4]])
+            );
+            assert_eq!(group.attr("str").unwrap().read_scalar::<VarLenUnicode>().unwrap(), s);
+
+            let mut empty = BTreeMap::new();
+            empty.insert(
+                "empty".to_owned(),
+                AttrValue::Array(ArrayD::from_shape_vec(vec![0], vec![]).unwrap()),
+            );
+            assert_err!(group.write_attrs(&empty), "cannot infer datatype of empty attribute");
+
+            // empty and null attributes keep their datatypes
+            file.new_attr::<u16>().shape((2, 0)).create("none").unwrap();
+            file.new_attr::<f32>().shape(Extents::Null).create("null").unwrap();
+            let attrs = file.read_attrs().unwrap();
+            assert_eq!(attrs["none"], AttrValue::Empty(u16::type_descriptor(), vec![2, 0]));
+            assert_eq!(attrs["null"], AttrValue::Null(f32::type_descriptor()));
+            assert!(attrs["null"].is_empty());
+            let other = file.create_group("bar").unwrap();
+            other.write_attrs(&attrs).unwrap();
+            assert_eq!(other.read_attrs().unwrap(), attrs);
+            assert!(other.attr("null").unwrap().space().unwrap().is_null());
+            assert_eq!(other.attr("none").unwrap().shape(), vec![2, 0]);
+            let null = AttrValue::Null(f32::type_descriptor());
+            assert_err!(group.attr("float").unwrap().write_value(&null), "null dataspace mismatch");
+        })
+    }
 }
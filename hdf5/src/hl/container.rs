@@ -1,21 +1,50 @@
 use std::convert::TryInto;
+use std::ffi::CStr;
 use std::fmt::{self, Debug};
 use std::io;
 use std::mem;
 use std::ops::Deref;
+use std::slice;
 
-use ndarray::{Array, Array1, Array2, ArrayD, ArrayView, ArrayView1};
+use hdf5_types::{
+    AsH5Type, H5Proxy, H5Slice, OwnedDynValue, StringPadding, TimeConversion, TypeDescriptor as TD,
+};
+use ndarray::{Array, Array1, Array2, ArrayD, ArrayView, ArrayView1, CowArray};
 
 use hdf5_sys::h5a::{H5Aget_space, H5Aget_storage_size, H5Aget_type, H5Aread, H5Awrite};
+#[cfg(not(feature = "1.12.0"))]
+use hdf5_sys::h5d::H5Dvlen_reclaim;
 use hdf5_sys::h5d::{H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dread, H5Dwrite};
 use hdf5_sys::h5p::H5Pcreate;
+use hdf5_sys::h5t::hvl_t;
+#[cfg(feature = "1.12.0")]
+use hdf5_sys::h5t::H5Treclaim;
 
 use crate::internal_prelude::*;
 
+/// Checks that time types (see `H5Time`) are only converted to or from counts in the units of
+/// the type, if the dataset has a CF-style `units` attribute.
+fn check_time_units(obj: &Container, mem_dtype: &Datatype) -> Result<()> {
+    let conv = match TimeConversion::find(&mem_dtype.to_descriptor()?) {
+        Some(conv) if !obj.is_attr() => conv,
+        _ => return Ok(()),
+    };
+    if let Some(units) = crate::hl::dataset::read_time_units(obj)? {
+        ensure!(
+            units == conv.units,
+            "time units mismatch: expected {:?}, got {:?} (use read_times() or write_times())",
+            conv.units.to_string(),
+            units.to_string()
+        );
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct Reader<'a> {
     obj: &'a Container,
     conv: Conversion,
+    trim_padding: bool,
 }
 
 impl<'a> Reader<'a> {
@@ -23,7 +52,7 @@ impl<'a> Reader<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self { obj, conv: Conversion::Soft, trim_padding: true }
     }
 
     /// Set maximum allowed conversion level.
@@ -38,16 +67,31 @@ impl<'a> Reader<'a> {
         self
     }
 
+    /// Whether to strip the padding of fixed-length strings in `read_strings()` (enabled by
+    /// default). If disabled, strings are returned with their full stored length.
+    pub fn trim_padding(mut self, trim: bool) -> Self {
+        self.trim_padding = trim;
+        self
+    }
+
     fn read_into_buf<T: H5Type>(
         &self, buf: *mut T, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
     ) -> Result<()> {
-        let file_dtype = self.obj.dtype()?;
         let mem_dtype = Datatype::from_type::<T>()?;
-        file_dtype.ensure_convertible(&mem_dtype, self.conv)?;
+        self.read_into_raw(&mem_dtype, buf.cast(), fspace, mspace)
+    }
+
+    pub(crate) fn read_into_raw(
+        &self, mem_dtype: &Datatype, buf: *mut c_void, fspace: Option<&Dataspace>,
+        mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        let file_dtype = self.obj.dtype()?;
+        file_dtype.ensure_convertible(mem_dtype, self.conv)?;
+        check_time_units(self.obj, mem_dtype)?;
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         if self.obj.is_attr() {
-            h5try!(H5Aread(obj_id, tp_id, buf.cast()));
+            h5try!(H5Aread(obj_id, tp_id, buf));
         } else {
             let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
             let mspace_id = mspace.map_or(H5S_ALL, |m| m.id());
@@ -56,7 +100,7 @@ impl<'a> Reader<'a> {
             if !hdf5_types::USING_H5_ALLOCATOR {
                 crate::hl::plist::set_vlen_manager_libc(xfer.id())?;
             }
-            h5try!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, xfer.id(), buf.cast()));
+            h5try!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, xfer.id(), buf));
         }
         Ok(())
     }
@@ -186,12 +230,119 @@ impl<'a> Reader<'a> {
         let mut val = mem::MaybeUninit::<T>::uninit();
         self.read_into_buf(val.as_mut_ptr(), None, None).map(|()| unsafe { val.assume_init() })
     }
+
+    /// Reads `len` elements of type `mem_dtype` into a buffer, passes it to `func` and then
+    /// reclaims any variable-length memory allocated by `HDF5` during the read.
+    ///
+    /// If `spaces` is provided, only the file selection is read into the memory dataspace.
+    pub(crate) fn read_reclaim<V, R>(
+        &self, mem_dtype: &Datatype, len: usize, spaces: Option<(&Dataspace, &Dataspace)>,
+        func: impl FnOnce(&[V]) -> R,
+    ) -> Result<R> {
+        let file_dtype = self.obj.dtype()?;
+        file_dtype.ensure_convertible(mem_dtype, self.conv)?;
+        let space = match spaces {
+            Some((_, mspace)) => mspace.clone(),
+            None => self.obj.space()?,
+        };
+        let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
+        let mut buf: Vec<V> = Vec::with_capacity(len);
+        let xfer = PropertyList::from_id(h5call!(H5Pcreate(*crate::globals::H5P_DATASET_XFER))?)?;
+
+        if self.obj.is_attr() {
+            h5try!(H5Aread(obj_id, tp_id, buf.as_mut_ptr().cast()));
+        } else {
+            if !hdf5_types::USING_H5_ALLOCATOR {
+                crate::hl::plist::set_vlen_manager_libc(xfer.id())?;
+            }
+            let (fspace_id, mspace_id) =
+                spaces.map_or((H5S_ALL, H5S_ALL), |(f, m)| (f.id(), m.id()));
+            h5try!(H5Dread(
+                obj_id,
+                tp_id,
+                mspace_id,
+                fspace_id,
+                xfer.id(),
+                buf.as_mut_ptr().cast()
+            ));
+        }
+        unsafe {
+            buf.set_len(len);
+        }
+        let out = func(&buf);
+
+        #[cfg(feature = "1.12.0")]
+        h5try!(H5Treclaim(tp_id, space.id(), xfer.id(), buf.as_mut_ptr().cast()));
+        #[cfg(not(feature = "1.12.0"))]
+        h5try!(H5Dvlen_reclaim(tp_id, space.id(), xfer.id(), buf.as_mut_ptr().cast()));
+        Ok(out)
+    }
+
+    /// Reads a dataset/attribute of strings into a vector in memory order.
+    ///
+    /// Both fixed-length and variable-length strings are supported, in either ASCII or UTF-8
+    /// encoding. The padding of fixed-length strings is stripped according to the stored
+    /// `StringPadding` unless disabled via `trim_padding()`; invalid UTF-8 sequences are
+    /// replaced with `U+FFFD`.
+    pub fn read_strings(&self) -> Result<Vec<String>> {
+        let file_dtype = self.obj.dtype()?;
+        let size = self.obj.space()?.size();
+        match file_dtype.to_descriptor()? {
+            TD::FixedAscii(len, padding) | TD::FixedUnicode(len, padding) => {
+                // read the raw bytes as stored to avoid any padding conversions
+                self.read_reclaim(&file_dtype, size * len, None, |buf: &[u8]| {
+                    buf.chunks_exact(len.max(1))
+                        .map(|chunk| {
+                            let chunk = if self.trim_padding { padding.trim(chunk) } else { chunk };
+                            String::from_utf8_lossy(chunk).into_owned()
+                        })
+                        .collect()
+                })
+            }
+            desc @ (TD::VarLenAscii | TD::VarLenUnicode) => {
+                let mem_dtype = Datatype::from_descriptor(&desc)?;
+                self.read_reclaim(&mem_dtype, size, None, |buf: &[*mut c_char]| {
+                    buf.iter()
+                        .map(|&ptr| {
+                            if ptr.is_null() {
+                                String::new()
+                            } else {
+                                let bytes = unsafe { std::ffi::CStr::from_ptr(ptr) }.to_bytes();
+                                String::from_utf8_lossy(bytes).into_owned()
+                            }
+                        })
+                        .collect()
+                })
+            }
+            desc => fail!("expected a string datatype, got {}", desc),
+        }
+    }
+
+    /// Reads a dataset/attribute of variable-length arrays into a vector of vectors in
+    /// memory order.
+    pub fn read_ragged<T: H5Type + Clone>(&self) -> Result<Vec<Vec<T>>> {
+        let desc = TD::VarLenArray(Box::new(T::type_descriptor()));
+        let mem_dtype = Datatype::from_descriptor(&desc)?;
+        let size = self.obj.space()?.size();
+        self.read_reclaim(&mem_dtype, size, None, |buf: &[hvl_t]| {
+            buf.iter()
+                .map(|vl| {
+                    if vl.p.is_null() || vl.len == 0 {
+                        vec![]
+                    } else {
+                        unsafe { slice::from_raw_parts(vl.p as *const T, vl.len) }.to_vec()
+                    }
+                })
+                .collect()
+        })
+    }
 }
 
 #[derive(Debug)]
 pub struct Writer<'a> {
     obj: &'a Container,
     conv: Conversion,
+    truncate: bool,
 }
 
 impl<'a> Writer<'a> {
@@ -199,7 +350,7 @@ impl<'a> Writer<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self { obj, conv: Conversion::Soft, truncate: false }
     }
 
     /// Set maximum allowed conversion level.
@@ -214,20 +365,40 @@ impl<'a> Writer<'a> {
         self
     }
 
+    /// Whether written strings that don't fit into the fixed length of the destination should
+    /// be truncated (at a character boundary) instead of failing (disabled by default).
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.truncate = truncate;
+        self
+    }
+
     fn write_from_buf<T: H5Type>(
         &self, buf: *const T, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
     ) -> Result<()> {
+        // there are no conversion paths from variable-length to fixed-length strings
         let file_dtype = self.obj.dtype()?;
+        if is_string_write(&T::type_descriptor(), &file_dtype.to_descriptor()?) {
+            return self.write_strings_from_buf(buf.cast(), &file_dtype, fspace, mspace);
+        }
         let mem_dtype = Datatype::from_type::<T>()?;
+        self.write_from_raw(&mem_dtype, buf.cast(), fspace, mspace)
+    }
+
+    pub(crate) fn write_from_raw(
+        &self, mem_dtype: &Datatype, buf: *const c_void, fspace: Option<&Dataspace>,
+        mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        let file_dtype = self.obj.dtype()?;
         mem_dtype.ensure_convertible(&file_dtype, self.conv)?;
+        check_time_units(self.obj, mem_dtype)?;
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         if self.obj.is_attr() {
-            h5try!(H5Awrite(obj_id, tp_id, buf.cast()));
+            h5try!(H5Awrite(obj_id, tp_id, buf));
         } else {
             let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
             let mspace_id = mspace.map_or(H5S_ALL, |m| m.id());
-            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, H5P_DEFAULT, buf.cast()));
+            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, H5P_DEFAULT, buf));
         }
         Ok(())
     }
@@ -240,7 +411,7 @@ impl<'a> Writer<'a> {
     pub fn write_slice<'b, A, T, S, D>(&self, arr: A, selection: S) -> Result<()>
     where
         A: Into<ArrayView<'b, T, D>>,
-        T: H5Type,
+        T: AsH5Type + 'b,
         S: TryInto<Selection>,
         Error: From<S::Error>,
         D: ndarray::Dimension,
@@ -286,18 +457,20 @@ impl<'a> Writer<'a> {
                 "Input array is not in standard layout or non-contiguous"
             );
 
-            self.write_from_buf(view.as_ptr(), Some(&fspace), Some(&mspace))
+            let arr = to_h5_array(&view)?;
+            self.write_from_buf(arr.as_ptr(), Some(&fspace), Some(&mspace))
         }
     }
 
     /// Writes an n-dimensional array view into a dataset/attribute.
     ///
     /// The shape of the view must match the shape of the dataset/attribute exactly.
-    /// The input argument must be convertible to an array view (this includes slices).
+    /// The input argument must be convertible to an array view (this includes slices); its
+    /// elements may also be e.g. strings or vectors, which are converted via `AsH5Type`.
     pub fn write<'b, A, T, D>(&self, arr: A) -> Result<()>
     where
         A: Into<ArrayView<'b, T, D>>,
-        T: H5Type,
+        T: AsH5Type + 'b,
         D: ndarray::Dimension,
     {
         let view = arr.into();
@@ -312,18 +485,19 @@ impl<'a> Writer<'a> {
             fail!("shape mismatch when writing: memory = {:?}, destination = {:?}", src, dst);
         }
 
-        self.write_from_buf(view.as_ptr(), None, None)
+        let arr = to_h5_array(&view)?;
+        self.write_from_buf(arr.as_ptr(), None, None)
     }
 
     /// Writes a 1-dimensional array view into a dataset/attribute in memory order.
     ///
     /// The number of elements in the view must match the number of elements in the
     /// destination dataset/attribute. The input argument must be convertible to a
-    /// 1-dimensional array view (this includes slices).
+    /// 1-dimensional array view (this includes slices) of `AsH5Type` elements.
     pub fn write_raw<'b, A, T>(&self, arr: A) -> Result<()>
     where
         A: Into<ArrayView1<'b, T>>,
-        T: H5Type,
+        T: AsH5Type + 'b,
     {
         let view = arr.into();
         ensure!(
@@ -336,7 +510,8 @@ impl<'a> Writer<'a> {
         if src != dst {
             fail!("length mismatch when writing: memory = {:?}, destination = {:?}", src, dst);
         }
-        self.write_from_buf(view.as_ptr(), None, None)
+        let arr = to_h5_array(&view)?;
+        self.write_from_buf(arr.as_ptr(), None, None)
     }
 
     /// Writes a scalar dataset/attribute.
@@ -345,6 +520,96 @@ impl<'a> Writer<'a> {
         ensure!(ndim == 0, "ndim mismatch: expected scalar, got {}", ndim);
         self.write_from_buf(val as *const _, None, None)
     }
+
+    /// Writes variable-length strings into a string destination of a different flavor.
+    ///
+    /// Strings are stored in the type of the destination, which may hold fixed-length or
+    /// variable-length, ASCII or UTF-8 strings; fixed-length strings are padded according to
+    /// the destination's `StringPadding`. Fails if a string is not ASCII when the destination
+    /// is, or is longer than the fixed length (unless `truncate()` is enabled; null-terminated
+    /// strings need one byte for the terminator).
+    fn write_strings_from_buf(
+        &self, buf: *const *const c_char, file_dtype: &Datatype, fspace: Option<&Dataspace>,
+        mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        let len = match mspace {
+            Some(mspace) => mspace.selection_size(),
+            None => self.obj.get_shape()?.size(),
+        };
+        let ptrs = unsafe { slice::from_raw_parts(buf, len) };
+        let strings = ptrs
+            .iter()
+            .map(|&ptr| {
+                if ptr.is_null() {
+                    return Ok("");
+                }
+                let s = unsafe { CStr::from_ptr(ptr) };
+                s.to_str().map_err(|_| Error::from(format!("invalid UTF-8 in string: {s:?}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let desc = file_dtype.to_descriptor()?;
+        if matches!(desc, TD::FixedAscii(..) | TD::VarLenAscii) {
+            for s in &strings {
+                ensure!(s.is_ascii(), "cannot store non-ASCII string as ASCII: {:?}", s);
+            }
+        }
+        match desc {
+            TD::FixedAscii(len, padding) | TD::FixedUnicode(len, padding) => {
+                let max_len =
+                    if padding == StringPadding::NullTerm { len.saturating_sub(1) } else { len };
+                let mut buf = vec![padding.pad_byte(); strings.len() * len];
+                for (&s, chunk) in strings.iter().zip(buf.chunks_exact_mut(len.max(1))) {
+                    let mut s = s;
+                    if s.len() > max_len {
+                        ensure!(
+                            self.truncate,
+                            "string of length {} exceeds fixed length {}: {:?}",
+                            s.len(),
+                            max_len,
+                            s
+                        );
+                        let mut end = max_len;
+                        while !s.is_char_boundary(end) {
+                            end -= 1;
+                        }
+                        s = &s[..end];
+                    }
+                    chunk[..s.len()].copy_from_slice(s.as_bytes());
+                }
+                self.write_from_raw(file_dtype, buf.as_ptr().cast(), fspace, mspace)
+            }
+            _ => {
+                // variable-length ASCII and UTF-8 strings share the same memory layout
+                let mem_dtype = Datatype::from_descriptor(&desc)?;
+                self.write_from_raw(&mem_dtype, buf.cast(), fspace, mspace)
+            }
+        }
+    }
+}
+
+/// Returns `true` if strings of the in-memory type `mem` are written into the string type
+/// `file` by `Writer` itself rather than via an `HDF5` conversion.
+pub(crate) fn is_string_write(mem: &TD, file: &TD) -> bool {
+    matches!(mem, TD::VarLenAscii | TD::VarLenUnicode)
+        && matches!(
+            file,
+            TD::FixedAscii(..) | TD::FixedUnicode(..) | TD::VarLenAscii | TD::VarLenUnicode
+        )
+        && mem != file
+}
+
+/// Converts an array view into the stored representation of its elements, see `AsH5Type`.
+pub(crate) fn to_h5_array<'a, T, D>(view: &ArrayView<'a, T, D>) -> Result<CowArray<'a, T::Repr, D>>
+where
+    T: AsH5Type,
+    D: ndarray::Dimension,
+{
+    let values =
+        view.to_slice().ok_or("input array is not in standard layout or is not contiguous")?;
+    Ok(match T::as_h5_slice(values).map_err(|err| err.to_string())? {
+        H5Slice::Borrowed(values) => ArrayView::from_shape(view.raw_dim(), values)?.into(),
+        H5Slice::Owned(values) => Array::from_shape_vec(view.raw_dim(), values)?.into(),
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -627,11 +892,12 @@ impl Container {
     /// Writes an n-dimensional array view into a dataset/attribute.
     ///
     /// The shape of the view must match the shape of the dataset/attribute exactly.
-    /// The input argument must be convertible to an array view (this includes slices).
+    /// The input argument must be convertible to an array view (this includes slices); its
+    /// elements may also be e.g. strings or vectors, which are converted via `AsH5Type`.
     pub fn write<'b, A, T, D>(&self, arr: A) -> Result<()>
     where
         A: Into<ArrayView<'b, T, D>>,
-        T: H5Type,
+        T: AsH5Type + 'b,
         D: ndarray::Dimension,
     {
         self.as_writer().write(arr)
@@ -641,11 +907,11 @@ impl Container {
     ///
     /// The number of elements in the view must match the number of elements in the
     /// destination dataset/attribute. The input argument must be convertible to a
-    /// 1-dimensional array view (this includes slices).
+    /// 1-dimensional array view (this includes slices) of `AsH5Type` elements.
     pub fn write_raw<'b, A, T>(&self, arr: A) -> Result<()>
     where
         A: Into<ArrayView1<'b, T>>,
-        T: H5Type,
+        T: AsH5Type + 'b,
     {
         self.as_writer().write_raw(arr)
     }
@@ -658,7 +924,7 @@ impl Container {
     pub fn write_slice<'b, A, T, S, D>(&self, arr: A, selection: S) -> Result<()>
     where
         A: Into<ArrayView<'b, T, D>>,
-        T: H5Type,
+        T: AsH5Type + 'b,
         S: TryInto<Selection>,
         Error: From<S::Error>,
         D: ndarray::Dimension,
@@ -670,4 +936,145 @@ impl Container {
     pub fn write_scalar<T: H5Type>(&self, val: &T) -> Result<()> {
         self.as_writer().write_scalar(val)
     }
+
+    /// Reads a dataset/attribute of fixed-length or variable-length strings into a vector
+    /// in memory order, see `Reader::read_strings()`.
+    pub fn read_strings(&self) -> Result<Vec<String>> {
+        self.as_reader().read_strings()
+    }
+
+    /// Reads a dataset/attribute of variable-length arrays into a vector of vectors in
+    /// memory order.
+    pub fn read_ragged<T: H5Type + Clone>(&self) -> Result<Vec<Vec<T>>> {
+        self.as_reader().read_ragged()
+    }
+
+    /// Reads a dataset/attribute of a proxied type into an n-dimensional array.
+    ///
+    /// The elements are read as `T::Proxy` and converted via `H5Proxy::from_proxy()`.
+    pub fn read_proxy<T: H5Proxy, D: ndarray::Dimension>(&self) -> Result<Array<T, D>> {
+        let arr = self.read::<T::Proxy, D>()?;
+        let shape = arr.raw_dim();
+        let vec = arr.into_raw_vec().into_iter().map(T::from_proxy).collect();
+        Ok(Array::from_shape_vec(shape, vec)?)
+    }
+
+    /// Reads a dataset/attribute of a proxied type into a vector in memory order.
+    pub fn read_raw_proxy<T: H5Proxy>(&self) -> Result<Vec<T>> {
+        Ok(self.read_raw::<T::Proxy>()?.into_iter().map(T::from_proxy).collect())
+    }
+
+    /// Reads a scalar dataset/attribute of a proxied type.
+    pub fn read_scalar_proxy<T: H5Proxy>(&self) -> Result<T> {
+        self.read_scalar::<T::Proxy>().map(T::from_proxy)
+    }
+
+    /// Writes an n-dimensional array view of a proxied type into a dataset/attribute.
+    ///
+    /// The elements are converted via `H5Proxy::to_proxy()` and written as `T::Proxy`.
+    pub fn write_proxy<'b, A, T, D>(&self, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Proxy + 'b,
+        D: ndarray::Dimension,
+    {
+        self.write(&arr.into().map(T::to_proxy))
+    }
+
+    /// Writes a scalar dataset/attribute of a proxied type.
+    pub fn write_scalar_proxy<T: H5Proxy>(&self, val: &T) -> Result<()> {
+        self.write_scalar(&val.to_proxy())
+    }
+
+    /// Reads all elements as dynamically typed values, see `Attribute::read_value()`.
+    pub(crate) fn read_dyn_value(&self) -> Result<AttrValue> {
+        let desc = self.dtype()?.to_descriptor()?.to_native_repr().to_c_repr();
+        let space = self.space()?;
+        if space.is_null() {
+            return Ok(AttrValue::Null(desc));
+        }
+        let (shape, size, item_size) = (space.shape(), space.size(), desc.size());
+        if size == 0 {
+            return Ok(AttrValue::Empty(desc, shape));
+        }
+        let mem_dtype = Datatype::from_descriptor(&desc)?;
+        let mut buf: Vec<u8> = Vec::with_capacity(size * item_size);
+        if self.is_attr() {
+            h5try!(H5Aread(self.id(), mem_dtype.id(), buf.as_mut_ptr().cast()));
+        } else {
+            let xfer =
+                PropertyList::from_id(h5call!(H5Pcreate(*crate::globals::H5P_DATASET_XFER))?)?;
+            if !hdf5_types::USING_H5_ALLOCATOR {
+                crate::hl::plist::set_vlen_manager_libc(xfer.id())?;
+            }
+            h5try!(H5Dread(
+                self.id(),
+                mem_dtype.id(),
+                H5S_ALL,
+                H5S_ALL,
+                xfer.id(),
+                buf.as_mut_ptr().cast()
+            ));
+        }
+        unsafe {
+            buf.set_len(size * item_size);
+        }
+        // each value takes ownership of any var-len memory allocated during the read
+        let mut values = (0..size).map(|i| {
+            let chunk = &buf[i * item_size..(i + 1) * item_size];
+            unsafe { OwnedDynValue::from_raw(desc.clone(), chunk.into()) }
+        });
+        if self.is_scalar() {
+            values.next().map(AttrValue::Scalar).ok_or_else(|| "empty scalar value".into())
+        } else {
+            Ok(AttrValue::Array(ArrayD::from_shape_vec(shape, values.collect())?))
+        }
+    }
+
+    /// Writes dynamically typed values, see `Attribute::write_value()`.
+    pub(crate) fn write_dyn_value(&self, value: &AttrValue) -> Result<()> {
+        ensure!(
+            value.shape() == self.shape().as_slice(),
+            "shape mismatch when writing: expected {:?}, got {:?}",
+            self.shape(),
+            value.shape()
+        );
+        ensure!(
+            matches!(value, AttrValue::Null(_)) == self.space()?.is_null(),
+            "null dataspace mismatch when writing: expected {}, got {}",
+            self.space()?.extents()?,
+            value.extents()
+        );
+        let desc = match value.type_descriptor() {
+            Some(desc) if !value.is_empty() => desc,
+            _ => return Ok(()),
+        };
+        let mut buf: Vec<u8> = Vec::with_capacity(value.len() * desc.size());
+        for item in value.iter() {
+            ensure!(
+                item.type_descriptor() == desc,
+                "array elements must share the same type: {} != {}",
+                item.type_descriptor(),
+                desc
+            );
+            buf.extend_from_slice(unsafe { item.get_buf() });
+        }
+        h5lock!({
+            let mem_dtype = Datatype::from_descriptor(desc)?;
+            mem_dtype.ensure_convertible(&self.dtype()?, Conversion::Soft)?;
+            if self.is_attr() {
+                h5try!(H5Awrite(self.id(), mem_dtype.id(), buf.as_ptr().cast()));
+            } else {
+                h5try!(H5Dwrite(
+                    self.id(),
+                    mem_dtype.id(),
+                    H5S_ALL,
+                    H5S_ALL,
+                    H5P_DEFAULT,
+                    buf.as_ptr().cast()
+                ));
+            }
+            Ok(())
+        })
+    }
 }
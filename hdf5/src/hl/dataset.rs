@@ -1,7 +1,7 @@
 use std::fmt::{self, Debug};
 use std::ops::Deref;
 
-use ndarray::{self, ArrayView};
+use ndarray::{self, Array, ArrayView, CowArray};
 
 use hdf5_sys::h5::HADDR_UNDEF;
 use hdf5_sys::h5d::{
@@ -11,8 +11,12 @@ use hdf5_sys::h5d::{
 use hdf5_sys::h5l::H5Ldelete;
 use hdf5_sys::h5p::H5P_DEFAULT;
 use hdf5_sys::h5z::H5Z_filter_t;
-use hdf5_types::{OwnedDynValue, TypeDescriptor};
+use hdf5_types::{
+    AsH5Type, H5Time, OwnedDynValue, StringError, TimeConversion, TimeUnits, TypeDescriptor,
+    VarLenUnicode,
+};
 
+use crate::hl::container::{is_string_write, to_h5_array};
 #[cfg(feature = "blosc")]
 use crate::hl::filters::{Blosc, BloscShuffle};
 use crate::hl::filters::{Filter, SZip, ScaleOffset};
@@ -135,9 +139,11 @@ impl Dataset {
         }
     }
 
-    /// Returns default fill value for the dataset if such value is set.
+    /// Returns default fill value for the dataset if such value is set (converted to the
+    /// type returned by `TypeDescriptor::to_native_repr()`).
     pub fn fill_value(&self) -> Result<Option<OwnedDynValue>> {
-        h5lock!(self.dcpl()?.get_fill_value(&self.dtype()?.to_descriptor()?))
+        let desc = self.dtype()?.to_descriptor()?.to_native_repr();
+        h5lock!(self.dcpl()?.get_fill_value(&desc))
     }
 
     /// Resizes the dataset to a new shape.
@@ -154,6 +160,152 @@ impl Dataset {
     pub fn filters(&self) -> Vec<Filter> {
         self.dcpl().map_or(Vec::default(), |pl| pl.filters())
     }
+
+    /// Reads a one-dimensional compound dataset as an Arrow record batch with a column for
+    /// each of the fields (see [`crate::arrow`] for how the datatypes are mapped).
+    #[cfg(feature = "arrow")]
+    pub fn read_record_batch(&self) -> Result<arrow_array::RecordBatch> {
+        crate::hl::arrow::read_record_batch(self, ..)
+    }
+
+    /// Reads a selection of a one-dimensional compound dataset as an Arrow record batch.
+    #[cfg(feature = "arrow")]
+    pub fn read_record_batch_slice<S>(&self, selection: S) -> Result<arrow_array::RecordBatch>
+    where
+        S: TryInto<Selection>,
+        Error: From<S::Error>,
+    {
+        crate::hl::arrow::read_record_batch(self, selection)
+    }
+
+    /// Writes an Arrow record batch into a one-dimensional compound dataset with the same
+    /// number of rows.
+    ///
+    /// Columns are matched to the fields of the dataset by name and converted to their
+    /// datatypes; fields without a corresponding column are left unchanged. Dictionary
+    /// columns are written into enum fields by member name.
+    #[cfg(feature = "arrow")]
+    pub fn write_record_batch(&self, batch: &arrow_array::RecordBatch) -> Result<()> {
+        ensure!(
+            batch.num_rows() == self.size(),
+            "record batch has {} rows, expected {}",
+            batch.num_rows(),
+            self.size()
+        );
+        crate::hl::arrow::write_record_batch(self, batch, 0)
+    }
+
+    /// Appends an Arrow record batch to a resizable one-dimensional compound dataset, see
+    /// [`write_record_batch()`](Self::write_record_batch); the dataset keeps its size if the
+    /// batch is rejected.
+    #[cfg(feature = "arrow")]
+    pub fn append_record_batch(&self, batch: &arrow_array::RecordBatch) -> Result<()> {
+        ensure!(self.is_resizable(), "dataset is not resizable");
+        ensure!(
+            self.ndim() == 1,
+            "expected a one-dimensional dataset, got {} dimensions",
+            self.ndim()
+        );
+        let start = self.size();
+        self.resize(start + batch.num_rows())?;
+        crate::hl::arrow::write_record_batch(self, batch, start).map_err(|err| {
+            // don't leave fill-value rows behind if the batch is rejected
+            let _ = self.resize(start);
+            err
+        })
+    }
+
+    /// Returns the time units of the dataset parsed from its CF-style `units` attribute, if
+    /// there is one.
+    pub fn time_units(&self) -> Result<Option<TimeUnits>> {
+        read_time_units(self)
+    }
+
+    fn checked_time_units<T: H5Time>(&self) -> Result<TimeUnits> {
+        let units = self.time_units()?.unwrap_or(T::UNITS);
+        ensure!(
+            units.is_time_point() == T::UNITS.is_time_point(),
+            "expected {} units, got {:?}",
+            if T::UNITS.is_time_point() { "time point" } else { "duration" },
+            units.to_string()
+        );
+        if self.attr_exists("calendar") {
+            let calendar = self.attr("calendar")?.read_strings()?.concat().to_ascii_lowercase();
+            ensure!(
+                matches!(calendar.as_str(), "standard" | "gregorian" | "proleptic_gregorian"),
+                "unsupported calendar: {:?}",
+                calendar
+            );
+        }
+        Ok(units)
+    }
+
+    fn has_float_type(&self) -> Result<bool> {
+        let desc = self.dtype()?.to_descriptor()?.to_native_repr();
+        Ok(matches!(desc, TypeDescriptor::Float(_) | TypeDescriptor::CustomFloat(_)))
+    }
+
+    /// Reads time points or durations stored as integer or floating-point counts, interpreting
+    /// them according to the dataset's CF-style `units` attribute (or `T::UNITS` if there is
+    /// none).
+    ///
+    /// Dates are always interpreted in the proleptic Gregorian calendar; datasets with a
+    /// `calendar` attribute other than `standard`, `gregorian` or `proleptic_gregorian` are
+    /// rejected.
+    pub fn read_times<T: H5Time>(&self) -> Result<Vec<T>> {
+        let units = self.checked_time_units::<T>()?;
+        let nanos = if self.has_float_type()? {
+            self.read_raw::<f64>()?
+                .into_iter()
+                .map(|v| units.float_to_nanos(v).ok_or_else(|| format!("invalid time value: {v}")))
+                .collect::<std::result::Result<Vec<_>, _>>()?
+        } else {
+            self.read_raw::<i64>()?.into_iter().map(|v| units.to_nanos(v)).collect()
+        };
+        nanos
+            .into_iter()
+            .map(|n| {
+                T::from_nanos(n).ok_or_else(|| format!("time value out of range: {n}ns").into())
+            })
+            .collect()
+    }
+
+    /// Writes time points or durations as counts in the units given by the dataset's
+    /// CF-style `units` attribute; if there is none, `T::UNITS` are used and the attribute
+    /// is created.
+    pub fn write_times<T: H5Time>(&self, data: &[T]) -> Result<()> {
+        let has_units = self.attr_exists("units");
+        let units = self.checked_time_units::<T>()?;
+        let nanos = data.iter().map(H5Time::to_nanos);
+        if self.has_float_type()? {
+            self.write_raw(&nanos.map(|n| units.float_from_nanos(n)).collect::<Vec<_>>())?;
+        } else {
+            let counts = nanos
+                .map(|n| {
+                    units.from_nanos(n).ok_or_else(|| format!("time value out of range: {n}ns"))
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            self.write_raw(&counts)?;
+        }
+        if !has_units {
+            write_time_units(self, &units)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn read_time_units(loc: &Location) -> Result<Option<TimeUnits>> {
+    if !loc.attr_exists("units") {
+        return Ok(None);
+    }
+    let units = loc.attr("units")?.read_strings()?;
+    ensure!(units.len() == 1, "expected a single units string, got {}", units.len());
+    Ok(Some(units[0].parse()?))
+}
+
+fn write_time_units(ds: &Dataset, units: &TimeUnits) -> Result<()> {
+    let units: VarLenUnicode = units.to_string().parse().map_err(|e: StringError| e.to_string())?;
+    ds.new_attr::<VarLenUnicode>().create("units")?.write_scalar(&units)
 }
 
 pub struct Maybe<T>(Option<T>);
@@ -184,6 +336,35 @@ impl<T> From<Option<T>> for Maybe<T> {
     }
 }
 
+/// A value that can be stored as a dataset by `#[derive(H5Group)]`.
+pub trait DatasetValue: Sized {
+    /// Creates a new dataset containing the value.
+    fn write_dataset(&self, builder: DatasetBuilder, name: &str) -> Result<Dataset>;
+
+    /// Reads the value from a dataset.
+    fn read_dataset(dataset: &Dataset) -> Result<Self>;
+}
+
+impl<T: H5Type> DatasetValue for Vec<T> {
+    fn write_dataset(&self, builder: DatasetBuilder, name: &str) -> Result<Dataset> {
+        builder.with_data(self.as_slice()).create(name)
+    }
+
+    fn read_dataset(dataset: &Dataset) -> Result<Self> {
+        dataset.read_raw()
+    }
+}
+
+impl<T: H5Type, D: ndarray::Dimension> DatasetValue for Array<T, D> {
+    fn write_dataset(&self, builder: DatasetBuilder, name: &str) -> Result<Dataset> {
+        builder.with_data(self).create(name)
+    }
+
+    fn read_dataset(dataset: &Dataset) -> Result<Self> {
+        dataset.read()
+    }
+}
+
 #[derive(Clone)]
 /// A dataset builder
 pub struct DatasetBuilder {
@@ -203,26 +384,30 @@ impl DatasetBuilder {
         DatasetBuilderEmpty { builder: self.builder, type_desc: type_desc.clone() }
     }
 
-    pub fn with_data<'d, A, T, D>(self, data: A) -> DatasetBuilderData<'d, T, D>
+    /// Creates a builder for a dataset holding the given data.
+    ///
+    /// The elements may also be e.g. strings or vectors, which are converted via `AsH5Type`
+    /// (errors in converting them are reported by `create()`).
+    pub fn with_data<'d, A, T, D>(self, data: A) -> DatasetBuilderData<'d, T::Repr, D>
     where
         A: Into<ArrayView<'d, T, D>>,
-        T: H5Type,
+        T: AsH5Type + 'd,
         D: ndarray::Dimension,
     {
-        self.with_data_as::<A, T, D>(data, &T::type_descriptor())
+        self.with_data_as::<A, T, D>(data, &T::Repr::type_descriptor())
     }
 
     pub fn with_data_as<'d, A, T, D>(
         self, data: A, type_desc: &TypeDescriptor,
-    ) -> DatasetBuilderData<'d, T, D>
+    ) -> DatasetBuilderData<'d, T::Repr, D>
     where
         A: Into<ArrayView<'d, T, D>>,
-        T: H5Type,
+        T: AsH5Type + 'd,
         D: ndarray::Dimension,
     {
         DatasetBuilderData {
             builder: self.builder,
-            data: data.into(),
+            data: to_h5_array(&data.into()),
             type_desc: type_desc.clone(),
             conv: Conversion::Soft,
         }
@@ -267,7 +452,7 @@ impl DatasetBuilderEmptyShape {
 /// A dataset builder with type, shape, and data known
 pub struct DatasetBuilderData<'d, T, D> {
     builder: DatasetBuilderInner,
-    data: ArrayView<'d, T, D>,
+    data: Result<CowArray<'d, T, D>>,
     type_desc: TypeDescriptor,
     conv: Conversion,
 }
@@ -290,18 +475,25 @@ where
     }
 
     pub fn create<'n, N: Into<Maybe<&'n str>>>(&self, name: N) -> Result<Dataset> {
+        let data = self.data.as_ref().map_err(Clone::clone)?;
         ensure!(
-            self.data.is_standard_layout(),
+            data.is_standard_layout(),
             "input array is not in standard layout or is not contiguous"
         ); // TODO: relax this when it's supported in the writer
-        let extents = Extents::from(self.data.shape());
+        let extents = Extents::from(data.shape());
         let name = name.into().into();
         h5lock!({
             let dtype_src = Datatype::from_type::<T>()?;
-            let dtype_dst = Datatype::from_descriptor(&self.type_desc)?;
-            dtype_src.ensure_convertible(&dtype_dst, self.conv)?;
+            let dtype_dst = match self.builder.committed_type {
+                Some(ref dtype) => dtype.clone(),
+                None => Datatype::from_descriptor(&self.type_desc)?,
+            };
+            let src_desc = T::type_descriptor();
+            if !dtype_dst.to_descriptor().map_or(false, |dst| is_string_write(&src_desc, &dst)) {
+                dtype_src.ensure_convertible(&dtype_dst, self.conv)?;
+            }
             let ds = self.builder.create(&self.type_desc, name, &extents)?;
-            if let Err(err) = ds.write(self.data.view()) {
+            if let Err(err) = ds.write(data.view()) {
                 self.builder.try_unlink(name);
                 Err(err)
             } else {
@@ -364,6 +556,7 @@ struct DatasetBuilderInner {
     lcpl_builder: LinkCreateBuilder,
     packed: bool,
     chunk: Option<Chunk>,
+    committed_type: Option<Datatype>,
 }
 
 impl DatasetBuilderInner {
@@ -384,6 +577,7 @@ impl DatasetBuilderInner {
             lcpl_builder: lcpl,
             packed: false,
             chunk: None,
+            committed_type: None,
         }
     }
 
@@ -391,6 +585,10 @@ impl DatasetBuilderInner {
         self.packed = packed;
     }
 
+    pub fn committed_type(&mut self, dtype: &Datatype) {
+        self.committed_type = Some(dtype.clone());
+    }
+
     fn build_dapl(&self) -> Result<DatasetAccess> {
         let mut dapl = match &self.dapl_base {
             Some(dapl) => dapl.clone(),
@@ -491,9 +689,19 @@ impl DatasetBuilderInner {
     unsafe fn create(
         &self, desc: &TypeDescriptor, name: Option<&str>, extents: &Extents,
     ) -> Result<Dataset> {
-        // construct in-file type descriptor; convert to packed representation if needed
-        let desc = if self.packed { desc.to_packed_repr() } else { desc.to_c_repr() };
-        let dtype = Datatype::from_descriptor(&desc)?;
+        // time types are stored as int64 counts with a CF-style `units` attribute
+        let time_conv = TimeConversion::find(desc);
+
+        // use the committed datatype if provided, otherwise construct in-file type
+        // descriptor (converting it to packed representation if needed)
+        let dtype = if let Some(ref dtype) = self.committed_type {
+            dtype.clone()
+        } else if time_conv.is_some() {
+            Datatype::from_type::<i64>()?
+        } else {
+            let desc = if self.packed { desc.to_packed_repr() } else { desc.to_c_repr() };
+            Datatype::from_descriptor(&desc)?
+        };
 
         // construct DAPL and DCPL, validate filters
         let dapl = self.build_dapl()?;
@@ -515,7 +723,14 @@ impl DatasetBuilderInner {
             // create anonymous dataset
             H5Dcreate_anon(pid, dtype_id, space_id, dcpl_id, dapl_id)
         };
-        Dataset::from_id(h5check(ds_id)?)
+        let ds = Dataset::from_id(h5check(ds_id)?)?;
+        if let Some(conv) = time_conv {
+            if let Err(err) = write_time_units(&ds, &conv.units) {
+                self.try_unlink(name);
+                return Err(err);
+            }
+        }
+        Ok(ds)
     }
 
     ////////////////////
@@ -904,6 +1119,7 @@ macro_rules! impl_builder {
 macro_rules! impl_builder_methods {
     () => {
         impl_builder!(*: packed(packed: bool));
+        impl_builder!(*: committed_type(dtype: &Datatype));
 
         impl_builder!(DatasetAccess: access/dapl);
 
@@ -1,35 +1,55 @@
 use std::borrow::Borrow;
 use std::cmp::{Ordering, PartialEq, PartialOrd};
+use std::collections::HashSet;
 use std::fmt::{self, Debug, Display};
+use std::mem;
 use std::ops::Deref;
-use std::ptr::{addr_of, addr_of_mut};
+use std::panic;
+use std::ptr::{self, addr_of, addr_of_mut};
 
 use hdf5_sys::h5t::{
-    H5T_cdata_t, H5T_class_t, H5T_cset_t, H5T_order_t, H5T_sign_t, H5T_str_t, H5Tarray_create2,
-    H5Tcompiler_conv, H5Tcopy, H5Tcreate, H5Tenum_create, H5Tenum_insert, H5Tequal, H5Tfind,
-    H5Tget_array_dims2, H5Tget_array_ndims, H5Tget_class, H5Tget_cset, H5Tget_member_name,
-    H5Tget_member_offset, H5Tget_member_type, H5Tget_member_value, H5Tget_nmembers, H5Tget_order,
-    H5Tget_sign, H5Tget_size, H5Tget_super, H5Tinsert, H5Tis_variable_str, H5Tset_cset,
-    H5Tset_size, H5Tset_strpad, H5Tvlen_create, H5T_VARIABLE,
+    H5T_bkg_t, H5T_cdata_t, H5T_class_t, H5T_cmd_t, H5T_conv_t, H5T_cset_t, H5T_order_t,
+    H5T_pers_t, H5T_sign_t, H5T_str_t, H5Tarray_create2, H5Tclose, H5Tcommitted, H5Tcompiler_conv,
+    H5Tconvert, H5Tcopy, H5Tcreate, H5Tenum_create, H5Tenum_insert, H5Tequal, H5Tfind,
+    H5Tget_array_dims2, H5Tget_array_ndims, H5Tget_class, H5Tget_cset, H5Tget_ebias, H5Tget_fields,
+    H5Tget_member_name, H5Tget_member_offset, H5Tget_member_type, H5Tget_member_value,
+    H5Tget_nmembers, H5Tget_offset, H5Tget_order, H5Tget_precision, H5Tget_sign, H5Tget_size,
+    H5Tget_strpad, H5Tget_super, H5Tget_tag, H5Tinsert, H5Tis_variable_str, H5Tregister,
+    H5Tset_cset, H5Tset_ebias, H5Tset_fields, H5Tset_offset, H5Tset_order, H5Tset_precision,
+    H5Tset_size, H5Tset_strpad, H5Tset_tag, H5Tvlen_create, H5T_VARIABLE,
 };
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
 use hdf5_types::{
-    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
+    CompoundField, CompoundType, CustomFloatType, CustomIntType, Endianness, EnumMember, EnumType,
+    FloatSize, H5Type, IntSize, StringPadding, TimeConversion, TypeDescriptor,
 };
 
-use crate::globals::{H5T_C_S1, H5T_NATIVE_INT, H5T_NATIVE_INT8};
+use crate::globals::{
+    H5T_C_S1, H5T_NATIVE_DOUBLE, H5T_NATIVE_FLOAT, H5T_NATIVE_INT, H5T_NATIVE_INT64,
+    H5T_NATIVE_INT8,
+};
 use crate::internal_prelude::*;
 
+#[cfg(feature = "2.0.0")]
+use hdf5_sys::h5t::H5Tcomplex_create;
+
 #[cfg(target_endian = "big")]
 use crate::globals::{
     H5T_IEEE_F32BE, H5T_IEEE_F64BE, H5T_STD_I16BE, H5T_STD_I32BE, H5T_STD_I64BE, H5T_STD_I8BE,
     H5T_STD_U16BE, H5T_STD_U32BE, H5T_STD_U64BE, H5T_STD_U8BE,
 };
+#[cfg(target_endian = "big")]
+use crate::globals::{H5T_STD_B16BE, H5T_STD_B32BE, H5T_STD_B64BE, H5T_STD_B8BE};
 
 #[cfg(target_endian = "little")]
 use crate::globals::{
     H5T_IEEE_F32LE, H5T_IEEE_F64LE, H5T_STD_I16LE, H5T_STD_I32LE, H5T_STD_I64LE, H5T_STD_I8LE,
     H5T_STD_U16LE, H5T_STD_U32LE, H5T_STD_U64LE, H5T_STD_U8LE,
 };
+#[cfg(target_endian = "little")]
+use crate::globals::{H5T_STD_B16LE, H5T_STD_B32LE, H5T_STD_B64LE, H5T_STD_B8LE};
 
 #[cfg(target_endian = "big")]
 macro_rules! be_le {
@@ -45,6 +65,452 @@ macro_rules! be_le {
     };
 }
 
+lazy_static! {
+    /// Pairs of opaque tags registered via [`Datatype::register_opaque_conversion`].
+    static ref OPAQUE_TAG_PAIRS: Mutex<HashSet<(String, String)>> = Mutex::default();
+}
+
+/// Returns the tag of an opaque datatype.
+unsafe fn opaque_tag(id: hid_t) -> Option<String> {
+    let tag = H5Tget_tag(id);
+    if tag.is_null() {
+        return None;
+    }
+    let tag_str = string_from_cstr(tag);
+    h5_free_memory(tag.cast());
+    Some(tag_str)
+}
+
+/// Soft conversion path between opaque datatypes of the same size but with different tags
+/// (which the library doesn't provide), only for the explicitly registered pairs of tags;
+/// the data is left as is.
+extern "C" fn conv_opaque(
+    src_id: hid_t, dst_id: hid_t, cdata: *mut H5T_cdata_t, _nelmts: size_t, _buf_stride: size_t,
+    _bkg_stride: size_t, _buf: *mut c_void, _bkg: *mut c_void, _dxpl_id: hid_t,
+) -> herr_t {
+    panic::catch_unwind(|| {
+        let cdata = unsafe { &mut *cdata };
+        if cdata.command == H5T_cmd_t::H5T_CONV_INIT {
+            cdata.need_bkg = H5T_bkg_t::H5T_BKG_NO;
+            if unsafe { H5Tget_size(src_id) != H5Tget_size(dst_id) } {
+                return -1;
+            }
+            let tags = unsafe { opaque_tag(src_id).zip(opaque_tag(dst_id)) };
+            if !tags.map_or(false, |tags| OPAQUE_TAG_PAIRS.lock().contains(&tags)) {
+                return -1;
+            }
+        }
+        0
+    })
+    .unwrap_or(-1)
+}
+
+/// Decodes an enum member value (in native byte order), sign-extending it if needed.
+fn enum_value_from_bytes(bytes: &[u8], signed: bool) -> u64 {
+    let (mut buf, n) = ([0; 16], bytes.len());
+    let value = if cfg!(target_endian = "little") {
+        buf[..n].copy_from_slice(bytes);
+        u128::from_le_bytes(buf)
+    } else {
+        buf[16 - n..].copy_from_slice(bytes);
+        u128::from_be_bytes(buf)
+    };
+    let shift = 128 - 8 * n as u32;
+    if signed {
+        (((value << shift) as i128) >> shift) as _
+    } else {
+        value as _
+    }
+}
+
+/// Encodes an enum member value as `size` bytes in native byte order.
+fn enum_value_to_bytes(value: u64, signed: bool, size: usize) -> Vec<u8> {
+    let value = if signed { value as i64 as i128 as u128 } else { u128::from(value) };
+    if cfg!(target_endian = "little") {
+        value.to_le_bytes()[..size].to_vec()
+    } else {
+        value.to_be_bytes()[16 - size..].to_vec()
+    }
+}
+
+/// Returns the base type and the combined shape of a (possibly nested) array type, with
+/// any leading unit dimensions removed.
+fn flatten_array(id: hid_t) -> Result<(Datatype, Vec<hsize_t>)> {
+    h5lock!({
+        let mut base = Datatype::from_id(h5try!(H5Tcopy(id)))?;
+        let mut shape = vec![];
+        while H5Tget_class(base.id()) == H5T_class_t::H5T_ARRAY {
+            let ndims = h5try!(H5Tget_array_ndims(base.id()));
+            let mut dims: Vec<hsize_t> = vec![0; ndims as _];
+            h5try!(H5Tget_array_dims2(base.id(), dims.as_mut_ptr()));
+            shape.extend(dims);
+            base = Datatype::from_id(h5try!(H5Tget_super(base.id())))?;
+        }
+        let leading = shape.iter().take_while(|&&dim| dim == 1).count();
+        shape.drain(..leading);
+        Ok((base, shape))
+    })
+}
+
+/// Soft conversion path between array datatypes with the same base type and shape but
+/// a different nesting, e.g. nested one-dimensional arrays (as written by older versions
+/// of this crate) and multi-dimensional arrays, or arrays differing by leading unit
+/// dimensions; the data is left as is. Arrays are never reshaped otherwise.
+extern "C" fn conv_array_shape(
+    src_id: hid_t, dst_id: hid_t, cdata: *mut H5T_cdata_t, _nelmts: size_t, _buf_stride: size_t,
+    _bkg_stride: size_t, _buf: *mut c_void, _bkg: *mut c_void, _dxpl_id: hid_t,
+) -> herr_t {
+    panic::catch_unwind(|| {
+        let cdata = unsafe { &mut *cdata };
+        if cdata.command == H5T_cmd_t::H5T_CONV_INIT {
+            cdata.need_bkg = H5T_bkg_t::H5T_BKG_NO;
+            match (flatten_array(src_id), flatten_array(dst_id)) {
+                (Ok(src), Ok(dst)) if src == dst => {}
+                _ => return -1,
+            }
+        }
+        0
+    })
+    .unwrap_or(-1)
+}
+
+/// Field names of the real and imaginary parts of complex numbers stored as compound types.
+const COMPLEX_FIELD_NAMES: &[(&str, &str)] = &[("r", "i"), ("re", "im"), ("real", "imag")];
+
+/// Location of the real and imaginary parts within a datatype holding complex numbers:
+/// a compound type with two fields of the same numeric type named exactly as one of the
+/// [`COMPLEX_FIELD_NAMES`] pairs (in any order), a two-element array, or a native complex
+/// datatype.
+struct ComplexParts {
+    base: Datatype,
+    size: usize,
+    re: usize,
+    im: usize,
+    /// Index of the matched pair of field names (compound types only).
+    names: Option<usize>,
+}
+
+impl ComplexParts {
+    fn new(id: hid_t) -> Option<Self> {
+        use hdf5_types::TypeDescriptor as TD;
+
+        let desc = h5lock!(Datatype::from_id(H5Tcopy(id))).ok()?.to_descriptor().ok()?;
+        let (base, re, im, names) = match desc {
+            TD::Compound(ref tp) if tp.fields.len() == 2 && tp.fields[0].ty == tp.fields[1].ty => {
+                let (a, b) = (&tp.fields[0], &tp.fields[1]);
+                let (a_name, b_name) = (a.name.as_str(), b.name.as_str());
+                let idx = COMPLEX_FIELD_NAMES
+                    .iter()
+                    .position(|&pair| pair == (a_name, b_name) || pair == (b_name, a_name))?;
+                if a_name == COMPLEX_FIELD_NAMES[idx].0 {
+                    (&a.ty, a.offset, b.offset, Some(idx))
+                } else {
+                    (&a.ty, b.offset, a.offset, Some(idx))
+                }
+            }
+            TD::FixedArray(ref ty, 2) | TD::Complex(ref ty) => (&**ty, 0, ty.size(), None),
+            _ => return None,
+        };
+        if !matches!(base.to_native_repr(), TD::Integer(_) | TD::Unsigned(_) | TD::Float(_)) {
+            return None;
+        }
+        let base = Datatype::from_descriptor(base).ok()?;
+        Some(Self { base, size: desc.size(), re, im, names })
+    }
+
+    /// Converts `nelmts` elements in place; all parts are gathered first so that the
+    /// elements may be resized.
+    unsafe fn convert(
+        &self, dst: &Self, nelmts: usize, buf_stride: usize, buf: *mut u8, dxpl_id: hid_t,
+    ) -> Result<()> {
+        let (src_size, dst_size) = (self.base.size(), dst.base.size());
+        let (src_stride, dst_stride) =
+            if buf_stride == 0 { (self.size, dst.size) } else { (buf_stride, buf_stride) };
+        let mut parts = vec![0_u8; 2 * nelmts * src_size.max(dst_size)];
+        let parts_ptr = parts.as_mut_ptr();
+        for i in 0..nelmts {
+            let elem = buf.add(i * src_stride);
+            ptr::copy_nonoverlapping(elem.add(self.re), parts_ptr.add(2 * i * src_size), src_size);
+            ptr::copy_nonoverlapping(
+                elem.add(self.im),
+                parts_ptr.add((2 * i + 1) * src_size),
+                src_size,
+            );
+        }
+        if self.base != dst.base {
+            h5try!(H5Tconvert(
+                self.base.id(),
+                dst.base.id(),
+                2 * nelmts,
+                parts_ptr.cast(),
+                ptr::null_mut(),
+                dxpl_id
+            ));
+        }
+        for i in 0..nelmts {
+            let elem = buf.add(i * dst_stride);
+            ptr::write_bytes(elem, 0, dst.size);
+            ptr::copy_nonoverlapping(parts_ptr.add(2 * i * dst_size), elem.add(dst.re), dst_size);
+            ptr::copy_nonoverlapping(
+                parts_ptr.add((2 * i + 1) * dst_size),
+                elem.add(dst.im),
+                dst_size,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Soft conversion path between different layouts of complex numbers (see [`ComplexParts`]),
+/// e.g. compound types with differently named fields (which the library would match by name)
+/// and two-element arrays; the parts are converted to the destination base type if needed.
+/// Compound types with the same field names are left to the library.
+extern "C" fn conv_complex(
+    src_id: hid_t, dst_id: hid_t, cdata: *mut H5T_cdata_t, nelmts: size_t, buf_stride: size_t,
+    _bkg_stride: size_t, buf: *mut c_void, _bkg: *mut c_void, dxpl_id: hid_t,
+) -> herr_t {
+    panic::catch_unwind(|| {
+        let cdata = unsafe { &mut *cdata };
+        if !matches!(cdata.command, H5T_cmd_t::H5T_CONV_INIT | H5T_cmd_t::H5T_CONV_CONV) {
+            return 0;
+        }
+        let (src, dst) = match (ComplexParts::new(src_id), ComplexParts::new(dst_id)) {
+            (Some(src), Some(dst))
+                if (src.names.is_none() || src.names != dst.names)
+                    && src.base.conv_path(&dst.base).is_some() =>
+            {
+                (src, dst)
+            }
+            _ => return -1,
+        };
+        if cdata.command == H5T_cmd_t::H5T_CONV_INIT {
+            cdata.need_bkg = H5T_bkg_t::H5T_BKG_NO;
+            return 0;
+        }
+        let res = unsafe { src.convert(&dst, nelmts as _, buf_stride as _, buf.cast(), dxpl_id) };
+        res.map_or(-1, |()| 0)
+    })
+    .unwrap_or(-1)
+}
+
+/// Rounds the bits of an `f32` to the nearest `bfloat16` (ties to even), keeping NaNs quiet.
+fn f32_to_bf16_bits(bits: u32) -> u16 {
+    if bits & 0x7fff_ffff > 0x7f80_0000 {
+        return (bits >> 16) as u16 | 0x0040;
+    }
+    let round_up = bits & 0x8000 != 0 && bits & 0x17fff != 0;
+    ((bits >> 16) + u32::from(round_up)) as u16
+}
+
+/// Rounds an `f64` to the nearest `bfloat16`; the intermediate `f32` is rounded to odd so that
+/// the result is not affected by double rounding.
+fn f64_to_bf16_bits(value: f64) -> u16 {
+    let single = value as f32;
+    let mut bits = single.to_bits();
+    if !value.is_nan() && f64::from(single) != value && bits & 1 == 0 {
+        // pick the odd neighbour between the value and its nearest `f32`
+        if f64::from(single).abs() > value.abs() {
+            bits -= 1;
+        } else {
+            bits += 1;
+        }
+    }
+    f32_to_bf16_bits(bits)
+}
+
+/// Hard conversion paths between `bfloat16` and native `f32`/`f64` in native byte order
+/// (the library only provides a generic soft conversion for custom float layouts).
+extern "C" fn conv_bfloat16(
+    src_id: hid_t, dst_id: hid_t, cdata: *mut H5T_cdata_t, nelmts: size_t, buf_stride: size_t,
+    _bkg_stride: size_t, buf: *mut c_void, _bkg: *mut c_void, _dxpl_id: hid_t,
+) -> herr_t {
+    let cdata = unsafe { &mut *cdata };
+    if cdata.command == H5T_cmd_t::H5T_CONV_INIT {
+        cdata.need_bkg = H5T_bkg_t::H5T_BKG_NO;
+    }
+    if cdata.command != H5T_cmd_t::H5T_CONV_CONV {
+        return 0;
+    }
+    let (src_size, dst_size) = unsafe { (H5Tget_size(src_id), H5Tget_size(dst_id)) };
+    let (src_stride, dst_stride) =
+        if buf_stride == 0 { (src_size, dst_size) } else { (buf_stride, buf_stride) };
+    let buf = buf.cast::<u8>();
+    unsafe {
+        if src_size == 2 {
+            // widening in place, so go backwards to avoid overwriting unconverted elements
+            for i in (0..nelmts).rev() {
+                let bits = ptr::read_unaligned(buf.add(i * src_stride).cast::<u16>());
+                let value = f32::from_bits(u32::from(bits) << 16);
+                let dst = buf.add(i * dst_stride);
+                match dst_size {
+                    4 => ptr::write_unaligned(dst.cast::<f32>(), value),
+                    _ => ptr::write_unaligned(dst.cast::<f64>(), f64::from(value)),
+                }
+            }
+        } else {
+            for i in 0..nelmts {
+                let src = buf.add(i * src_stride);
+                let bits = match src_size {
+                    4 => f32_to_bf16_bits(ptr::read_unaligned(src.cast::<u32>())),
+                    _ => f64_to_bf16_bits(ptr::read_unaligned(src.cast::<f64>())),
+                };
+                ptr::write_unaligned(buf.add(i * dst_stride).cast::<u16>(), bits);
+            }
+        }
+    }
+    0
+}
+
+/// Returns the conversion of the time type stored in an opaque datatype, if any.
+unsafe fn time_conversion(id: hid_t) -> Option<&'static TimeConversion> {
+    let tag = opaque_tag(id)?;
+    TimeConversion::find(&TypeDescriptor::Opaque { size: H5Tget_size(id), tag })
+}
+
+/// Soft conversion paths between time types (stored in memory as tagged opaque datatypes)
+/// and native `int64` counts in the time units of the type; fails if a value is out of range.
+extern "C" fn conv_time(
+    src_id: hid_t, dst_id: hid_t, cdata: *mut H5T_cdata_t, nelmts: size_t, buf_stride: size_t,
+    _bkg_stride: size_t, buf: *mut c_void, _bkg: *mut c_void, _dxpl_id: hid_t,
+) -> herr_t {
+    panic::catch_unwind(|| {
+        let cdata = unsafe { &mut *cdata };
+        if !matches!(cdata.command, H5T_cmd_t::H5T_CONV_INIT | H5T_cmd_t::H5T_CONV_CONV) {
+            return 0;
+        }
+        let (conv, to_count) = unsafe {
+            match (time_conversion(src_id), time_conversion(dst_id)) {
+                (Some(conv), None) if H5Tequal(dst_id, *H5T_NATIVE_INT64) > 0 => (conv, true),
+                (None, Some(conv)) if H5Tequal(src_id, *H5T_NATIVE_INT64) > 0 => (conv, false),
+                _ => return -1,
+            }
+        };
+        if cdata.command == H5T_cmd_t::H5T_CONV_INIT {
+            cdata.need_bkg = H5T_bkg_t::H5T_BKG_NO;
+            return 0;
+        }
+        let count_size = mem::size_of::<i64>();
+        let (src_size, dst_size) =
+            if to_count { (conv.size, count_size) } else { (count_size, conv.size) };
+        let (src_stride, dst_stride) =
+            if buf_stride == 0 { (src_size, dst_size) } else { (buf_stride, buf_stride) };
+        let buf = buf.cast::<u8>();
+        let convert = |i: usize| unsafe {
+            let (src, dst) = (buf.add(i * src_stride), buf.add(i * dst_stride));
+            if to_count {
+                (conv.to_count)(src).map(|count| ptr::write_unaligned(dst.cast::<i64>(), count))
+            } else {
+                (conv.from_count)(ptr::read_unaligned(src.cast::<i64>()), dst)
+            }
+        };
+        // when widening in place, go backwards to avoid overwriting unconverted elements
+        let ok = if dst_stride > src_stride {
+            (0..nelmts).rev().all(|i| convert(i).is_some())
+        } else {
+            (0..nelmts).all(|i| convert(i).is_some())
+        };
+        if ok {
+            0
+        } else {
+            -1
+        }
+    })
+    .unwrap_or(-1)
+}
+
+/// Creates a `bfloat16` datatype in native byte order.
+unsafe fn bfloat16_type() -> hid_t {
+    let id = H5Tcopy(*H5T_NATIVE_FLOAT);
+    let bf16 = CustomFloatType::bfloat16();
+    if id < 0
+        || H5Tset_fields(
+            id,
+            bf16.sign_pos,
+            bf16.exp_pos,
+            bf16.exp_size,
+            bf16.mant_pos,
+            bf16.mant_size,
+        ) < 0
+        || H5Tset_ebias(id, bf16.exp_bias) < 0
+        || H5Tset_size(id, bf16.size) < 0
+    {
+        return -1;
+    }
+    id
+}
+
+unsafe fn register_as(
+    pers: H5T_pers_t, name: &[u8], src_id: hid_t, dst_id: hid_t, func: H5T_conv_t,
+) {
+    if src_id < 0 || dst_id < 0 || H5Tregister(pers, name.as_ptr().cast(), src_id, dst_id, func) < 0
+    {
+        eprintln!("Error while registering datatype conversion: {}", String::from_utf8_lossy(name));
+    }
+}
+
+unsafe fn register(name: &[u8], src_id: hid_t, dst_id: hid_t, func: H5T_conv_t) {
+    register_as(H5T_pers_t::H5T_PERS_SOFT, name, src_id, dst_id, func);
+}
+
+/// This function requires a synchronisation with other calls to `hdf5`
+pub(crate) fn register_conversions() {
+    unsafe {
+        let opaque_id = H5Tcreate(H5T_class_t::H5T_OPAQUE, 1);
+        let dims: hsize_t = 1;
+        let array_id = H5Tarray_create2(*H5T_NATIVE_INT8, 1, addr_of!(dims));
+        register(b"opaque_tags\0", opaque_id, opaque_id, Some(conv_opaque));
+        register(b"array_shape\0", array_id, array_id, Some(conv_array_shape));
+        for (src_id, dst_id) in [(opaque_id, *H5T_NATIVE_INT64), (*H5T_NATIVE_INT64, opaque_id)] {
+            register(b"time\0", src_id, dst_id, Some(conv_time));
+        }
+        let bfloat16_id = bfloat16_type();
+        for float_id in [*H5T_NATIVE_FLOAT, *H5T_NATIVE_DOUBLE] {
+            for (src_id, dst_id) in [(bfloat16_id, float_id), (float_id, bfloat16_id)] {
+                let pers = H5T_pers_t::H5T_PERS_HARD;
+                register_as(pers, b"bfloat16\0", src_id, dst_id, Some(conv_bfloat16));
+            }
+        }
+        for id in [opaque_id, array_id, bfloat16_id] {
+            if id >= 0 {
+                H5Tclose(id);
+            }
+        }
+    }
+}
+
+/// Registers the conversion paths between complex number layouts (see [`conv_complex`]).
+///
+/// This function requires a synchronisation with other calls to `hdf5`
+fn register_complex_conversions() {
+    unsafe {
+        let dims: hsize_t = 1;
+        let array_id = H5Tarray_create2(*H5T_NATIVE_INT8, 1, addr_of!(dims));
+        let compound_id = H5Tcreate(H5T_class_t::H5T_COMPOUND, 1);
+        #[cfg(feature = "2.0.0")]
+        let complex_id = H5Tcomplex_create(*H5T_NATIVE_FLOAT);
+        #[cfg(not(feature = "2.0.0"))]
+        let complex_id = -1;
+        let mut pairs =
+            vec![(compound_id, compound_id), (compound_id, array_id), (array_id, compound_id)];
+        if complex_id >= 0 {
+            pairs.extend([
+                (complex_id, compound_id),
+                (compound_id, complex_id),
+                (complex_id, array_id),
+                (array_id, complex_id),
+            ]);
+        }
+        for (src_id, dst_id) in pairs {
+            register(b"complex_layout\0", src_id, dst_id, Some(conv_complex));
+        }
+        for id in [array_id, compound_id, complex_id] {
+            if id >= 0 {
+                H5Tclose(id);
+            }
+        }
+    }
+}
+
 /// Represents the HDF5 datatype object.
 #[repr(transparent)]
 #[derive(Clone)]
@@ -166,6 +632,12 @@ impl Datatype {
         h5lock!(H5Tget_order(self.id())).into()
     }
 
+    /// Returns `true` if the datatype is committed, i.e. stored in a file as a named
+    /// (or anonymous) datatype object.
+    pub fn is_committed(&self) -> bool {
+        h5lock!(H5Tcommitted(self.id())) > 0
+    }
+
     pub fn conv_path<D>(&self, dst: D) -> Option<Conversion>
     where
         D: Borrow<Self>,
@@ -194,6 +666,31 @@ impl Datatype {
         Self::from_type::<T>().ok().and_then(|dtype| dtype.conv_path(self))
     }
 
+    /// Allows data to be converted (in either direction) between opaque datatypes of the
+    /// same size tagged `tag_a` and `tag_b`, e.g. `Opaque<N>` (whose tag is empty) and opaque
+    /// data with a specific tag; the library doesn't convert between different tags otherwise.
+    ///
+    /// The registration is global and applies to all subsequent conversions.
+    pub fn register_opaque_conversion(tag_a: &str, tag_b: &str) {
+        let mut pairs = OPAQUE_TAG_PAIRS.lock();
+        pairs.insert((tag_a.to_owned(), tag_b.to_owned()));
+        pairs.insert((tag_b.to_owned(), tag_a.to_owned()));
+    }
+
+    /// Allows `Complex<T>` (and other complex number layouts) to be converted between
+    /// compound types with fields `r`/`i`, `re`/`im` or `real`/`imag` (in any order),
+    /// two-element arrays and native complex datatypes (HDF5 2.0 or later); the library
+    /// would otherwise only match compound fields by name.
+    ///
+    /// The registration is global and applies to all subsequent conversions; calling this
+    /// function more than once has no effect.
+    pub fn register_complex_conversions() {
+        lazy_static! {
+            static ref REGISTERED: () = h5lock!(register_complex_conversions());
+        }
+        lazy_static::initialize(&REGISTERED);
+    }
+
     pub fn is<T: H5Type>(&self) -> bool {
         Self::from_type::<T>().ok().map_or(false, |dtype| &dtype == self)
     }
@@ -213,6 +710,10 @@ impl Datatype {
         }
     }
 
+    /// Converts the datatype into a type descriptor.
+    ///
+    /// Integer, float and bitfield types stored in non-native byte order are wrapped in
+    /// `TypeDescriptor::Endian`.
     pub fn to_descriptor(&self) -> Result<TypeDescriptor> {
         use hdf5_types::TypeDescriptor as TD;
 
@@ -220,6 +721,11 @@ impl Datatype {
             let id = self.id();
             #[allow(clippy::unnecessary_cast)]
             let size = H5Tget_size(id) as usize;
+            let with_order = |desc: TypeDescriptor| match H5Tget_order(id) {
+                H5T_order_t::H5T_ORDER_LE => desc.with_byte_order(Endianness::Little),
+                H5T_order_t::H5T_ORDER_BE => desc.with_byte_order(Endianness::Big),
+                _ => desc,
+            };
             match H5Tget_class(id) {
                 H5T_class_t::H5T_INTEGER => {
                     let signed = match H5Tget_sign(id) {
@@ -227,28 +733,73 @@ impl Datatype {
                         H5T_sign_t::H5T_SGN_2 => true,
                         _ => return Err("Invalid sign of integer datatype".into()),
                     };
-                    let size = IntSize::from_int(size).ok_or("Invalid size of integer datatype")?;
-                    Ok(if signed { TD::Integer(size) } else { TD::Unsigned(size) })
+                    let precision = H5Tget_precision(id);
+                    let offset = h5try!(H5Tget_offset(id)) as usize;
+                    let desc = match IntSize::from_int(size) {
+                        Some(size) if precision == size as usize * 8 && offset == 0 => {
+                            if signed {
+                                TD::Integer(size)
+                            } else {
+                                TD::Unsigned(size)
+                            }
+                        }
+                        _ => {
+                            ensure!(precision > 0, "Invalid precision of integer datatype");
+                            TD::CustomInteger(CustomIntType { size, precision, offset, signed })
+                        }
+                    };
+                    Ok(with_order(desc))
                 }
                 H5T_class_t::H5T_FLOAT => {
-                    let size = FloatSize::from_int(size).ok_or("Invalid size of float datatype")?;
-                    Ok(TD::Float(size))
+                    let (mut sign_pos, mut exp_pos, mut exp_size) = (0, 0, 0);
+                    let (mut mant_pos, mut mant_size) = (0, 0);
+                    h5try!(H5Tget_fields(
+                        id,
+                        &mut sign_pos,
+                        &mut exp_pos,
+                        &mut exp_size,
+                        &mut mant_pos,
+                        &mut mant_size
+                    ));
+                    let layout = CustomFloatType {
+                        size,
+                        precision: H5Tget_precision(id),
+                        offset: h5try!(H5Tget_offset(id)) as _,
+                        sign_pos,
+                        exp_pos,
+                        exp_size,
+                        exp_bias: H5Tget_ebias(id),
+                        mant_pos,
+                        mant_size,
+                    };
+                    let desc = match FloatSize::from_int(size) {
+                        Some(size) if layout == CustomFloatType::ieee(size) => TD::Float(size),
+                        _ => TD::CustomFloat(layout),
+                    };
+                    Ok(with_order(desc))
                 }
                 H5T_class_t::H5T_ENUM => {
+                    let base_dt = Self::from_id(H5Tget_super(id))?;
+                    let base = base_dt.to_descriptor()?;
+                    let swap = matches!(base, TD::Endian(..));
+                    let (size, signed) = match base.with_byte_order(Endianness::NATIVE) {
+                        TD::Integer(size) => Ok((size, true)),
+                        TD::Unsigned(size) => Ok((size, false)),
+                        _ => Err("Invalid base type for enum datatype"),
+                    }?;
                     let mut members: Vec<EnumMember> = Vec::new();
                     for idx in 0..h5try!(H5Tget_nmembers(id)) as _ {
-                        let mut value: u64 = 0;
-                        h5try!(H5Tget_member_value(id, idx, addr_of_mut!(value).cast()));
+                        let mut buf = [0_u8; 16];
+                        h5try!(H5Tget_member_value(id, idx, buf.as_mut_ptr().cast()));
+                        let bytes = &mut buf[..size as usize];
+                        if swap {
+                            bytes.reverse();
+                        }
+                        let value = enum_value_from_bytes(bytes, signed);
                         let name = H5Tget_member_name(id, idx);
                         members.push(EnumMember { name: string_from_cstr(name), value });
                         h5_free_memory(name.cast());
                     }
-                    let base_dt = Self::from_id(H5Tget_super(id))?;
-                    let (size, signed) = match base_dt.to_descriptor()? {
-                        TD::Integer(size) => Ok((size, true)),
-                        TD::Unsigned(size) => Ok((size, false)),
-                        _ => Err("Invalid base type for enum datatype"),
-                    }?;
                     let bool_members = [
                         EnumMember { name: "FALSE".to_owned(), value: 0 },
                         EnumMember { name: "TRUE".to_owned(), value: 1 },
@@ -256,7 +807,7 @@ impl Datatype {
                     if size == IntSize::U1 && members == bool_members {
                         Ok(TD::Boolean)
                     } else {
-                        Ok(TD::Enum(EnumType { size, signed, members }))
+                        Ok(with_order(TD::Enum(EnumType { size, signed, members })))
                     }
                 }
                 H5T_class_t::H5T_COMPOUND => {
@@ -278,20 +829,28 @@ impl Datatype {
                 H5T_class_t::H5T_ARRAY => {
                     let base_dt = Self::from_id(H5Tget_super(id))?;
                     let ndims = h5try!(H5Tget_array_ndims(id));
-                    if ndims == 1 {
-                        let mut len: hsize_t = 0;
-                        h5try!(H5Tget_array_dims2(id, addr_of_mut!(len)));
-                        Ok(TD::FixedArray(Box::new(base_dt.to_descriptor()?), len as _))
-                    } else {
-                        Err("Multi-dimensional array datatypes are not supported".into())
+                    ensure!(ndims > 0, "Invalid rank of array datatype: {}", ndims);
+                    let mut dims: Vec<hsize_t> = vec![0; ndims as _];
+                    h5try!(H5Tget_array_dims2(id, dims.as_mut_ptr()));
+                    // multi-dimensional arrays are represented as nested fixed arrays
+                    let mut desc = base_dt.to_descriptor()?;
+                    for &len in dims.iter().rev() {
+                        desc = TD::FixedArray(Box::new(desc), len as _);
                     }
+                    Ok(desc)
                 }
                 H5T_class_t::H5T_STRING => {
                     let is_variable = h5try!(H5Tis_variable_str(id)) == 1;
                     let encoding = h5lock!(H5Tget_cset(id));
+                    let padding = match h5lock!(H5Tget_strpad(id)) {
+                        H5T_str_t::H5T_STR_NULLTERM => StringPadding::NullTerm,
+                        H5T_str_t::H5T_STR_NULLPAD => StringPadding::NullPad,
+                        H5T_str_t::H5T_STR_SPACEPAD => StringPadding::SpacePad,
+                        _ => fail!("Invalid padding for string datatype"),
+                    };
                     match (is_variable, encoding) {
-                        (false, H5T_cset_t::H5T_CSET_ASCII) => Ok(TD::FixedAscii(size)),
-                        (false, H5T_cset_t::H5T_CSET_UTF8) => Ok(TD::FixedUnicode(size)),
+                        (false, H5T_cset_t::H5T_CSET_ASCII) => Ok(TD::FixedAscii(size, padding)),
+                        (false, H5T_cset_t::H5T_CSET_UTF8) => Ok(TD::FixedUnicode(size, padding)),
                         (true, H5T_cset_t::H5T_CSET_ASCII) => Ok(TD::VarLenAscii),
                         (true, H5T_cset_t::H5T_CSET_UTF8) => Ok(TD::VarLenUnicode),
                         _ => Err("Invalid encoding for string datatype".into()),
@@ -301,6 +860,23 @@ impl Datatype {
                     let base_dt = Self::from_id(H5Tget_super(id))?;
                     Ok(TD::VarLenArray(Box::new(base_dt.to_descriptor()?)))
                 }
+                H5T_class_t::H5T_OPAQUE => {
+                    let tag = H5Tget_tag(id);
+                    ensure!(!tag.is_null(), "Invalid tag of opaque datatype");
+                    let tag_str = string_from_cstr(tag);
+                    h5_free_memory(tag.cast());
+                    Ok(TD::Opaque { size, tag: tag_str })
+                }
+                H5T_class_t::H5T_BITFIELD => {
+                    let size =
+                        IntSize::from_int(size).ok_or("Invalid size of bitfield datatype")?;
+                    Ok(with_order(TD::Bitfield(size)))
+                }
+                #[cfg(feature = "2.0.0")]
+                H5T_class_t::H5T_COMPLEX => {
+                    let base_dt = Self::from_id(H5Tget_super(id))?;
+                    Ok(TD::Complex(Box::new(base_dt.to_descriptor()?)))
+                }
                 _ => Err("Unsupported datatype class".into()),
             }
         })
@@ -313,28 +889,117 @@ impl Datatype {
     pub fn from_descriptor(desc: &TypeDescriptor) -> Result<Self> {
         use hdf5_types::TypeDescriptor as TD;
 
-        unsafe fn string_type(size: Option<usize>, encoding: H5T_cset_t) -> Result<hid_t> {
+        unsafe fn string_type(
+            size: Option<(usize, StringPadding)>, encoding: H5T_cset_t,
+        ) -> Result<hid_t> {
             let string_id = h5try!(H5Tcopy(*H5T_C_S1));
-            let padding = if size.is_none() {
-                H5T_str_t::H5T_STR_NULLTERM
-            } else {
-                H5T_str_t::H5T_STR_NULLPAD
+            let padding = match size.map(|(_, padding)| padding) {
+                None | Some(StringPadding::NullTerm) => H5T_str_t::H5T_STR_NULLTERM,
+                Some(StringPadding::NullPad) => H5T_str_t::H5T_STR_NULLPAD,
+                Some(StringPadding::SpacePad) => H5T_str_t::H5T_STR_SPACEPAD,
             };
-            let size = size.unwrap_or(H5T_VARIABLE);
+            let size = size.map_or(H5T_VARIABLE, |(size, _)| size);
             h5try!(H5Tset_cset(string_id, encoding));
             h5try!(H5Tset_strpad(string_id, padding));
             h5try!(H5Tset_size(string_id, size));
             Ok(string_id)
         }
 
-        #[cfg(feature = "f16")]
-        unsafe fn f16_type() -> Result<hid_t> {
-            use hdf5_sys::h5t::{H5Tset_ebias, H5Tset_fields};
-            let f16_id = be_le!(H5T_IEEE_F32BE, H5T_IEEE_F32LE);
-            h5try!(H5Tset_fields(f16_id, 15, 10, 5, 0, 10)); // cf. h5py/h5py#339
-            h5try!(H5Tset_size(f16_id, 2));
-            h5try!(H5Tset_ebias(f16_id, 15));
-            Ok(f16_id)
+        unsafe fn resized_int_type(int_id: hid_t, size: usize) -> Result<hid_t> {
+            // growing an integer type keeps its precision, so it has to be set explicitly
+            h5try!(H5Tset_size(int_id, size));
+            h5try!(H5Tset_precision(int_id, 8 * size));
+            Ok(int_id)
+        }
+
+        unsafe fn custom_int_type(tp: &CustomIntType) -> Result<hid_t> {
+            ensure!(
+                tp.precision > 0 && tp.offset + tp.precision <= 8 * tp.size,
+                "Invalid custom integer type: {} bits at offset {} do not fit in {} bytes",
+                tp.precision,
+                tp.offset,
+                tp.size
+            );
+            let int_id = if tp.signed {
+                be_le!(H5T_STD_I8BE, H5T_STD_I8LE)
+            } else {
+                be_le!(H5T_STD_U8BE, H5T_STD_U8LE)
+            };
+            h5try!(H5Tset_size(int_id, tp.size));
+            h5try!(H5Tset_precision(int_id, tp.precision));
+            h5try!(H5Tset_offset(int_id, tp.offset));
+            Ok(int_id)
+        }
+
+        unsafe fn custom_float_type(tp: &CustomFloatType) -> Result<hid_t> {
+            let bits = tp.offset + tp.precision;
+            ensure!(
+                tp.precision > 0
+                    && bits <= 8 * tp.size
+                    && tp.exp_size > 0
+                    && tp.mant_size > 0
+                    && tp.sign_pos < bits
+                    && tp.exp_pos + tp.exp_size <= bits
+                    && tp.mant_pos + tp.mant_size <= bits,
+                "Invalid custom float type: {:?}",
+                tp
+            );
+            // start from a float type at least as large so that the fields can be set first
+            // (the library doesn't allow them to extend past the significant bits)
+            let (float_id, base_size) = if tp.size <= 4 {
+                (be_le!(H5T_IEEE_F32BE, H5T_IEEE_F32LE), 4)
+            } else {
+                (be_le!(H5T_IEEE_F64BE, H5T_IEEE_F64LE), 8)
+            };
+            if tp.size > base_size {
+                h5try!(H5Tset_size(float_id, tp.size));
+                h5try!(H5Tset_precision(float_id, 8 * tp.size));
+            }
+            h5try!(H5Tset_fields(
+                float_id,
+                tp.sign_pos,
+                tp.exp_pos,
+                tp.exp_size,
+                tp.mant_pos,
+                tp.mant_size
+            ));
+            h5try!(H5Tset_ebias(float_id, tp.exp_bias));
+            if tp.size < base_size {
+                h5try!(H5Tset_size(float_id, tp.size));
+            }
+            if (tp.precision, tp.offset) != (8 * tp.size, 0) {
+                // shifting the offset may temporarily grow the type
+                h5try!(H5Tset_precision(float_id, bits));
+                h5try!(H5Tset_offset(float_id, tp.offset));
+                h5try!(H5Tset_precision(float_id, tp.precision));
+                h5try!(H5Tset_size(float_id, tp.size));
+            }
+            Ok(float_id)
+        }
+
+        unsafe fn enum_type_with_order(tp: &EnumType, order: Endianness) -> Result<hid_t> {
+            let base = Datatype::from_descriptor(&tp.base_type().with_byte_order(order))?;
+            let enum_id = h5try!(H5Tenum_create(base.id()));
+            for member in &tp.members {
+                let name = to_cstring(member.name.as_ref())?;
+                let mut value = enum_value_to_bytes(member.value, tp.signed, base.size());
+                if !order.is_native() {
+                    value.reverse();
+                }
+                h5try!(H5Tenum_insert(enum_id, name.as_ptr(), value.as_ptr().cast()));
+            }
+            Ok(enum_id)
+        }
+
+        #[cfg(feature = "2.0.0")]
+        unsafe fn complex_type(base: &TypeDescriptor) -> Result<hid_t> {
+            let base_dt = Datatype::from_descriptor(base)?;
+            Ok(h5try!(H5Tcomplex_create(base_dt.id())))
+        }
+
+        #[cfg(not(feature = "2.0.0"))]
+        unsafe fn complex_type(_base: &TypeDescriptor) -> Result<hid_t> {
+            fail!("Native complex datatypes require HDF5 2.0 or later")
         }
 
         let datatype_id: Result<_> = h5lock!({
@@ -344,18 +1009,20 @@ impl Datatype {
                     IntSize::U2 => be_le!(H5T_STD_I16BE, H5T_STD_I16LE),
                     IntSize::U4 => be_le!(H5T_STD_I32BE, H5T_STD_I32LE),
                     IntSize::U8 => be_le!(H5T_STD_I64BE, H5T_STD_I64LE),
+                    IntSize::U16 => resized_int_type(be_le!(H5T_STD_I64BE, H5T_STD_I64LE), 16)?,
                 }),
                 TD::Unsigned(size) => Ok(match size {
                     IntSize::U1 => be_le!(H5T_STD_U8BE, H5T_STD_U8LE),
                     IntSize::U2 => be_le!(H5T_STD_U16BE, H5T_STD_U16LE),
                     IntSize::U4 => be_le!(H5T_STD_U32BE, H5T_STD_U32LE),
                     IntSize::U8 => be_le!(H5T_STD_U64BE, H5T_STD_U64LE),
+                    IntSize::U16 => resized_int_type(be_le!(H5T_STD_U64BE, H5T_STD_U64LE), 16)?,
                 }),
                 TD::Float(size) => Ok(match size {
                     #[cfg(feature = "f16")]
-                    FloatSize::U2 => f16_type()?,
+                    FloatSize::U2 => custom_float_type(&CustomFloatType::ieee(FloatSize::U2))?,
                     FloatSize::U4 => be_le!(H5T_IEEE_F32BE, H5T_IEEE_F32LE),
-                    FloatSize::U8 => be_le!(H5T_IEEE_I16BE, H5T_IEEE_F64LE),
+                    FloatSize::U8 => be_le!(H5T_IEEE_F64BE, H5T_IEEE_F64LE),
                 }),
                 TD::Boolean => {
                     let bool_id = h5try!(H5Tenum_create(*H5T_NATIVE_INT8));
@@ -373,19 +1040,7 @@ impl Datatype {
                     ));
                     Ok(bool_id)
                 }
-                TD::Enum(ref enum_type) => {
-                    let base = Self::from_descriptor(&enum_type.base_type())?;
-                    let enum_id = h5try!(H5Tenum_create(base.id()));
-                    for member in &enum_type.members {
-                        let name = to_cstring(member.name.as_ref())?;
-                        h5try!(H5Tenum_insert(
-                            enum_id,
-                            name.as_ptr(),
-                            addr_of!(member.value).cast()
-                        ));
-                    }
-                    Ok(enum_id)
-                }
+                TD::Enum(ref enum_type) => enum_type_with_order(enum_type, Endianness::NATIVE),
                 TD::Compound(ref compound_type) => {
                     let compound_id = h5try!(H5Tcreate(H5T_class_t::H5T_COMPOUND, 1));
                     for field in &compound_type.fields {
@@ -398,18 +1053,83 @@ impl Datatype {
                     Ok(compound_id)
                 }
                 TD::FixedArray(ref ty, len) => {
+                    // nested fixed arrays are stored as a single multi-dimensional array
+                    let mut dims = vec![len as hsize_t];
+                    let mut ty = &**ty;
+                    while let TD::FixedArray(ref elem_ty, len) = *ty {
+                        dims.push(len as _);
+                        ty = elem_ty;
+                    }
                     let elem_dt = Self::from_descriptor(ty)?;
-                    let dims = len as hsize_t;
-                    Ok(h5try!(H5Tarray_create2(elem_dt.id(), 1, addr_of!(dims))))
+                    Ok(h5try!(H5Tarray_create2(elem_dt.id(), dims.len() as _, dims.as_ptr())))
+                }
+                TD::FixedAscii(size, pad) => {
+                    string_type(Some((size, pad)), H5T_cset_t::H5T_CSET_ASCII)
+                }
+                TD::FixedUnicode(size, pad) => {
+                    string_type(Some((size, pad)), H5T_cset_t::H5T_CSET_UTF8)
                 }
-                TD::FixedAscii(size) => string_type(Some(size), H5T_cset_t::H5T_CSET_ASCII),
-                TD::FixedUnicode(size) => string_type(Some(size), H5T_cset_t::H5T_CSET_UTF8),
                 TD::VarLenArray(ref ty) => {
                     let elem_dt = Self::from_descriptor(ty)?;
                     Ok(h5try!(H5Tvlen_create(elem_dt.id())))
                 }
                 TD::VarLenAscii => string_type(None, H5T_cset_t::H5T_CSET_ASCII),
                 TD::VarLenUnicode => string_type(None, H5T_cset_t::H5T_CSET_UTF8),
+                TD::Opaque { size, ref tag } => {
+                    let opaque_id = h5try!(H5Tcreate(H5T_class_t::H5T_OPAQUE, size));
+                    if !tag.is_empty() {
+                        let tag = to_cstring(tag.as_ref())?;
+                        h5try!(H5Tset_tag(opaque_id, tag.as_ptr()));
+                    }
+                    Ok(opaque_id)
+                }
+                TD::Bitfield(size) => Ok(match size {
+                    IntSize::U1 => be_le!(H5T_STD_B8BE, H5T_STD_B8LE),
+                    IntSize::U2 => be_le!(H5T_STD_B16BE, H5T_STD_B16LE),
+                    IntSize::U4 => be_le!(H5T_STD_B32BE, H5T_STD_B32LE),
+                    IntSize::U8 => be_le!(H5T_STD_B64BE, H5T_STD_B64LE),
+                    IntSize::U16 => resized_int_type(be_le!(H5T_STD_B64BE, H5T_STD_B64LE), 16)?,
+                }),
+                TD::CustomInteger(ref tp) => custom_int_type(tp),
+                TD::CustomFloat(ref tp) => custom_float_type(tp),
+                TD::Endian(ref ty, order) => {
+                    if let TD::Enum(ref enum_type) = **ty {
+                        // the byte order can't be changed once the members are inserted
+                        enum_type_with_order(enum_type, order)
+                    } else {
+                        ensure!(
+                            matches!(
+                                **ty,
+                                TD::Integer(_)
+                                    | TD::Unsigned(_)
+                                    | TD::Float(_)
+                                    | TD::Bitfield(_)
+                                    | TD::CustomInteger(_)
+                                    | TD::CustomFloat(_)
+                            ),
+                            "Byte order can't be set on {}",
+                            ty
+                        );
+                        let dt = Self::from_descriptor(ty)?;
+                        let order_id = h5try!(H5Tcopy(dt.id()));
+                        h5try!(H5Tset_order(
+                            order_id,
+                            match order {
+                                Endianness::Little => H5T_order_t::H5T_ORDER_LE,
+                                Endianness::Big => H5T_order_t::H5T_ORDER_BE,
+                            }
+                        ));
+                        Ok(order_id)
+                    }
+                }
+                TD::Complex(ref ty) => {
+                    ensure!(
+                        matches!(ty.to_native_repr(), TD::Float(_)),
+                        "Complex datatypes can only have float base types, got {}",
+                        ty
+                    );
+                    complex_type(ty)
+                }
             }
         });
 
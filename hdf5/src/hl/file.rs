@@ -1,13 +1,28 @@
+use std::ffi::CString;
 use std::fmt::{self, Debug};
 use std::mem;
 use std::ops::Deref;
 use std::path::Path;
-
+#[cfg(feature = "1.10.0")]
+use std::{ptr, slice};
+
+#[cfg(feature = "1.14.0")]
+use hdf5_sys::h5f::H5Fdelete;
+#[cfg(feature = "1.12.0")]
+use hdf5_sys::h5f::H5Fis_accessible;
+use hdf5_sys::h5f::H5Freset_mdc_hit_rate_stats;
+#[cfg(feature = "1.10.0")]
+use hdf5_sys::h5f::{
+    H5F_info2_t, H5F_retry_info_t, H5F_sect_info_t, H5Fget_free_sections, H5Fget_info2,
+    H5Fget_mdc_hit_rate, H5Fget_mdc_size, H5Fget_metadata_read_retry_info, H5FD_MEM_DEFAULT,
+};
 use hdf5_sys::h5f::{
     H5Fclose, H5Fcreate, H5Fflush, H5Fget_access_plist, H5Fget_create_plist, H5Fget_filesize,
-    H5Fget_freespace, H5Fget_intent, H5Fget_obj_count, H5Fget_obj_ids, H5Fopen, H5F_ACC_DEFAULT,
-    H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_SCOPE_LOCAL,
+    H5Fget_freespace, H5Fget_intent, H5Fget_obj_count, H5Fget_obj_ids, H5Fis_hdf5, H5Fopen,
+    H5F_ACC_DEFAULT, H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_SCOPE_LOCAL,
 };
+#[cfg(feature = "1.10.1")]
+use hdf5_sys::h5f::{H5Fget_page_buffering_stats, H5Freset_page_buffering_stats};
 
 use crate::hl::plist::{
     file_access::{FileAccess, FileAccessBuilder},
@@ -107,6 +122,38 @@ impl File {
         FileBuilder::new()
     }
 
+    /// Returns `true` if the file at the given path is in the HDF5 format.
+    ///
+    /// Fails if the file does not exist or cannot be read.
+    pub fn is_hdf5<P: AsRef<Path>>(filename: P) -> Result<bool> {
+        let filename = path_to_cstring(filename.as_ref())?;
+        Ok(h5call!(H5Fis_hdf5(filename.as_ptr()))? > 0)
+    }
+
+    /// Returns `true` if the file at the given path is in the HDF5 format and can be
+    /// opened with the given file access properties (e.g. file driver).
+    ///
+    /// Fails if the file does not exist or cannot be read.
+    #[cfg(feature = "1.12.0")]
+    pub fn is_accessible<P: AsRef<Path>>(filename: P, fapl: &FileAccess) -> Result<bool> {
+        let filename = path_to_cstring(filename.as_ref())?;
+        Ok(h5call!(H5Fis_accessible(filename.as_ptr(), fapl.id()))? > 0)
+    }
+
+    /// Deletes an HDF5 file using the given file access properties.
+    ///
+    /// Unlike `std::fs::remove_file()`, this removes all member files of file sets created
+    /// by the family, multi and split drivers (in which case `filename` should be the same
+    /// as the one used to create the file). Fails if the file is not an HDF5 file.
+    ///
+    /// Note: this requires HDF5 1.14, since earlier versions don't implement deletion in the
+    /// file drivers.
+    #[cfg(feature = "1.14.0")]
+    pub fn delete<P: AsRef<Path>>(filename: P, fapl: &FileAccess) -> Result<()> {
+        let filename = path_to_cstring(filename.as_ref())?;
+        h5call!(H5Fdelete(filename.as_ptr(), fapl.id())).and(Ok(()))
+    }
+
     /// Returns the file size in bytes (or 0 if the file handle is invalid).
     pub fn size(&self) -> u64 {
         h5get_d!(H5Fget_filesize(self.id()): hsize_t) as _
@@ -132,6 +179,131 @@ impl File {
         h5call!(H5Fflush(self.id(), H5F_SCOPE_LOCAL)).and(Ok(()))
     }
 
+    /// Collects file-level statistics: metadata sizes, free-space sections, metadata cache
+    /// and page buffer usage, and metadata read retries.
+    ///
+    /// Cache hit rate and page buffer counters are accumulated since the file was opened or
+    /// since the last call to [`reset_mdc_hit_rate_stats()`](Self::reset_mdc_hit_rate_stats).
+    #[cfg(feature = "1.10.0")]
+    pub fn stats(&self) -> Result<FileStats> {
+        h5lock!({
+            let info = h5get!(H5Fget_info2(self.id()): H5F_info2_t)?;
+            let mdc_hit_rate = h5get!(H5Fget_mdc_hit_rate(self.id()): c_double)?;
+            let (max_size, min_clean_size, cur_size, cur_num_entries) =
+                h5get!(H5Fget_mdc_size(self.id()): size_t, size_t, size_t, c_int)?;
+            Ok(FileStats {
+                superblock: SuperblockStats {
+                    version: info.super_.version as _,
+                    size: info.super_.super_size as _,
+                    ext_size: info.super_.super_ext_size as _,
+                },
+                free_space: FreeSpaceStats {
+                    version: info.free.version as _,
+                    meta_size: info.free.meta_size as _,
+                    total: info.free.tot_space as _,
+                },
+                shared_messages: SharedMessageStats {
+                    version: info.sohm.version as _,
+                    header_size: info.sohm.hdr_size as _,
+                    index_size: info.sohm.msgs_info.index_size as _,
+                    heap_size: info.sohm.msgs_info.heap_size as _,
+                },
+                free_sections: self.get_free_sections()?,
+                mdc_hit_rate,
+                mdc_size: MetadataCacheSize {
+                    max_size,
+                    min_clean_size,
+                    cur_size,
+                    cur_num_entries: cur_num_entries.max(0) as _,
+                },
+                #[cfg(feature = "1.10.1")]
+                page_buffering: self.get_page_buffering_stats(),
+                read_retries: self.get_read_retries()?,
+            })
+        })
+    }
+
+    #[cfg(feature = "1.10.0")]
+    fn get_free_sections(&self) -> Result<Vec<FreeSection>> {
+        h5lock!({
+            let count =
+                h5try!(H5Fget_free_sections(self.id(), H5FD_MEM_DEFAULT, 0, ptr::null_mut()))
+                    as usize;
+            let mut sections = vec![H5F_sect_info_t::default(); count];
+            if count > 0 {
+                h5try!(H5Fget_free_sections(
+                    self.id(),
+                    H5FD_MEM_DEFAULT,
+                    count,
+                    sections.as_mut_ptr()
+                ));
+            }
+            Ok(sections
+                .iter()
+                .map(|s| FreeSection { addr: s.addr as _, size: s.size as _ })
+                .collect())
+        })
+    }
+
+    #[cfg(feature = "1.10.1")]
+    fn get_page_buffering_stats(&self) -> Option<PageBufferingStats> {
+        // fails if page buffering is not enabled for the file
+        let (mut accesses, mut hits, mut misses) = ([0; 2], [0; 2], [0; 2]);
+        let (mut evictions, mut bypasses) = ([0; 2], [0; 2]);
+        h5call!(H5Fget_page_buffering_stats(
+            self.id(),
+            accesses.as_mut_ptr(),
+            hits.as_mut_ptr(),
+            misses.as_mut_ptr(),
+            evictions.as_mut_ptr(),
+            bypasses.as_mut_ptr()
+        ))
+        .ok()?;
+        let counters = |i: usize| PageBufferCounters {
+            accesses: accesses[i] as _,
+            hits: hits[i] as _,
+            misses: misses[i] as _,
+            evictions: evictions[i] as _,
+            bypasses: bypasses[i] as _,
+        };
+        Some(PageBufferingStats { metadata: counters(0), raw_data: counters(1) })
+    }
+
+    #[cfg(feature = "1.10.0")]
+    fn get_read_retries(&self) -> Result<ReadRetryStats> {
+        h5lock!({
+            let info = h5get!(H5Fget_metadata_read_retry_info(self.id()): H5F_retry_info_t)?;
+            let nbins = info.nbins as usize;
+            let retries = info
+                .retries
+                .iter()
+                .map(|&ptr| {
+                    if ptr.is_null() {
+                        Vec::new()
+                    } else {
+                        let bins = unsafe { slice::from_raw_parts(ptr, nbins) }.to_vec();
+                        unsafe { h5_free_memory(ptr.cast()) };
+                        bins
+                    }
+                })
+                .collect();
+            Ok(ReadRetryStats { nbins, retries })
+        })
+    }
+
+    /// Resets the metadata cache hit rate statistics (and page buffering statistics,
+    /// if page buffering is enabled).
+    pub fn reset_mdc_hit_rate_stats(&self) -> Result<()> {
+        h5lock!({
+            h5try!(H5Freset_mdc_hit_rate_stats(self.id()));
+            #[cfg(feature = "1.10.1")]
+            if self.get_page_buffering_stats().is_some() {
+                h5try!(H5Freset_page_buffering_stats(self.id()));
+            }
+            Ok(())
+        })
+    }
+
     /// Returns objects IDs of the contained objects. NOTE: these are borrowed references.
     #[allow(unused)]
     fn get_obj_ids(&self, types: c_uint) -> Vec<hid_t> {
@@ -180,6 +352,133 @@ impl File {
     }
 }
 
+/// Superblock information, see [`FileStats`].
+#[cfg(feature = "1.10.0")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SuperblockStats {
+    /// Superblock version
+    pub version: u32,
+    /// Superblock size in bytes
+    pub size: u64,
+    /// Superblock extension size in bytes
+    pub ext_size: u64,
+}
+
+/// Free-space manager information, see [`FileStats`].
+#[cfg(feature = "1.10.0")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FreeSpaceStats {
+    /// Free-space manager version
+    pub version: u32,
+    /// Size of free-space manager metadata in bytes
+    pub meta_size: u64,
+    /// Total amount of free space in bytes
+    pub total: u64,
+}
+
+/// Shared object header message information, see [`FileStats`].
+#[cfg(feature = "1.10.0")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SharedMessageStats {
+    /// Shared message table version
+    pub version: u32,
+    /// Size of the shared message table header in bytes
+    pub header_size: u64,
+    /// Size of the shared message indices in bytes
+    pub index_size: u64,
+    /// Size of the shared message heap in bytes
+    pub heap_size: u64,
+}
+
+/// A free-space section tracked by the library.
+#[cfg(feature = "1.10.0")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FreeSection {
+    /// Address of the section in the file
+    pub addr: u64,
+    /// Size of the section in bytes
+    pub size: u64,
+}
+
+/// Metadata cache size information, see [`FileStats`].
+#[cfg(feature = "1.10.0")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MetadataCacheSize {
+    /// Maximum cache size in bytes
+    pub max_size: usize,
+    /// Minimum clean size in bytes
+    pub min_clean_size: usize,
+    /// Current cache size in bytes
+    pub cur_size: usize,
+    /// Current number of cache entries
+    pub cur_num_entries: usize,
+}
+
+/// Page buffer counters for either metadata or raw data pages.
+#[cfg(feature = "1.10.1")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PageBufferCounters {
+    /// Number of page accesses
+    pub accesses: u32,
+    /// Number of page hits
+    pub hits: u32,
+    /// Number of page misses
+    pub misses: u32,
+    /// Number of page evictions
+    pub evictions: u32,
+    /// Number of accesses that bypassed the page buffer
+    pub bypasses: u32,
+}
+
+/// Page buffering statistics, see [`FileStats`].
+#[cfg(feature = "1.10.1")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PageBufferingStats {
+    /// Counters for metadata pages
+    pub metadata: PageBufferCounters,
+    /// Counters for raw data pages
+    pub raw_data: PageBufferCounters,
+}
+
+/// Metadata read retry information (only relevant for files opened for SWMR reading).
+#[cfg(feature = "1.10.0")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReadRetryStats {
+    /// Number of bins in each retry histogram
+    pub nbins: usize,
+    /// Retry histograms for each metadata entry type (empty if there were no retries)
+    pub retries: Vec<Vec<u32>>,
+}
+
+/// File-level statistics, see [`File::stats()`].
+#[cfg(feature = "1.10.0")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileStats {
+    /// Superblock information
+    pub superblock: SuperblockStats,
+    /// Free-space manager information
+    pub free_space: FreeSpaceStats,
+    /// Shared object header message information
+    pub shared_messages: SharedMessageStats,
+    /// Free-space sections of all types
+    pub free_sections: Vec<FreeSection>,
+    /// Metadata cache hit rate (between 0 and 1)
+    pub mdc_hit_rate: f64,
+    /// Metadata cache size
+    pub mdc_size: MetadataCacheSize,
+    /// Page buffering statistics (`None` if page buffering is not enabled)
+    #[cfg(feature = "1.10.1")]
+    pub page_buffering: Option<PageBufferingStats>,
+    /// Metadata read retries
+    pub read_retries: ReadRetryStats,
+}
+
+fn path_to_cstring(filename: &Path) -> Result<CString> {
+    to_cstring(
+        filename.to_str().ok_or_else(|| format!("Invalid UTF-8 in file name: {filename:?}"))?,
+    )
+}
+
 /// File builder allowing to customize file access/creation property lists.
 #[derive(Default, Clone, Debug)]
 pub struct FileBuilder {
@@ -226,9 +525,7 @@ impl FileBuilder {
                 return Ok(file);
             }
         }
-        let filename = to_cstring(
-            filename.to_str().ok_or_else(|| format!("Invalid UTF-8 in file name: {filename:?}"))?,
-        )?;
+        let filename = path_to_cstring(filename)?;
         let flags = match mode {
             OpenMode::Read => H5F_ACC_RDONLY,
             OpenMode::ReadWrite => H5F_ACC_RDWR,
@@ -341,6 +638,11 @@ pub mod tests {
     use std::fs;
     use std::io::{Read, Write};
 
+    #[cfg(feature = "1.10.1")]
+    use crate::file::FileSpaceStrategy;
+    #[cfg(feature = "1.12.0")]
+    use crate::plist::FileAccess;
+
     #[test]
     pub fn test_is_read_only() {
         with_tmp_path(|path| {
@@ -443,6 +745,101 @@ pub mod tests {
         })
     }
 
+    #[test]
+    #[cfg(feature = "1.10.0")]
+    pub fn test_stats() {
+        with_tmp_file(|file| {
+            file.new_dataset::<u32>().shape(100).create("ds").unwrap();
+            file.flush().unwrap();
+            let stats = file.stats().unwrap();
+            assert!(stats.superblock.size > 0);
+            assert!((0.0..=1.0).contains(&stats.mdc_hit_rate));
+            assert!(stats.mdc_size.max_size > 0);
+            assert!(stats.mdc_size.cur_num_entries > 0);
+            assert!(stats.mdc_size.cur_size <= stats.mdc_size.max_size);
+            assert_eq!(stats.read_retries.retries.len(), 21);
+            assert!(stats.read_retries.retries.iter().all(Vec::is_empty));
+            #[cfg(feature = "1.10.1")]
+            assert_eq!(stats.page_buffering, None);
+            file.reset_mdc_hit_rate_stats().unwrap();
+            assert_eq!(file.stats().unwrap().mdc_hit_rate, 0.0);
+        });
+        #[cfg(feature = "1.10.1")]
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fcpl(|p| {
+                    p.file_space_strategy(FileSpaceStrategy::PageAggregation)
+                        .file_space_page_size(4096)
+                })
+                .with_fapl(|p| p.page_buffer_size(4096 * 16, 0, 0))
+                .create(&path)
+                .unwrap();
+            file.new_dataset::<u32>().shape(1000).create("ds").unwrap();
+            let stats = file.stats().unwrap();
+            assert!(stats.page_buffering.is_some());
+            file.reset_mdc_hit_rate_stats().unwrap();
+            let pb = file.stats().unwrap().page_buffering.unwrap();
+            assert_eq!(pb, Default::default());
+        });
+    }
+
+    #[test]
+    pub fn test_is_hdf5() {
+        with_tmp_dir(|dir| {
+            let path = dir.join("foo.h5");
+            File::create(&path).unwrap().close().unwrap();
+            assert!(File::is_hdf5(&path).unwrap());
+            let text = dir.join("foo.txt");
+            fs::write(&text, "foo").unwrap();
+            assert!(!File::is_hdf5(&text).unwrap());
+            assert!(File::is_hdf5(dir.join("bar.h5")).is_err());
+            #[cfg(feature = "1.12.0")]
+            {
+                let fapl = FileAccess::try_new().unwrap();
+                assert!(File::is_accessible(&path, &fapl).unwrap());
+                assert!(!File::is_accessible(&text, &fapl).unwrap());
+            }
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "1.14.0")]
+    pub fn test_delete() {
+        with_tmp_dir(|dir| {
+            let count_files = || fs::read_dir(&dir).unwrap().count();
+
+            let path = dir.join("foo.h5");
+            File::create(&path).unwrap().close().unwrap();
+            assert_eq!(count_files(), 1);
+            File::delete(&path, &FileAccess::try_new().unwrap()).unwrap();
+            assert_eq!(count_files(), 0);
+            assert!(File::delete(&path, &FileAccess::try_new().unwrap()).is_err());
+
+            let fapl = FileAccess::build().family_options(1024).finish().unwrap();
+            let path = dir.join("family-%05d.h5");
+            let file = File::with_options().set_fapl(&fapl).unwrap().create(&path).unwrap();
+            file.new_dataset::<u8>().shape(4096).create("data").unwrap();
+            file.close().unwrap();
+            assert!(count_files() > 1);
+            File::delete(&path, &fapl).unwrap();
+            assert_eq!(count_files(), 0);
+
+            let fapl = FileAccess::build().split().finish().unwrap();
+            let path = dir.join("split");
+            File::with_options().set_fapl(&fapl).unwrap().create(&path).unwrap().close().unwrap();
+            assert_eq!(count_files(), 2);
+            File::delete(&path, &fapl).unwrap();
+            assert_eq!(count_files(), 0);
+
+            let fapl = FileAccess::build().multi().finish().unwrap();
+            let path = dir.join("multi");
+            File::with_options().set_fapl(&fapl).unwrap().create(&path).unwrap().close().unwrap();
+            assert!(count_files() > 1);
+            File::delete(&path, &fapl).unwrap();
+            assert_eq!(count_files(), 0);
+        })
+    }
+
     #[test]
     pub fn test_userblock() {
         with_tmp_file(|file| {
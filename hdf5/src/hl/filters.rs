@@ -216,6 +216,8 @@ impl Filter {
         Self::SZip(coding, px_per_block)
     }
 
+    /// N-bit filter, storing only the significant bits of integer and float values; it is
+    /// useful with custom-precision types (`TypeDescriptor::CustomInteger`/`CustomFloat`).
     pub fn nbit() -> Self {
         Self::NBit
     }
@@ -1,23 +1,37 @@
 use std::fmt::{self, Debug};
 use std::ops::Deref;
 use std::panic;
-use std::ptr::addr_of_mut;
+use std::ptr::{self, addr_of_mut};
 
 use hdf5_sys::{
     h5::{hsize_t, H5_index_t, H5_iter_order_t},
     h5d::H5Dopen2,
+    h5f::{H5Fmount, H5Funmount},
     h5g::{H5G_info_t, H5Gcreate2, H5Gget_info, H5Gopen2},
     h5l::{
         H5L_info_t, H5L_iterate_t, H5L_type_t, H5Lcreate_external, H5Lcreate_hard, H5Lcreate_soft,
-        H5Ldelete, H5Lexists, H5Literate, H5Lmove, H5L_SAME_LOC,
+        H5Ldelete, H5Lexists, H5Lget_val, H5Literate, H5Lmove, H5Lunpack_elink_val, H5L_SAME_LOC,
     },
     h5p::{H5Pcreate, H5Pset_create_intermediate_group},
-    h5t::H5T_cset_t,
+    h5t::{H5T_cset_t, H5Tcommit2, H5Tcommit_anon, H5Topen2},
 };
 
-use crate::globals::H5P_LINK_CREATE;
+#[cfg(not(feature = "1.12.0"))]
+use hdf5_sys::h5l::H5Lget_info1 as H5Lget_info;
+#[cfg(feature = "1.12.0")]
+use hdf5_sys::h5l::H5Lget_info2 as H5Lget_info;
+#[cfg(feature = "1.8.5")]
+use hdf5_sys::h5o::H5Oexists_by_name;
+
+use hdf5_types::TypeDescriptor;
+
+use crate::globals::{H5P_DATASET_ACCESS, H5P_GROUP_ACCESS, H5P_LINK_CREATE};
+use crate::hl::dataset::Maybe;
+use crate::hl::link_class::{create_user_defined, LinkClass};
+use crate::hl::table::Table;
 use crate::internal_prelude::*;
-use crate::{Location, LocationType};
+use crate::plist::{FileMount, LinkAccess};
+use crate::{Location, LocationToken, LocationType};
 
 /// Represents the HDF5 group object.
 #[repr(transparent)]
@@ -101,8 +115,13 @@ impl Group {
 
     /// Opens an existing group in a file or group.
     pub fn group(&self, name: &str) -> Result<Self> {
-        let name = to_cstring(name)?;
-        Self::from_id(h5try!(H5Gopen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
+        GroupWithLapl { group: self, lapl: None }.group(name)
+    }
+
+    /// Returns accessors for members of this group which traverse links using the given
+    /// link access properties (e.g. to resolve external links in relocated files).
+    pub fn with_lapl<'a>(&'a self, lapl: &'a LinkAccess) -> GroupWithLapl<'a> {
+        GroupWithLapl { group: self, lapl: Some(lapl) }
     }
 
     /// Creates a soft link.
@@ -168,6 +187,19 @@ impl Group {
         .and(Ok(()))
     }
 
+    /// Creates a user-defined link of the given link class, registering the class if needed.
+    ///
+    /// The payload is stored in the link and passed to `LinkClass::traverse()` when the link
+    /// is followed. Note: `link_name` is relative to the current object.
+    pub fn link_user_defined<L: LinkClass>(&self, payload: &[u8], link_name: &str) -> Result<()> {
+        L::register()?;
+        h5lock!({
+            let lcpl = make_lcpl()?;
+            let link_name = to_cstring(link_name)?;
+            create_user_defined(self.id(), link_name.as_ptr(), L::ID, payload, lcpl.id())
+        })
+    }
+
     /// Relinks an object. Note: `name` and `path` are relative to the current object.
     pub fn relink(&self, name: &str, path: &str) -> Result<()> {
         // TODO: &mut self?
@@ -193,11 +225,88 @@ impl Group {
 
     /// Check if a link with a given name exists in this file or group.
     pub fn link_exists(&self, name: &str) -> bool {
-        (|| -> Result<bool> {
-            let name = to_cstring(name)?;
-            Ok(h5call!(H5Lexists(self.id(), name.as_ptr(), H5P_DEFAULT))? > 0)
-        })()
-        .unwrap_or(false)
+        GroupWithLapl { group: self, lapl: None }.link_exists(name)
+    }
+
+    /// Returns the target of a link without traversing it.
+    ///
+    /// This works for dangling soft and external links as well, since the target object
+    /// is never opened (see also `link_is_dangling()`).
+    pub fn link_target(&self, name: &str) -> Result<LinkTarget> {
+        let name = to_cstring(name)?;
+        h5lock!({
+            let mut info = H5L_info_t::default();
+            h5try!(H5Lget_info(self.id(), name.as_ptr(), &mut info, H5P_DEFAULT));
+            let link_type = LinkType::from(info.type_);
+            if link_type == LinkType::Hard {
+                #[cfg(not(feature = "1.12.0"))]
+                let token = LocationToken(*info.u.address());
+                #[cfg(feature = "1.12.0")]
+                let token = LocationToken(*info.u.token());
+                return Ok(LinkTarget::Hard(token));
+            }
+            let size = *info.u.val_size();
+            let mut buf = vec![0_u8; size];
+            if size > 0 {
+                h5try!(H5Lget_val(
+                    self.id(),
+                    name.as_ptr(),
+                    buf.as_mut_ptr().cast(),
+                    size,
+                    H5P_DEFAULT
+                ));
+            }
+            match link_type {
+                LinkType::Soft => Ok(LinkTarget::Soft(string_from_cstr(buf.as_ptr().cast()))),
+                LinkType::External => {
+                    let (mut flags, mut file, mut path) = (0, ptr::null(), ptr::null());
+                    h5try!(H5Lunpack_elink_val(
+                        buf.as_ptr().cast(),
+                        size,
+                        &mut flags,
+                        &mut file,
+                        &mut path
+                    ));
+                    Ok(LinkTarget::External {
+                        file: string_from_cstr(file),
+                        path: string_from_cstr(path),
+                    })
+                }
+                LinkType::UserDefined(id) => Ok(LinkTarget::UserDefined { id, payload: buf }),
+                LinkType::Hard => unreachable!(),
+            }
+        })
+    }
+
+    /// Returns `true` if the link exists but its target cannot be resolved (e.g. a soft link
+    /// to a removed object or an external link to a missing file).
+    #[cfg(feature = "1.8.5")]
+    pub fn link_is_dangling(&self, name: &str) -> Result<bool> {
+        ensure!(self.link_exists(name), "Link doesn't exist: {:?}", name);
+        let name = to_cstring(name)?;
+        Ok(h5call!(H5Oexists_by_name(self.id(), name.as_ptr(), H5P_DEFAULT))
+            .map_or(true, |x| x <= 0))
+    }
+
+    /// Mounts a file onto this group, so that the root group of the mounted file
+    /// is accessible through this group until it is unmounted.
+    pub fn mount(&self, file: &File) -> Result<()> {
+        h5call!(H5Fmount(self.id(), b".\0".as_ptr().cast(), file.id(), H5P_DEFAULT)).and(Ok(()))
+    }
+
+    /// Mounts a file onto this group using the given file mount properties.
+    pub fn mount_with_fmpl(&self, file: &File, fmpl: &FileMount) -> Result<()> {
+        h5call!(H5Fmount(self.id(), b".\0".as_ptr().cast(), file.id(), fmpl.id())).and(Ok(()))
+    }
+
+    /// Unmounts the file mounted onto this group.
+    pub fn unmount(&self) -> Result<()> {
+        h5call!(H5Funmount(self.id(), b".\0".as_ptr().cast())).and(Ok(()))
+    }
+
+    /// Returns `true` if a file is mounted onto this group.
+    pub fn is_mount_point(&self) -> bool {
+        group_info(self.id()).map(|info| info.mounted > 0).unwrap_or(false)
     }
 
     /// Instantiates a new typed dataset builder.
@@ -212,8 +321,80 @@ impl Group {
 
     /// Opens an existing dataset in the file or group.
     pub fn dataset(&self, name: &str) -> Result<Dataset> {
+        GroupWithLapl { group: self, lapl: None }.dataset(name)
+    }
+
+    /// Creates a new empty table with records of type `T` (see [`crate::table`]).
+    ///
+    /// The records are stored in a resizable dataset chunked by `chunk_size` records.
+    pub fn new_table<T: H5Type>(
+        &self, name: &str, title: &str, chunk_size: usize,
+    ) -> Result<Table> {
+        self.new_table_as(name, &T::type_descriptor(), title, chunk_size)
+    }
+
+    /// Creates a new empty table with records of the given compound datatype.
+    pub fn new_table_as(
+        &self, name: &str, type_desc: &TypeDescriptor, title: &str, chunk_size: usize,
+    ) -> Result<Table> {
+        Table::create(self, name, type_desc, title, chunk_size)
+    }
+
+    /// Opens an existing table in the file or group.
+    pub fn table(&self, name: &str) -> Result<Table> {
+        Table::open(self, name)
+    }
+}
+
+/// Accessors for members of a group which traverse links using custom link access
+/// properties, see [`Group::with_lapl()`].
+#[derive(Clone, Copy, Debug)]
+pub struct GroupWithLapl<'a> {
+    group: &'a Group,
+    lapl: Option<&'a LinkAccess>,
+}
+
+impl GroupWithLapl<'_> {
+    /// Creates an access property list of the given class with the link access properties
+    /// applied, or returns `None` if the defaults are used.
+    fn access_plist(&self, class: hid_t) -> Result<Option<PropertyList>> {
+        self.lapl
+            .map(|lapl| {
+                let plist = PropertyList::from_id(h5try!(H5Pcreate(class)))?;
+                lapl.apply_to(plist.id())?;
+                Ok(plist)
+            })
+            .transpose()
+    }
+
+    /// Opens an existing group, see `Group::group()`.
+    pub fn group(&self, name: &str) -> Result<Group> {
         let name = to_cstring(name)?;
-        Dataset::from_id(h5try!(H5Dopen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
+        h5lock!({
+            let gapl = self.access_plist(*H5P_GROUP_ACCESS)?;
+            let gapl_id = gapl.as_ref().map_or(H5P_DEFAULT, |pl| pl.id());
+            Group::from_id(h5try!(H5Gopen2(self.group.id(), name.as_ptr(), gapl_id)))
+        })
+    }
+
+    /// Opens an existing dataset, see `Group::dataset()`.
+    pub fn dataset(&self, name: &str) -> Result<Dataset> {
+        let name = to_cstring(name)?;
+        h5lock!({
+            let dapl = self.access_plist(*H5P_DATASET_ACCESS)?;
+            let dapl_id = dapl.as_ref().map_or(H5P_DEFAULT, |pl| pl.id());
+            Dataset::from_id(h5try!(H5Dopen2(self.group.id(), name.as_ptr(), dapl_id)))
+        })
+    }
+
+    /// Checks if a link with a given name exists, see `Group::link_exists()`.
+    pub fn link_exists(&self, name: &str) -> bool {
+        (|| -> Result<bool> {
+            let name = to_cstring(name)?;
+            let lapl_id = self.lapl.map_or(H5P_DEFAULT, |lapl| lapl.id());
+            Ok(h5call!(H5Lexists(self.group.id(), name.as_ptr(), lapl_id))? > 0)
+        })()
+        .unwrap_or(false)
     }
 }
 
@@ -266,6 +447,8 @@ pub enum LinkType {
     Hard,
     Soft,
     External,
+    /// User-defined link with the given link class identifier (see `LinkClass`)
+    UserDefined(i32),
 }
 
 impl From<H5L_type_t> for LinkType {
@@ -273,11 +456,25 @@ impl From<H5L_type_t> for LinkType {
         match link_type {
             H5L_type_t::H5L_TYPE_HARD => Self::Hard,
             H5L_type_t::H5L_TYPE_SOFT => Self::Soft,
-            _ => Self::External,
+            H5L_type_t::H5L_TYPE_EXTERNAL => Self::External,
+            H5L_type_t(id) => Self::UserDefined(id),
         }
     }
 }
 
+/// Target of a link, see `Group::link_target()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// Hard link to an object in the same file
+    Hard(LocationToken),
+    /// Soft link to a path in the same file
+    Soft(String),
+    /// External link to a path in another file
+    External { file: String, path: String },
+    /// User-defined link with its link class identifier and payload
+    UserDefined { id: i32, payload: Vec<u8> },
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct LinkInfo {
     pub link_type: LinkType,
@@ -387,6 +584,51 @@ impl Group {
             .map(|vec| vec.into_iter().map(|obj| unsafe { obj.cast_unchecked() }).collect())
     }
 
+    /// Commits the datatype corresponding to `T` to the file, so that it can be stored
+    /// once and shared by multiple datasets and attributes (see `committed_type()` on
+    /// dataset and attribute builders).
+    ///
+    /// If the name is `None`, the datatype is committed anonymously; it is then retained in
+    /// the file only as long as other objects refer to it.
+    pub fn commit_datatype<'n, T: H5Type>(
+        &self, name: impl Into<Maybe<&'n str>>,
+    ) -> Result<Datatype> {
+        self.commit_datatype_desc(name, &T::type_descriptor())
+    }
+
+    /// Commits the datatype corresponding to the type descriptor to the file.
+    ///
+    /// See [`commit_datatype()`](Self::commit_datatype) for details.
+    pub fn commit_datatype_desc<'n>(
+        &self, name: impl Into<Maybe<&'n str>>, desc: &TypeDescriptor,
+    ) -> Result<Datatype> {
+        let name: Option<&str> = name.into().into();
+        h5lock!({
+            let dtype = Datatype::from_descriptor(&desc.to_c_repr())?;
+            if let Some(name) = name {
+                let lcpl = make_lcpl()?;
+                let name = to_cstring(name)?;
+                h5try!(H5Tcommit2(
+                    self.id(),
+                    name.as_ptr(),
+                    dtype.id(),
+                    lcpl.id(),
+                    H5P_DEFAULT,
+                    H5P_DEFAULT
+                ));
+            } else {
+                h5try!(H5Tcommit_anon(self.id(), dtype.id(), H5P_DEFAULT, H5P_DEFAULT));
+            }
+            Ok(dtype)
+        })
+    }
+
+    /// Opens an existing named datatype in the file or group.
+    pub fn named_datatype(&self, name: &str) -> Result<Datatype> {
+        let name = to_cstring(name)?;
+        Datatype::from_id(h5try!(H5Topen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
+    }
+
     /// Returns all named types in the group, non-recursively
     pub fn named_datatypes(&self) -> Result<Vec<Datatype>> {
         self.get_all_of_type(LocationType::NamedDatatype)
@@ -402,6 +644,26 @@ impl Group {
     }
 }
 
+/// A type that can be stored as a group layout of datasets, attributes and subgroups.
+///
+/// This trait is usually implemented via `#[derive(H5Group)]`, where each field is annotated
+/// with `#[hdf5(dataset)]`, `#[hdf5(attr)]` or `#[hdf5(group)]`:
+///
+/// - dataset fields must implement [`DatasetValue`](crate::dataset::DatasetValue) (e.g.
+///   `Vec<T>` or `Array<T, D>`) and may specify `chunk = ...`, `deflate = ...`, `shuffle`
+///   and `lzf` dataset creation options;
+/// - attribute fields must implement [`H5Type`](crate::H5Type) and are stored as scalars;
+/// - group fields must implement `H5Group` themselves.
+///
+/// Fields may be renamed via `rename = "..."`.
+pub trait H5Group: Sized {
+    /// Writes the value into an existing group, creating its members and attributes.
+    fn write_to(&self, group: &Group) -> Result<()>;
+
+    /// Reads the value from a group.
+    fn read_from(group: &Group) -> Result<Self>;
+}
+
 #[cfg(test)]
 pub mod tests {
     use crate::internal_prelude::*;
@@ -647,6 +909,254 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_external_link_lapl() {
+        use crate::plist::file_access::{FileAccessBuilder, FileDriver};
+        use crate::plist::link_access::{ElinkTraverse, LinkAccess};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static TRAVERSED: AtomicUsize = AtomicUsize::new(0);
+
+        fn count_cb(info: &mut ElinkTraverse) -> Result<()> {
+            assert_eq!(info.child_file_name, "foo.h5");
+            assert!(info.child_object_name.ends_with("foo"));
+            TRAVERSED.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn deny_cb(_info: &mut ElinkTraverse) -> Result<()> {
+            fail!("denied")
+        }
+
+        fn core_cb(info: &mut ElinkTraverse) -> Result<()> {
+            FileAccessBuilder::new().core().apply(&mut info.fapl)
+        }
+
+        with_tmp_dir(|dir| {
+            std::fs::create_dir(dir.join("data")).unwrap();
+            let file1 = File::create(dir.join("data").join("foo.h5")).unwrap();
+            file1.create_group("foo").unwrap();
+
+            let file2 = File::create(dir.join("bar.h5")).unwrap();
+            file2.link_external("foo.h5", "foo", "bar").unwrap();
+            file2.new_dataset::<u8>().create("ds").unwrap();
+            file2.link_external("foo.h5", "foo", "baz").unwrap();
+            assert!(file2.group("bar").is_err());
+            assert!(!file2.link_exists("bar/x"));
+
+            let prefix = dir.join("data");
+            let lapl = LinkAccess::build().elink_prefix(prefix.to_str().unwrap()).finish().unwrap();
+            assert!(file2.with_lapl(&lapl).group("bar").is_ok());
+            assert!(!file2.with_lapl(&lapl).link_exists("bar/x"));
+            file1.new_dataset::<i32>().create("foo/x").unwrap().write_scalar(&7).unwrap();
+            assert!(file2.with_lapl(&lapl).link_exists("bar/x"));
+            let ds = file2.with_lapl(&lapl).dataset("bar/x").unwrap();
+            assert_eq!(ds.read_scalar::<i32>().unwrap(), 7);
+            assert!(file2.with_lapl(&lapl).dataset("ds").is_ok());
+
+            let lapl = LinkAccess::build()
+                .elink_prefix(prefix.to_str().unwrap())
+                .elink_cb(count_cb)
+                .finish()
+                .unwrap();
+            file2.with_lapl(&lapl).group("baz").unwrap();
+            assert_eq!(TRAVERSED.load(Ordering::SeqCst), 1);
+
+            let lapl = LinkAccess::build()
+                .elink_prefix(prefix.to_str().unwrap())
+                .elink_cb(deny_cb)
+                .finish()
+                .unwrap();
+            assert!(file2.with_lapl(&lapl).group("baz").is_err());
+
+            // the callback can modify the file access properties of the target file
+            let lapl = LinkAccess::build()
+                .elink_prefix(prefix.to_str().unwrap())
+                .elink_cb(core_cb)
+                .finish()
+                .unwrap();
+            let ds = file2.with_lapl(&lapl).dataset("baz/x").unwrap();
+            assert!(matches!(ds.file().unwrap().fapl().unwrap().driver(), FileDriver::Core(_)));
+
+            let lapl = LinkAccess::build().nlinks(1).finish().unwrap();
+            file2.link_soft("/ds", "s1").unwrap();
+            file2.link_soft("s1", "s2").unwrap();
+            assert!(file2.with_lapl(&lapl).dataset("s1").is_ok());
+            assert!(file2.with_lapl(&lapl).dataset("s2").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_link_target() {
+        with_tmp_dir(|dir| {
+            let file = File::create(dir.join("foo.h5")).unwrap();
+            let group = file.create_group("a/b").unwrap();
+            file.link_hard("a/b", "hard").unwrap();
+            file.link_soft("/a/b", "soft").unwrap();
+            file.link_soft("/a/missing", "dangling").unwrap();
+            file.link_external("bar.h5", "/x/y", "ext").unwrap();
+
+            let token = group.loc_info().unwrap().token;
+            assert_eq!(file.link_target("a/b").unwrap(), LinkTarget::Hard(token));
+            assert_eq!(file.link_target("hard").unwrap(), LinkTarget::Hard(token));
+            assert_eq!(file.link_target("soft").unwrap(), LinkTarget::Soft("/a/b".into()));
+            assert_eq!(
+                file.link_target("dangling").unwrap(),
+                LinkTarget::Soft("/a/missing".into())
+            );
+            assert_eq!(
+                file.link_target("ext").unwrap(),
+                LinkTarget::External { file: "bar.h5".into(), path: "/x/y".into() }
+            );
+            assert!(file.link_target("missing").is_err());
+
+            assert!(!file.link_is_dangling("hard").unwrap());
+            assert!(!file.link_is_dangling("soft").unwrap());
+            assert!(file.link_is_dangling("dangling").unwrap());
+            assert!(file.link_is_dangling("ext").unwrap());
+            assert!(file.link_is_dangling("missing").is_err());
+
+            File::create(dir.join("bar.h5")).unwrap().create_group("x/y").unwrap();
+            assert!(!file.link_is_dangling("ext").unwrap());
+            file.create_group("a/missing").unwrap();
+            assert!(!file.link_is_dangling("dangling").unwrap());
+        })
+    }
+
+    #[test]
+    pub fn test_link_user_defined() {
+        use crate::LinkClass;
+        use hdf5_sys::h5l::H5L_type_t;
+
+        struct PathLink;
+
+        impl LinkClass for PathLink {
+            const ID: u8 = 150;
+
+            fn traverse(group: &Group, _link_name: &str, payload: &[u8]) -> Result<Location> {
+                let path = std::str::from_utf8(payload).map_err(|e| e.to_string())?;
+                Ok((*group.group(path)?).clone())
+            }
+        }
+
+        struct BadLink;
+
+        impl LinkClass for BadLink {
+            const ID: u8 = 10;
+
+            fn traverse(_group: &Group, _link_name: &str, _payload: &[u8]) -> Result<Location> {
+                fail!("unreachable")
+            }
+        }
+
+        with_tmp_file(|file| {
+            file.create_group("a/b").unwrap().create_group("c").unwrap();
+            file.link_user_defined::<PathLink>(b"a/b", "ud").unwrap();
+            assert!(PathLink::is_registered());
+            assert_eq!(file.group("ud").unwrap().member_names().unwrap(), vec!["c"]);
+            file.group("ud/c").unwrap();
+            assert_eq!(
+                file.link_target("ud").unwrap(),
+                LinkTarget::UserDefined { id: 150, payload: b"a/b".to_vec() }
+            );
+
+            let types = file
+                .iter_visit_default(vec![], |_, name, info, types| {
+                    types.push((name.to_owned(), info.link_type));
+                    true
+                })
+                .unwrap();
+            assert!(types.contains(&("ud".to_owned(), LinkType::UserDefined(150))));
+            assert_eq!(LinkType::from(H5L_type_t::H5L_TYPE_EXTERNAL), LinkType::External);
+            assert_eq!(LinkType::from(H5L_type_t(200)), LinkType::UserDefined(200));
+
+            file.link_user_defined::<PathLink>(b"missing", "dangling").unwrap();
+            assert!(file.group("dangling").is_err());
+            assert!(file.link_user_defined::<BadLink>(b"", "bad").is_err());
+
+            PathLink::unregister().unwrap();
+            assert!(!PathLink::is_registered());
+            assert!(file.group("ud").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_mount() {
+        with_tmp_dir(|dir| {
+            let parent = File::create(dir.join("parent.h5")).unwrap();
+            parent.new_dataset::<u8>().create("a").unwrap();
+            let mnt = parent.create_group("mnt").unwrap();
+            let child = File::create(dir.join("child.h5")).unwrap();
+            child.new_dataset::<i32>().create("x").unwrap().write_scalar(&42).unwrap();
+
+            assert!(!mnt.is_mount_point());
+            mnt.mount(&child).unwrap();
+            assert!(mnt.is_mount_point());
+            assert!(mnt.mount(&child).is_err());
+
+            let ds = parent.dataset("mnt/x").unwrap();
+            assert_eq!(ds.read_scalar::<i32>().unwrap(), 42);
+            assert_eq!(ds.name(), "/mnt/x");
+            assert_eq!(ds.filename(), child.filename());
+            assert_eq!(ds.file().unwrap().filename(), child.filename());
+            assert_eq!(parent.group("mnt").unwrap().filename(), child.filename());
+            assert_eq!(parent.dataset("a").unwrap().file().unwrap().filename(), parent.filename());
+            assert_eq!(mnt.filename(), parent.filename());
+
+            mnt.unmount().unwrap();
+            assert!(!mnt.is_mount_point());
+            assert!(parent.dataset("mnt/x").is_err());
+            assert!(mnt.unmount().is_err());
+            assert_eq!(ds.read_scalar::<i32>().unwrap(), 42);
+
+            let fmpl = crate::plist::FileMount::try_new().unwrap();
+            mnt.mount_with_fmpl(&child, &fmpl).unwrap();
+            assert!(parent.link_exists("mnt/x"));
+            mnt.unmount().unwrap();
+        })
+    }
+
+    #[test]
+    pub fn test_commit_datatype() {
+        use crate::types::{FloatSize, TypeDescriptor};
+
+        with_tmp_file(|file| {
+            let dtype = file.commit_datatype::<(i32, f64)>("types/pair").unwrap();
+            assert!(dtype.is_committed());
+            assert!(!Datatype::from_type::<(i32, f64)>().unwrap().is_committed());
+            assert!(file.commit_datatype::<(i32, f64)>("types/pair").is_err());
+            let types = file.group("types").unwrap();
+            assert_eq!(types.named_datatypes().unwrap().len(), 1);
+            assert_eq!(types.named_datatype("pair").unwrap(), dtype);
+
+            let ds1 = file.new_dataset::<(i32, f64)>().committed_type(&dtype).create("a").unwrap();
+            let ds2 = file
+                .new_dataset_builder()
+                .committed_type(&dtype)
+                .with_data(&[(1, 2.0), (3, 4.0)])
+                .create("b")
+                .unwrap();
+            for ds in [&ds1, &ds2] {
+                let ds_dtype = ds.dtype().unwrap();
+                assert!(ds_dtype.is_committed());
+                assert_eq!(ds_dtype, dtype);
+            }
+            assert_eq!(ds2.read_raw::<(i32, f64)>().unwrap(), vec![(1, 2.0), (3, 4.0)]);
+
+            let attr = ds1.new_attr::<(i32, f64)>().committed_type(&dtype).create("attr").unwrap();
+            assert!(attr.dtype().unwrap().is_committed());
+
+            let anon =
+                file.commit_datatype_desc(None, &TypeDescriptor::Float(FloatSize::U4)).unwrap();
+            assert!(anon.is_committed());
+            assert_eq!(file.named_datatypes().unwrap().len(), 0);
+            let ds3 = file.new_dataset::<f64>().committed_type(&anon).shape(3).create("c").unwrap();
+            ds3.write(&[1.0, 2.0, 3.0]).unwrap();
+            assert_eq!(ds3.dtype().unwrap().size(), 4);
+            assert_eq!(ds3.read_raw::<f64>().unwrap(), vec![1.0, 2.0, 3.0]);
+        })
+    }
+
     #[test]
     pub fn test_iterators() {
         with_tmp_file(|file| {
@@ -0,0 +1,168 @@
+//! User-defined link classes.
+
+use std::mem;
+use std::panic;
+use std::ptr;
+use std::slice;
+
+use hdf5_sys::h5l::{
+    H5L_class_t, H5L_type_t, H5Lcreate_ud, H5Lis_registered, H5Lregister, H5Lunregister,
+    H5L_LINK_CLASS_T_VERS,
+};
+use libc::ssize_t;
+
+use crate::internal_prelude::*;
+
+/// Smallest identifier that can be used for a user-defined link class.
+pub const LINK_CLASS_ID_MIN: u8 = 65;
+
+/// A user-defined link class.
+///
+/// Links of this class store an arbitrary byte payload and are resolved into objects
+/// by the `traverse` callback each time they are followed.
+///
+/// # Example
+///
+/// ```no_run
+/// # fn main() -> hdf5::Result<()> {
+/// use hdf5::{Group, LinkClass, Location};
+///
+/// /// Links pointing to a path within the same file.
+/// struct PathLink;
+///
+/// impl LinkClass for PathLink {
+///     const ID: u8 = 100;
+///
+///     fn traverse(group: &Group, _link_name: &str, payload: &[u8]) -> hdf5::Result<Location> {
+///         let path = std::str::from_utf8(payload).map_err(|e| e.to_string())?;
+///         Ok((*group.file()?.group(path)?).clone())
+///     }
+/// }
+///
+/// let file = hdf5::File::create("foo.h5")?;
+/// file.create_group("data")?;
+/// file.link_user_defined::<PathLink>(b"/data", "link")?;
+/// let group = file.group("link")?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait LinkClass {
+    /// Link class identifier, must be in the range `65..=255`.
+    const ID: u8;
+
+    /// Resolves a link into an object.
+    ///
+    /// `group` is the group containing the link and `payload` is the data stored in the link.
+    fn traverse(group: &Group, link_name: &str, payload: &[u8]) -> Result<Location>;
+
+    /// Registers the link class with the library (does nothing if it is already registered).
+    fn register() -> Result<()>
+    where
+        Self: Sized,
+    {
+        ensure!(
+            Self::ID >= LINK_CLASS_ID_MIN,
+            "Invalid user-defined link class id: {} (expected {}..=255)",
+            Self::ID,
+            LINK_CLASS_ID_MIN
+        );
+        h5lock!({
+            if !is_registered(Self::ID)? {
+                let cls = H5L_class_t {
+                    version: H5L_LINK_CLASS_T_VERS as _,
+                    id: link_type(Self::ID),
+                    comment: ptr::null(),
+                    create_func: None,
+                    move_func: None,
+                    copy_func: None,
+                    trav_func: Some(traverse::<Self>),
+                    del_func: None,
+                    query_func: Some(query),
+                };
+                h5try!(H5Lregister(&cls));
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns `true` if a link class with this identifier is currently registered.
+    fn is_registered() -> bool
+    where
+        Self: Sized,
+    {
+        is_registered(Self::ID).unwrap_or(false)
+    }
+
+    /// Unregisters the link class; links of this class cannot be traversed afterwards.
+    fn unregister() -> Result<()>
+    where
+        Self: Sized,
+    {
+        h5call!(H5Lunregister(link_type(Self::ID))).and(Ok(()))
+    }
+}
+
+fn link_type(id: u8) -> H5L_type_t {
+    H5L_type_t(c_int::from(id))
+}
+
+fn is_registered(id: u8) -> Result<bool> {
+    Ok(h5call!(H5Lis_registered(link_type(id)))? > 0)
+}
+
+pub(crate) fn create_user_defined(
+    loc_id: hid_t, name: *const c_char, id: u8, payload: &[u8], lcpl_id: hid_t,
+) -> Result<()> {
+    h5call!(H5Lcreate_ud(
+        loc_id,
+        name,
+        link_type(id),
+        payload.as_ptr().cast(),
+        payload.len(),
+        lcpl_id,
+        H5P_DEFAULT
+    ))
+    .and(Ok(()))
+}
+
+unsafe fn payload<'a>(lnkdata: *const c_void, lnkdata_size: size_t) -> &'a [u8] {
+    if lnkdata.is_null() || lnkdata_size == 0 {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(lnkdata.cast(), lnkdata_size) }
+    }
+}
+
+extern "C" fn traverse<L: LinkClass>(
+    link_name: *const c_char, cur_group: hid_t, lnkdata: *const c_void, lnkdata_size: size_t,
+    _lapl_id: hid_t,
+) -> hid_t {
+    panic::catch_unwind(|| {
+        let handle = Handle::try_borrow(cur_group).expect("traverse: unable to create a handle");
+        let group = Group::from_handle(handle);
+        let link_name = unsafe { string_from_cstr(link_name) };
+        let payload = unsafe { payload(lnkdata, lnkdata_size) };
+        match L::traverse(&group, &link_name, payload) {
+            Ok(loc) => {
+                // the library takes ownership of the returned identifier
+                let id = loc.id();
+                mem::forget(loc);
+                id
+            }
+            Err(_) => H5I_INVALID_HID,
+        }
+    })
+    .unwrap_or(H5I_INVALID_HID)
+}
+
+extern "C" fn query(
+    _link_name: *const c_char, lnkdata: *const c_void, lnkdata_size: size_t, buf: *mut c_void,
+    buf_size: size_t,
+) -> ssize_t {
+    let size = buf_size.min(lnkdata_size);
+    // the link data may be null if it's empty, which is not allowed even for empty copies
+    if !buf.is_null() && !lnkdata.is_null() && size != 0 {
+        unsafe { ptr::copy_nonoverlapping(lnkdata.cast::<u8>(), buf.cast::<u8>(), size) };
+    }
+    lnkdata_size as _
+}
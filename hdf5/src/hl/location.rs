@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::{self, Debug};
 use std::mem::MaybeUninit;
 use std::ops::Deref;
@@ -18,15 +19,17 @@ use hdf5_sys::h5o::{H5O_INFO_BASIC, H5O_INFO_NUM_ATTRS, H5O_INFO_TIME};
 #[cfg(not(feature = "1.12.0"))]
 use hdf5_sys::{h5::haddr_t, h5o::H5O_info1_t, h5o::H5Oopen_by_addr};
 use hdf5_sys::{
-    h5a::H5Aopen,
+    h5a::{H5Adelete, H5Aexists, H5Aopen, H5Arename},
     h5f::H5Fget_name,
     h5i::{H5Iget_file_id, H5Iget_name},
     h5o::{H5O_type_t, H5Oget_comment},
 };
+use ndarray::ArrayView;
 
 use crate::internal_prelude::*;
 
-use super::attribute::AttributeBuilderEmpty;
+use super::attribute::{AttrValue, AttributeBuilderEmpty, AttributeIter};
+use super::group::{IterationOrder, TraversalOrder};
 
 /// Named location (file, group, dataset, named datatype).
 #[repr(transparent)]
@@ -74,12 +77,16 @@ impl Location {
     }
 
     /// Returns the name of the file containing the named object (or the file itself).
+    ///
+    /// For objects accessed through a mount point, this is the name of the mounted file.
     pub fn filename(&self) -> String {
         // TODO: should this return Result<String> or an empty string if it fails?
         h5lock!(get_h5_str(|m, s| H5Fget_name(self.id(), m, s)).unwrap_or_else(|_| String::new()))
     }
 
     /// Returns a handle to the file containing the named object (or the file itself).
+    ///
+    /// For objects accessed through a mount point, this is the mounted file.
     pub fn file(&self) -> Result<File> {
         File::from_id(h5try!(H5Iget_file_id(self.id())))
     }
@@ -125,6 +132,105 @@ impl Location {
         Attribute::attr_names(self)
     }
 
+    /// Check if an attribute with a given name is attached to this object.
+    pub fn attr_exists(&self, name: &str) -> bool {
+        (|| -> Result<bool> {
+            let name = to_cstring(name)?;
+            Ok(h5call!(H5Aexists(self.id(), name.as_ptr()))? > 0)
+        })()
+        .unwrap_or(false)
+    }
+
+    /// Renames an attribute attached to this object.
+    pub fn rename_attr(&self, old_name: &str, new_name: &str) -> Result<()> {
+        let old_name = to_cstring(old_name)?;
+        let new_name = to_cstring(new_name)?;
+        h5call!(H5Arename(self.id(), old_name.as_ptr(), new_name.as_ptr())).and(Ok(()))
+    }
+
+    /// Removes an attribute attached to this object.
+    pub fn delete_attr(&self, name: &str) -> Result<()> {
+        let name = to_cstring(name)?;
+        h5call!(H5Adelete(self.id(), name.as_ptr())).and(Ok(()))
+    }
+
+    /// Returns an iterator over the attributes attached to this object, ordered by name.
+    pub fn attrs(&self) -> Result<AttributeIter> {
+        self.attrs_by(TraversalOrder::Name, IterationOrder::Increasing)
+    }
+
+    /// Returns an iterator over the attributes attached to this object in a given order.
+    ///
+    /// Traversing in creation order requires attribute creation order to be tracked
+    /// and indexed on the object (see e.g. `DatasetBuilder::attr_creation_order`).
+    pub fn attrs_by(
+        &self, traversal_order: TraversalOrder, iteration_order: IterationOrder,
+    ) -> Result<AttributeIter> {
+        AttributeIter::new(self, traversal_order, iteration_order)
+    }
+
+    /// Writes an attribute, creating it if it doesn't exist yet.
+    ///
+    /// If an attribute with this name already exists but its datatype or shape differs
+    /// from the data being written, it is deleted and re-created.
+    pub fn set_attr<'d, A, T, D>(&self, name: &str, value: A) -> Result<Attribute>
+    where
+        A: Into<ArrayView<'d, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        let view = value.into();
+        h5lock!({
+            if self.attr_exists(name) {
+                let attr = self.attr(name)?;
+                let dtype = Datatype::from_descriptor(&T::type_descriptor().to_c_repr())?;
+                if attr.dtype()? == dtype && attr.shape() == view.shape() {
+                    attr.write(view.view())?;
+                    return Ok(attr);
+                }
+                drop(attr);
+                self.delete_attr(name)?;
+            }
+            self.new_attr_builder().with_data(view).create(name)
+        })
+    }
+
+    /// Reads all attributes attached to this object into a map keyed by attribute name.
+    ///
+    /// The values are dynamically typed, with types derived from the stored datatypes.
+    pub fn read_attrs(&self) -> Result<BTreeMap<String, AttrValue>> {
+        self.attrs()?
+            .map(|attr| {
+                let attr = attr?;
+                Ok((attr.name(), attr.read_value()?))
+            })
+            .collect()
+    }
+
+    /// Writes all attributes from the map, replacing any existing attributes with the same names.
+    ///
+    /// Array values without elements are rejected since their datatype cannot be inferred; use
+    /// `AttrValue::Empty` for empty attributes instead.
+    pub fn write_attrs(&self, attrs: &BTreeMap<String, AttrValue>) -> Result<()> {
+        for (name, value) in attrs {
+            let desc = value
+                .type_descriptor()
+                .ok_or_else(|| format!("cannot infer datatype of empty attribute: {:?}", name))?;
+            h5lock!({
+                if self.attr_exists(name) {
+                    self.delete_attr(name)?;
+                }
+                let attr = self
+                    .new_attr_builder()
+                    .empty_as(desc)
+                    .shape(value.extents())
+                    .create(name.as_str())?;
+                attr.write_value(value)
+            })?;
+        }
+        Ok(())
+    }
+
     pub fn loc_info(&self) -> Result<LocationInfo> {
         H5O_get_info(self.id(), true)
     }
@@ -150,8 +256,8 @@ impl Location {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct LocationToken(
-    #[cfg(not(feature = "1.12.0"))] haddr_t,
-    #[cfg(feature = "1.12.0")] H5O_token_t,
+    #[cfg(not(feature = "1.12.0"))] pub(crate) haddr_t,
+    #[cfg(feature = "1.12.0")] pub(crate) H5O_token_t,
 );
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
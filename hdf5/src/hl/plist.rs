@@ -16,6 +16,8 @@ pub mod dataset_access;
 pub mod dataset_create;
 pub mod file_access;
 pub mod file_create;
+pub mod file_mount;
+pub mod link_access;
 pub mod link_create;
 
 /// Represents the HDF5 property list.
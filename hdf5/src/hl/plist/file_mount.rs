@@ -0,0 +1,108 @@
+//! File mount properties.
+
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+
+use hdf5_sys::h5p::H5Pcreate;
+
+use crate::globals::H5P_FILE_MOUNT;
+use crate::internal_prelude::*;
+
+/// File mount properties.
+#[repr(transparent)]
+pub struct FileMount(Handle);
+
+impl ObjectClass for FileMount {
+    const NAME: &'static str = "file mount property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.is_class(PropertyListClass::FileMount),
+            "expected file mount property list, got {:?}",
+            self.class()
+        );
+        Ok(())
+    }
+}
+
+impl Debug for FileMount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FileMount").finish()
+    }
+}
+
+impl Deref for FileMount {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for FileMount {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for FileMount {}
+
+impl Clone for FileMount {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast_unchecked() }
+    }
+}
+
+/// Builder used to create file mount property list.
+///
+/// There are currently no settable file mount properties in the library; the builder exists
+/// for consistency with other property lists.
+#[derive(Clone, Debug, Default)]
+pub struct FileMountBuilder {}
+
+impl FileMountBuilder {
+    /// Creates a new file mount property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(_plist: &FileMount) -> Result<Self> {
+        Ok(Self::default())
+    }
+
+    pub fn apply(&self, _plist: &mut FileMount) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn finish(&self) -> Result<FileMount> {
+        h5lock!({
+            let mut plist = FileMount::try_new()?;
+            self.apply(&mut plist).map(|()| plist)
+        })
+    }
+}
+
+/// File mount property list.
+impl FileMount {
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(*H5P_FILE_MOUNT)))
+    }
+
+    pub fn copy(&self) -> Self {
+        unsafe { self.deref().copy().cast_unchecked() }
+    }
+
+    pub fn build() -> FileMountBuilder {
+        FileMountBuilder::new()
+    }
+}
@@ -0,0 +1,288 @@
+//! Link access properties.
+
+use std::fmt::{self, Debug};
+use std::mem;
+use std::ops::Deref;
+use std::panic;
+use std::ptr;
+
+use hdf5_sys::h5l::H5L_elink_traverse_t;
+use hdf5_sys::h5p::{
+    H5Pcreate, H5Pget_elink_cb, H5Pget_elink_fapl, H5Pget_elink_prefix, H5Pget_nlinks,
+    H5Pset_elink_cb, H5Pset_elink_fapl, H5Pset_elink_prefix, H5Pset_nlinks,
+};
+
+use crate::globals::H5P_LINK_ACCESS;
+use crate::internal_prelude::*;
+use crate::plist::FileAccess;
+
+/// Link access properties.
+#[repr(transparent)]
+pub struct LinkAccess(Handle);
+
+impl ObjectClass for LinkAccess {
+    const NAME: &'static str = "link access property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.is_class(PropertyListClass::LinkAccess),
+            "expected link access property list, got {:?}",
+            self.class()
+        );
+        Ok(())
+    }
+}
+
+impl Debug for LinkAccess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut formatter = f.debug_struct("LinkAccess");
+        formatter.field("nlinks", &self.nlinks());
+        formatter.field("elink_prefix", &self.elink_prefix());
+        formatter.field("elink_fapl", &self.elink_fapl());
+        formatter.field("elink_cb", &self.elink_cb().is_some());
+        formatter.finish()
+    }
+}
+
+impl Deref for LinkAccess {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for LinkAccess {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for LinkAccess {}
+
+impl Clone for LinkAccess {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast_unchecked() }
+    }
+}
+
+/// Information about an external link being traversed, passed to [`ElinkCallback`].
+#[derive(Debug)]
+pub struct ElinkTraverse {
+    /// Name of the file containing the external link
+    pub parent_file_name: String,
+    /// Name of the group containing the external link
+    pub parent_group_name: String,
+    /// Name of the target file
+    pub child_file_name: String,
+    /// Path of the target object within the target file
+    pub child_object_name: String,
+    /// File access flags (`H5F_ACC_*`) that will be used to open the target file
+    pub acc_flags: u32,
+    /// File access properties that will be used to open the target file
+    pub fapl: FileAccess,
+}
+
+/// Callback invoked before an external link is traversed.
+///
+/// The callback may change the access flags of the target file or modify its file access
+/// properties in place (e.g. via `FileAccessBuilder::apply(&mut info.fapl)`); returning an
+/// error aborts the traversal.
+pub type ElinkCallback = fn(&mut ElinkTraverse) -> Result<()>;
+
+extern "C" fn elink_traverse(
+    parent_file_name: *const c_char, parent_group_name: *const c_char,
+    child_file_name: *const c_char, child_object_name: *const c_char, acc_flags: *mut c_uint,
+    fapl_id: hid_t, op_data: *mut c_void,
+) -> herr_t {
+    panic::catch_unwind(|| {
+        let callback: ElinkCallback = unsafe { mem::transmute(op_data) };
+        let acc_flags = unsafe { acc_flags.as_mut().expect("elink_cb: null acc_flags ptr") };
+        let fapl = Handle::try_borrow(fapl_id).expect("elink_cb: unable to create a handle");
+        let mut info = unsafe {
+            ElinkTraverse {
+                parent_file_name: string_from_cstr(parent_file_name),
+                parent_group_name: string_from_cstr(parent_group_name),
+                child_file_name: string_from_cstr(child_file_name),
+                child_object_name: string_from_cstr(child_object_name),
+                acc_flags: *acc_flags,
+                fapl: FileAccess::from_handle(fapl),
+            }
+        };
+        let ret = callback(&mut info);
+        *acc_flags = info.acc_flags;
+        if ret.is_ok() {
+            0
+        } else {
+            -1
+        }
+    })
+    .unwrap_or(-1)
+}
+
+/// Builder used to create link access property list.
+#[derive(Clone, Debug, Default)]
+pub struct LinkAccessBuilder {
+    nlinks: Option<usize>,
+    elink_prefix: Option<String>,
+    elink_fapl: Option<FileAccess>,
+    elink_cb: Option<ElinkCallback>,
+}
+
+impl LinkAccessBuilder {
+    /// Creates a new link access property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &LinkAccess) -> Result<Self> {
+        let mut builder = Self::default();
+        builder.nlinks(plist.get_nlinks()?);
+        builder.elink_prefix(&plist.get_elink_prefix()?);
+        if let Some(fapl) = plist.get_elink_fapl()? {
+            builder.elink_fapl(&fapl);
+        }
+        if let Some(cb) = plist.get_elink_cb()? {
+            builder.elink_cb(cb);
+        }
+        Ok(builder)
+    }
+
+    /// Sets the maximum number of soft or user-defined links that can be traversed
+    /// before assuming a cycle (16 by default).
+    pub fn nlinks(&mut self, nlinks: usize) -> &mut Self {
+        self.nlinks = Some(nlinks);
+        self
+    }
+
+    /// Sets the prefix prepended to the file names of external link targets.
+    ///
+    /// If the prefix starts with `${HDF5_EXT_PREFIX}`, it is replaced with the value of the
+    /// corresponding environment variable.
+    pub fn elink_prefix(&mut self, prefix: &str) -> &mut Self {
+        self.elink_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets the file access properties used to open external link targets.
+    pub fn elink_fapl(&mut self, fapl: &FileAccess) -> &mut Self {
+        self.elink_fapl = Some(fapl.clone());
+        self
+    }
+
+    /// Sets the callback invoked before traversing an external link.
+    pub fn elink_cb(&mut self, cb: ElinkCallback) -> &mut Self {
+        self.elink_cb = Some(cb);
+        self
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(v) = self.nlinks {
+            h5try!(H5Pset_nlinks(id, v as _));
+        }
+        if let Some(ref v) = self.elink_prefix {
+            let v = to_cstring(v.as_ref())?;
+            h5try!(H5Pset_elink_prefix(id, v.as_ptr()));
+        }
+        if let Some(ref v) = self.elink_fapl {
+            h5try!(H5Pset_elink_fapl(id, v.id()));
+        }
+        if let Some(cb) = self.elink_cb {
+            h5try!(H5Pset_elink_cb(id, Some(elink_traverse), cb as *mut c_void));
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, plist: &mut LinkAccess) -> Result<()> {
+        h5lock!(self.populate_plist(plist.id()))
+    }
+
+    pub fn finish(&self) -> Result<LinkAccess> {
+        h5lock!({
+            let mut plist = LinkAccess::try_new()?;
+            self.apply(&mut plist).map(|()| plist)
+        })
+    }
+}
+
+/// Link access property list.
+impl LinkAccess {
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(*H5P_LINK_ACCESS)))
+    }
+
+    pub fn copy(&self) -> Self {
+        unsafe { self.deref().copy().cast_unchecked() }
+    }
+
+    pub fn build() -> LinkAccessBuilder {
+        LinkAccessBuilder::new()
+    }
+
+    /// Copies the link access properties into a property list of a derived class
+    /// (e.g. group or dataset access property list).
+    pub(crate) fn apply_to(&self, id: hid_t) -> Result<()> {
+        h5lock!(LinkAccessBuilder::from_plist(self)?.populate_plist(id))
+    }
+
+    #[doc(hidden)]
+    pub fn get_nlinks(&self) -> Result<usize> {
+        h5get!(H5Pget_nlinks(self.id()): size_t).map(|x| x as _)
+    }
+
+    pub fn nlinks(&self) -> usize {
+        self.get_nlinks().unwrap_or(16)
+    }
+
+    #[doc(hidden)]
+    pub fn get_elink_prefix(&self) -> Result<String> {
+        h5lock!(get_h5_str(|m, s| H5Pget_elink_prefix(self.id(), m, s)))
+    }
+
+    pub fn elink_prefix(&self) -> String {
+        self.get_elink_prefix().ok().unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    pub fn get_elink_fapl(&self) -> Result<Option<FileAccess>> {
+        let fapl_id = h5try!(H5Pget_elink_fapl(self.id()));
+        if fapl_id == H5P_DEFAULT {
+            Ok(None)
+        } else {
+            FileAccess::from_id(fapl_id).map(Some)
+        }
+    }
+
+    pub fn elink_fapl(&self) -> Option<FileAccess> {
+        self.get_elink_fapl().ok().flatten()
+    }
+
+    #[doc(hidden)]
+    pub fn get_elink_cb(&self) -> Result<Option<ElinkCallback>> {
+        let mut func: H5L_elink_traverse_t = None;
+        let mut op_data: *mut c_void = ptr::null_mut();
+        h5try!(H5Pget_elink_cb(self.id(), &mut func, &mut op_data));
+        // only callbacks set via the builder can be recovered
+        let ours: H5L_elink_traverse_t = Some(elink_traverse);
+        let is_ours = func.map(|f| f as usize) == ours.map(|f| f as usize);
+        if is_ours && !op_data.is_null() {
+            Ok(Some(unsafe { mem::transmute::<*mut c_void, ElinkCallback>(op_data) }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn elink_cb(&self) -> Option<ElinkCallback> {
+        self.get_elink_cb().ok().flatten()
+    }
+}
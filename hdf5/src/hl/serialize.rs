@@ -0,0 +1,59 @@
+//! Mapping of Rust values onto HDF5 groups via `serde`.
+//!
+//! Structs and maps are stored as groups, with their fields mapped as follows:
+//!
+//! - nested structs and maps become subgroups;
+//! - sequences of numbers, booleans, strings or unit enum variants (including nested
+//!   sequences of equal length) become datasets, as do `ndarray` arrays serialized via
+//!   `#[serde(with = "hdf5::serialize::array")]`;
+//! - other sequences (including empty ones, whose element type is unknown) become groups
+//!   with members named `"0"`, `"1"`, etc.;
+//! - scalars and strings become attributes or scalar datasets (see [`Placement`]);
+//! - unit enum variants are stored using HDF5 enum datatypes, other enum variants
+//!   become groups with a single member named after the variant;
+//! - `None` and unit values are skipped.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal_prelude::*;
+
+pub mod array;
+mod de;
+mod ser;
+
+pub use self::de::Deserializer;
+pub use self::ser::Serializer;
+
+/// Where scalar values and strings are stored when serializing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// Store scalars as attributes of the parent group (default).
+    #[default]
+    Attribute,
+    /// Store scalars as scalar datasets within the parent group.
+    Dataset,
+}
+
+/// Serializes a struct or a map into an existing group.
+pub fn to_group<T: Serialize + ?Sized>(value: &T, group: &Group) -> Result<()> {
+    value.serialize(Serializer::new(group))
+}
+
+/// Deserializes a struct or a map from a group.
+pub fn from_group<T: for<'de> Deserialize<'de>>(group: &Group) -> Result<T> {
+    T::deserialize(Deserializer::new(group))
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        msg.to_string().into()
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        msg.to_string().into()
+    }
+}
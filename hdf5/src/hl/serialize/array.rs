@@ -0,0 +1,96 @@
+//! Serialization of `ndarray` arrays as multi-dimensional datasets.
+//!
+//! To be used via `#[serde(with = "hdf5::serialize::array")]` on fields of types such as
+//! `Array2<T>` or `ArrayD<T>`; with serializers other than the ones from this crate,
+//! arrays are represented as structs with `shape` and `data` fields.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use ndarray::{Array, ArrayBase, Data, Dimension, IxDyn};
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Struct name used to tell arrays apart from other structs.
+pub(super) const ARRAY_NAME: &str = "$hdf5::serialize::array";
+
+/// Struct fields: shape of the array and its elements in logical order.
+pub(super) const ARRAY_FIELDS: &[&str] = &["shape", "data"];
+
+struct Elements<'a, S: Data, D>(&'a ArrayBase<S, D>);
+
+impl<A, S, D> Serialize for Elements<'_, S, D>
+where
+    A: Serialize,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+/// Serializes an array along with its shape.
+pub fn serialize<A, S, D, Ser>(
+    array: &ArrayBase<S, D>, serializer: Ser,
+) -> Result<Ser::Ok, Ser::Error>
+where
+    A: Serialize,
+    S: Data<Elem = A>,
+    D: Dimension,
+    Ser: Serializer,
+{
+    let mut state = serializer.serialize_struct(ARRAY_NAME, ARRAY_FIELDS.len())?;
+    state.serialize_field(ARRAY_FIELDS[0], array.shape())?;
+    state.serialize_field(ARRAY_FIELDS[1], &Elements(array))?;
+    state.end()
+}
+
+/// Deserializes an array, checking that its shape matches the expected dimensionality.
+pub fn deserialize<'de, A, D, De>(deserializer: De) -> Result<Array<A, D>, De::Error>
+where
+    A: Deserialize<'de>,
+    D: Dimension,
+    De: Deserializer<'de>,
+{
+    deserializer.deserialize_struct(ARRAY_NAME, ARRAY_FIELDS, ArrayVisitor(PhantomData))
+}
+
+struct ArrayVisitor<A, D>(PhantomData<(A, D)>);
+
+impl<A, D: Dimension> ArrayVisitor<A, D> {
+    fn build<E: de::Error>(shape: Vec<usize>, data: Vec<A>) -> Result<Array<A, D>, E> {
+        Array::from_shape_vec(IxDyn(&shape), data)
+            .and_then(|array| array.into_dimensionality())
+            .map_err(|err| E::custom(format!("invalid array of shape {:?}: {}", shape, err)))
+    }
+}
+
+impl<'de, A: Deserialize<'de>, D: Dimension> Visitor<'de> for ArrayVisitor<A, D> {
+    type Value = Array<A, D>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array with its shape")
+    }
+
+    fn visit_seq<V: SeqAccess<'de>>(self, mut seq: V) -> Result<Self::Value, V::Error> {
+        let shape = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let data = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Self::build(shape, data)
+    }
+
+    fn visit_map<V: MapAccess<'de>>(self, mut map: V) -> Result<Self::Value, V::Error> {
+        let (mut shape, mut data) = (None, None);
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "shape" => shape = Some(map.next_value()?),
+                "data" => data = Some(map.next_value()?),
+                _ => return Err(de::Error::unknown_field(&key, ARRAY_FIELDS)),
+            }
+        }
+        let shape = shape.ok_or_else(|| de::Error::missing_field("shape"))?;
+        let data = data.ok_or_else(|| de::Error::missing_field("data"))?;
+        Self::build(shape, data)
+    }
+}
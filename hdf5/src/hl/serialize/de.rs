@@ -0,0 +1,553 @@
+use hdf5_types::dyn_value::{DynFloat, DynInteger, DynScalar, DynValue, OwnedDynValue};
+use ndarray::{ArrayD, ArrayViewD, IxDyn};
+use serde::de::{
+    self,
+    value::{StrDeserializer, UnitDeserializer},
+    DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+use super::array::ARRAY_NAME;
+use crate::internal_prelude::*;
+
+/// Deserializer of structs and maps from an HDF5 group.
+///
+/// Attributes and members of the group are used as struct fields or map entries; see
+/// the [module-level documentation](super) for details on how values are mapped.
+#[derive(Clone, Debug)]
+pub struct Deserializer {
+    group: Group,
+}
+
+impl Deserializer {
+    /// Creates a new deserializer reading from an existing group.
+    pub fn new(group: &Group) -> Self {
+        Self { group: group.clone() }
+    }
+
+    /// Returns names of all attributes and members of the group.
+    fn names(&self) -> Result<Vec<String>> {
+        let mut names = self.group.attr_names()?;
+        names.extend(self.group.member_names()?);
+        Ok(names)
+    }
+
+    /// Returns names of the group members sorted as sequence indices.
+    fn indices(&self) -> Result<Vec<String>> {
+        let mut indices = Vec::new();
+        for name in self.names()? {
+            match name.parse::<usize>() {
+                Ok(index) => indices.push((index, name)),
+                Err(_) => fail!("expected a sequence, found member {:?}", name),
+            }
+        }
+        indices.sort_unstable();
+        Ok(indices.into_iter().map(|(_, name)| name).collect())
+    }
+
+    fn entry(&self, name: &str) -> Result<Entry> {
+        if self.group.attr_names()?.iter().any(|attr| attr == name) {
+            return Ok(Entry::Value(self.group.attr(name)?.read_value()?));
+        }
+        match self.group.loc_type_by_name(name)? {
+            LocationType::Group => Ok(Entry::Group(Self::new(&self.group.group(name)?))),
+            LocationType::Dataset => Ok(Entry::Value(self.group.dataset(name)?.read_dyn_value()?)),
+            loc_type => fail!("unexpected {:?} object: {:?}", loc_type, name),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let names = self.names()?;
+        visitor.visit_map(GroupAccess { group: self, names: names.into_iter(), name: None })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self, _name: &'static str, _fields: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let names = self.indices()?;
+        visitor.visit_seq(GroupAccess { group: self, names: names.into_iter(), name: None })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self, _name: &'static str, _len: usize, visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self, _name: &'static str, _variants: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value> {
+        match self.names()?.as_slice() {
+            [variant] => visitor.visit_enum(GroupVariant { group: self, variant: variant.clone() }),
+            _ => fail!("expected a group with a single member named after the enum variant"),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self, _name: &'static str, visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self, _name: &'static str, visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf identifier ignored_any
+    }
+}
+
+/// Access to attributes and members of a group, either as a map or as a sequence.
+struct GroupAccess {
+    group: Deserializer,
+    names: std::vec::IntoIter<String>,
+    name: Option<String>,
+}
+
+impl<'de> MapAccess<'de> for GroupAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.names.next() {
+            Some(name) => {
+                let key = seed.deserialize(KeyDeserializer(&name))?;
+                self.name = Some(name);
+                Ok(Some(key))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let name = self.name.take().ok_or("map value deserialized before its key")?;
+        seed.deserialize(self.group.entry(&name)?)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.names.len())
+    }
+}
+
+impl<'de> SeqAccess<'de> for GroupAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.names.next() {
+            Some(name) => seed.deserialize(self.group.entry(&name)?).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.names.len())
+    }
+}
+
+/// Non-unit enum variant stored as a group with a single member.
+struct GroupVariant {
+    group: Deserializer,
+    variant: String,
+}
+
+impl<'de> EnumAccess<'de> for GroupVariant {
+    type Error = Error;
+    type Variant = Entry;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Entry)> {
+        let variant = seed.deserialize(KeyDeserializer(&self.variant))?;
+        Ok((variant, self.group.entry(&self.variant)?))
+    }
+}
+
+/// A single attribute or group member.
+enum Entry {
+    Value(AttrValue),
+    Group(Deserializer),
+}
+
+impl<'de> VariantAccess<'de> for Entry {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self, fields: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
+
+macro_rules! forward_entry {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {$(
+        fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value> {
+            match self {
+                Self::Value(value) => match value {
+                    AttrValue::Scalar(value) => {
+                        DynDeserializer(value.get()).$method($($arg,)* visitor)
+                    }
+                    AttrValue::Array(arr) => ArrayDeserializer(arr.view()).$method($($arg,)* visitor),
+                    AttrValue::Empty(_, shape) => {
+                        let arr = ArrayD::<OwnedDynValue>::from_shape_vec(shape, vec![])?;
+                        ArrayDeserializer(arr.view()).$method($($arg,)* visitor)
+                    }
+                    AttrValue::Null(_) => UnitDeserializer::new().$method($($arg,)* visitor),
+                },
+                Self::Group(group) => group.$method($($arg,)* visitor),
+            }
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for Entry {
+    type Error = Error;
+
+    forward_entry! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+}
+
+/// Deserializer of array values; multi-dimensional arrays are read as nested sequences.
+struct ArrayDeserializer<'a>(ArrayViewD<'a, OwnedDynValue>);
+
+impl<'de> de::Deserializer<'de> for ArrayDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0.ndim() {
+            0 => DynDeserializer(self.0.iter().next().ok_or("empty array")?.get())
+                .deserialize_any(visitor),
+            1 => visitor.visit_seq(ElementAccess(self.0.iter())),
+            _ => visitor.visit_seq(RowAccess(self.0.outer_iter())),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self, name: &'static str, _fields: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value> {
+        if name == ARRAY_NAME {
+            visitor.visit_seq(NdArrayAccess { arr: self.0, field: 0 })
+        } else {
+            fail!("cannot deserialize struct {} from an array", name)
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self, _name: &'static str, visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct ElementAccess<I>(I);
+
+impl<'de, 'a, I: Iterator<Item = &'a OwnedDynValue> + ExactSizeIterator> SeqAccess<'de>
+    for ElementAccess<I>
+{
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        self.0.next().map(|value| seed.deserialize(DynDeserializer(value.get()))).transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct RowAccess<'a>(ndarray::iter::AxisIter<'a, OwnedDynValue, IxDyn>);
+
+impl<'de> SeqAccess<'de> for RowAccess<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        self.0.next().map(|row| seed.deserialize(ArrayDeserializer(row))).transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// Fields of arrays serialized via [`serialize::array`](super::array): shape and data.
+struct NdArrayAccess<'a> {
+    arr: ArrayViewD<'a, OwnedDynValue>,
+    field: usize,
+}
+
+impl<'de> SeqAccess<'de> for NdArrayAccess<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        self.field += 1;
+        match self.field {
+            1 => {
+                let shape = self.arr.shape().to_vec().into_deserializer();
+                seed.deserialize(shape).map(Some)
+            }
+            2 => {
+                let data =
+                    ArrayDeserializer(self.arr.view().into_shape(self.arr.len())?.into_dyn());
+                seed.deserialize(data).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Deserializer of a single value of any type.
+struct DynDeserializer<'a>(DynValue<'a>);
+
+impl DynDeserializer<'_> {
+    fn variant_name(&self) -> Option<String> {
+        match self.0 {
+            DynValue::Enum(ref value) => value.name().map(Into::into),
+            DynValue::String(ref value) => Some(String::from_utf8_lossy(value.get_buf()).into()),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for DynDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            DynValue::Scalar(DynScalar::Integer(value) | DynScalar::Bitfield(value)) => {
+                visit_integer(value, visitor)
+            }
+            DynValue::Scalar(DynScalar::Float(value)) => match value {
+                #[cfg(feature = "f16")]
+                DynFloat::Float16(x) => visitor.visit_f32(x.to_f32()),
+                DynFloat::Float32(x) => visitor.visit_f32(x),
+                DynFloat::Float64(x) => visitor.visit_f64(x),
+            },
+            DynValue::Scalar(DynScalar::Boolean(value)) => visitor.visit_bool(value),
+            DynValue::Enum(ref value) => match value.name() {
+                Some(name) => visitor.visit_string(name.into()),
+                None => fail!("invalid enum value: {}", value),
+            },
+            DynValue::Compound(ref value) => {
+                let fields: Vec<_> = value.iter().collect();
+                visitor.visit_map(FieldAccess { fields: fields.into_iter(), value: None })
+            }
+            DynValue::Array(ref value) => {
+                let items: Vec<_> = value.iter().collect();
+                visitor.visit_seq(ItemAccess(items.into_iter()))
+            }
+            DynValue::String(ref value) => {
+                visitor.visit_string(String::from_utf8_lossy(value.get_buf()).into())
+            }
+            DynValue::Opaque(ref value) => visitor.visit_byte_buf(value.get_buf().to_vec()),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self, _name: &'static str, _variants: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value> {
+        match self.variant_name() {
+            Some(name) => visitor.visit_enum(name.into_deserializer()),
+            None => fail!("expected an enum or a string, found {}", self.0),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self, _name: &'static str, visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+fn visit_integer<'de, V: Visitor<'de>>(value: DynInteger, visitor: V) -> Result<V::Value> {
+    match value {
+        DynInteger::Int8(x) => visitor.visit_i8(x),
+        DynInteger::Int16(x) => visitor.visit_i16(x),
+        DynInteger::Int32(x) => visitor.visit_i32(x),
+        DynInteger::Int64(x) => visitor.visit_i64(x),
+        DynInteger::Int128(x) => visitor.visit_i128(x),
+        DynInteger::UInt8(x) => visitor.visit_u8(x),
+        DynInteger::UInt16(x) => visitor.visit_u16(x),
+        DynInteger::UInt32(x) => visitor.visit_u32(x),
+        DynInteger::UInt64(x) => visitor.visit_u64(x),
+        DynInteger::UInt128(x) => visitor.visit_u128(x),
+    }
+}
+
+/// Fields of a compound value.
+struct FieldAccess<'a, I> {
+    fields: I,
+    value: Option<DynValue<'a>>,
+}
+
+impl<'de, 'a, I: Iterator<Item = (&'a str, DynValue<'a>)>> MapAccess<'de> for FieldAccess<'a, I> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.fields.next() {
+            Some((name, value)) => {
+                self.value = Some(value);
+                seed.deserialize(KeyDeserializer(name)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self.value.take().ok_or("map value deserialized before its key")?;
+        seed.deserialize(DynDeserializer(value))
+    }
+}
+
+/// Elements of an array value.
+struct ItemAccess<I>(I);
+
+impl<'de, 'a, I: Iterator<Item = DynValue<'a>> + ExactSizeIterator> SeqAccess<'de>
+    for ItemAccess<I>
+{
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        self.0.next().map(|value| seed.deserialize(DynDeserializer(value))).transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// Deserializer of map keys from attribute and member names (integer keys are parsed).
+struct KeyDeserializer<'a>(&'a str);
+
+macro_rules! deserialize_keys {
+    ($($method:ident => $visit:ident),* $(,)?) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self.0.parse() {
+                Ok(key) => visitor.$visit(key),
+                Err(_) => fail!("invalid integer key: {:?}", self.0),
+            }
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_str(self.0)
+    }
+
+    deserialize_keys! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self, _name: &'static str, _variants: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(StrDeserializer::new(self.0))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self, _name: &'static str, visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf option unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
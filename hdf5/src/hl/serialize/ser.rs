@@ -0,0 +1,732 @@
+use std::str::FromStr;
+
+use hdf5_types::{EnumMember, EnumType, IntSize, OwnedDynValue, TypeDescriptor, VarLenUnicode};
+use ndarray::ArrayD;
+use serde::ser::{
+    self, Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct,
+    SerializeStructVariant, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+
+use super::array::ARRAY_NAME;
+use super::Placement;
+use crate::internal_prelude::*;
+
+/// Intermediate representation of a serialized value.
+enum Node {
+    Skip,
+    Value(OwnedDynValue),
+    Variant(u32, &'static str),
+    Seq(Vec<Node>),
+    Map(Vec<(String, Node)>),
+    Array(Vec<Ix>, Vec<Node>),
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool {
+        matches!(self, Self::Value(_) | Self::Variant(..))
+    }
+}
+
+/// Returns the shape of (possibly nested) sequences of leaf nodes of equal length.
+fn array_shape(nodes: &[Node]) -> Option<Vec<Ix>> {
+    if nodes.iter().all(Node::is_leaf) {
+        return Some(vec![nodes.len()]);
+    }
+    let mut inner: Option<Vec<Ix>> = None;
+    for node in nodes {
+        let shape = match node {
+            Node::Seq(items) => array_shape(items)?,
+            Node::Array(shape, items) if items.iter().all(Node::is_leaf) => shape.clone(),
+            _ => return None,
+        };
+        if inner.get_or_insert_with(|| shape.clone()) != &shape {
+            return None;
+        }
+    }
+    Some([nodes.len()].into_iter().chain(inner.unwrap_or_default()).collect())
+}
+
+fn leaves<'a>(nodes: &'a [Node], out: &mut Vec<&'a Node>) {
+    for node in nodes {
+        match node {
+            Node::Seq(items) | Node::Array(_, items) => leaves(items, out),
+            node => out.push(node),
+        }
+    }
+}
+
+fn into_leaves(nodes: Vec<Node>, out: &mut Vec<Node>) {
+    for node in nodes {
+        match node {
+            Node::Seq(items) | Node::Array(_, items) => into_leaves(items, out),
+            node => out.push(node),
+        }
+    }
+}
+
+/// Returns `true` if all leaves can be stored as a single array: either all of them are
+/// unit enum variants, or all of them are values of the same type.
+fn is_uniform(nodes: &[Node]) -> bool {
+    let mut refs = Vec::new();
+    leaves(nodes, &mut refs);
+    match refs.first() {
+        None => true,
+        Some(Node::Variant(..)) => refs.iter().all(|node| matches!(node, Node::Variant(..))),
+        Some(Node::Value(first)) => refs.iter().all(|node| match node {
+            Node::Value(value) => value.type_descriptor() == first.type_descriptor(),
+            _ => false,
+        }),
+        Some(_) => false,
+    }
+}
+
+/// Converts uniform leaf nodes into values; unit enum variants are converted into values of
+/// an enum type containing all of the variants encountered.
+fn leaf_values(nodes: Vec<Node>) -> Vec<OwnedDynValue> {
+    let mut members: Vec<EnumMember> = Vec::new();
+    for node in &nodes {
+        if let Node::Variant(index, name) = *node {
+            if !members.iter().any(|m| m.value == u64::from(index)) {
+                members.push(EnumMember { name: name.into(), value: index.into() });
+            }
+        }
+    }
+    members.sort_by_key(|m| m.value);
+    let desc = TypeDescriptor::Enum(EnumType { size: IntSize::U4, signed: false, members });
+    nodes
+        .into_iter()
+        .filter_map(|node| match node {
+            Node::Value(value) => Some(value),
+            Node::Variant(index, _) => {
+                Some(unsafe { OwnedDynValue::from_raw(desc.clone(), index.to_ne_bytes().into()) })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn array_value(shape: Vec<Ix>, nodes: Vec<Node>) -> Result<AttrValue> {
+    let mut flat = Vec::new();
+    into_leaves(nodes, &mut flat);
+    Ok(AttrValue::Array(ArrayD::from_shape_vec(shape, leaf_values(flat))?))
+}
+
+/// Writes serialized values into a group.
+#[derive(Clone, Copy)]
+struct NodeWriter {
+    placement: Placement,
+}
+
+impl NodeWriter {
+    fn write_entries(self, group: &Group, entries: Vec<(String, Node)>) -> Result<()> {
+        for (name, node) in entries {
+            self.write(group, &name, node)?;
+        }
+        Ok(())
+    }
+
+    fn write(self, group: &Group, name: &str, node: Node) -> Result<()> {
+        match node {
+            Node::Skip => Ok(()),
+            Node::Value(_) | Node::Variant(..) => {
+                let value = AttrValue::Scalar(leaf_values(vec![node]).remove(0));
+                match self.placement {
+                    Placement::Attribute => Self::write_attr(group, name, &value),
+                    Placement::Dataset => Self::write_dataset(group, name, &value),
+                }
+            }
+            Node::Map(entries) => self.write_entries(&group.create_group(name)?, entries),
+            // the element type of empty sequences is unknown, so they are stored as groups
+            Node::Seq(items) if items.is_empty() => self.write_items(group, name, items),
+            Node::Seq(items) => match array_shape(&items) {
+                Some(shape) if is_uniform(&items) => {
+                    Self::write_dataset(group, name, &array_value(shape, items)?)
+                }
+                _ => self.write_items(group, name, items),
+            },
+            Node::Array(shape, items) => {
+                ensure!(
+                    items.iter().all(Node::is_leaf) && is_uniform(&items),
+                    "array elements must be scalars of the same type: {:?}",
+                    name
+                );
+                Self::write_dataset(group, name, &array_value(shape, items)?)
+            }
+        }
+    }
+
+    fn write_items(self, group: &Group, name: &str, items: Vec<Node>) -> Result<()> {
+        let group = group.create_group(name)?;
+        let entries = items.into_iter().enumerate().map(|(i, node)| (i.to_string(), node));
+        self.write_entries(&group, entries.collect())
+    }
+
+    fn write_attr(group: &Group, name: &str, value: &AttrValue) -> Result<()> {
+        let desc = value.type_descriptor().ok_or_else(|| empty_array_error(name))?;
+        let builder = group.new_attr_builder().empty_as(desc);
+        let attr = if let AttrValue::Scalar(_) = value {
+            builder.create(name)?
+        } else {
+            builder.shape(value.shape()).create(name)?
+        };
+        attr.write_dyn_value(value)
+    }
+
+    fn write_dataset(group: &Group, name: &str, value: &AttrValue) -> Result<()> {
+        let desc = value.type_descriptor().ok_or_else(|| empty_array_error(name))?;
+        let builder = group.new_dataset_builder().empty_as(desc);
+        let dataset = if let AttrValue::Scalar(_) = value {
+            builder.create(name)?
+        } else {
+            builder.shape(value.shape()).create(name)?
+        };
+        dataset.write_dyn_value(value)
+    }
+}
+
+fn empty_array_error(name: &str) -> Error {
+    format!("cannot determine the element type of empty array: {:?}", name).into()
+}
+
+/// Serializer of values into intermediate nodes.
+struct NodeSerializer;
+
+macro_rules! serialize_values {
+    ($($method:ident: $ty:ty),* $(,)?) => {$(
+        fn $method(self, v: $ty) -> Result<Node> {
+            Ok(Node::Value(OwnedDynValue::new(v)))
+        }
+    )*};
+}
+
+impl ser::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = VariantSerializer<SeqSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = VariantSerializer<MapSerializer>;
+
+    serialize_values! {
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+    }
+
+    fn serialize_char(self, v: char) -> Result<Node> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Node> {
+        let s = VarLenUnicode::from_str(v).map_err(|e| e.to_string())?;
+        Ok(Node::Value(OwnedDynValue::new(s)))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Node> {
+        Ok(Node::Seq(v.iter().map(|&b| Node::Value(OwnedDynValue::new(b))).collect()))
+    }
+
+    fn serialize_none(self) -> Result<Node> {
+        Ok(Node::Skip)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Node> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Node> {
+        Ok(Node::Skip)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Node> {
+        Ok(Node::Skip)
+    }
+
+    fn serialize_unit_variant(
+        self, _name: &'static str, variant_index: u32, variant: &'static str,
+    ) -> Result<Node> {
+        Ok(Node::Variant(variant_index, variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self, _name: &'static str, value: &T,
+    ) -> Result<Node> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T,
+    ) -> Result<Node> {
+        Ok(Node::Map(vec![(variant.into(), value.serialize(self)?)]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize,
+    ) -> Result<VariantSerializer<SeqSerializer>> {
+        Ok(VariantSerializer { variant, inner: self.serialize_seq(Some(len))? })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer::new(len.unwrap_or(0)))
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<MapSerializer> {
+        let mut map = MapSerializer::new(len);
+        map.array = name == ARRAY_NAME;
+        Ok(map)
+    }
+
+    fn serialize_struct_variant(
+        self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize,
+    ) -> Result<VariantSerializer<MapSerializer>> {
+        Ok(VariantSerializer { variant, inner: MapSerializer::new(len) })
+    }
+}
+
+struct SeqSerializer(Vec<Node>);
+
+impl SerializeSeq for SeqSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.0.push(value.serialize(NodeSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node> {
+        Ok(Node::Seq(self.0))
+    }
+}
+
+impl SerializeTuple for SeqSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Node> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for SeqSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Node> {
+        SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    entries: Vec<(String, Node)>,
+    key: Option<String>,
+    array: bool,
+}
+
+impl MapSerializer {
+    fn new(len: usize) -> Self {
+        Self { entries: Vec::with_capacity(len), key: None, array: false }
+    }
+
+    fn into_node(self) -> Node {
+        // arrays serialized via `serialize::array` are structs with `shape` and `data` fields
+        match self.entries.as_slice() {
+            [(_, Node::Seq(shape)), (_, Node::Seq(_))] if self.array => {
+                let shape: Option<Vec<Ix>> = shape
+                    .iter()
+                    .map(|node| match node {
+                        Node::Value(value) => value.clone().cast::<u64>().ok().map(|n| n as _),
+                        _ => None,
+                    })
+                    .collect();
+                if let Some(shape) = shape {
+                    if let Some((_, Node::Seq(items))) = self.entries.into_iter().nth(1) {
+                        return Node::Array(shape, items);
+                    }
+                    unreachable!()
+                }
+                Node::Map(self.entries)
+            }
+            _ => Node::Map(self.entries),
+        }
+    }
+}
+
+impl SerializeMap for MapSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self.key.take().ok_or("map value serialized before its key")?;
+        self.entries.push((key, value.serialize(NodeSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node> {
+        Ok(self.into_node())
+    }
+}
+
+impl SerializeStruct for MapSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self, key: &'static str, value: &T,
+    ) -> Result<()> {
+        self.entries.push((key.into(), value.serialize(NodeSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node> {
+        Ok(self.into_node())
+    }
+}
+
+/// Non-unit enum variants are stored as single-entry maps keyed by the variant name.
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl SerializeTupleVariant for VariantSerializer<SeqSerializer> {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Node> {
+        Ok(Node::Map(vec![(self.variant.into(), SerializeSeq::end(self.inner)?)]))
+    }
+}
+
+impl SerializeStructVariant for VariantSerializer<MapSerializer> {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self, key: &'static str, value: &T,
+    ) -> Result<()> {
+        SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Node> {
+        Ok(Node::Map(vec![(self.variant.into(), self.inner.into_node())]))
+    }
+}
+
+/// Serializer of map keys into member names (only strings, chars and integers are allowed).
+struct KeySerializer;
+
+macro_rules! serialize_keys {
+    ($($method:ident: $ty:ty),* $(,)?) => {$(
+        fn $method(self, v: $ty) -> Result<String> {
+            Ok(v.to_string())
+        }
+    )*};
+}
+
+macro_rules! unsupported {
+    ($ok:ty; $($method:ident($($arg:ty),*)),* $(,)?) => {$(
+        fn $method(self, $(_: $arg),*) -> Result<$ok> {
+            fail!(concat!("unsupported value: ", stringify!($method)))
+        }
+    )*};
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    serialize_keys! {
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_char: char,
+        serialize_str: &str,
+    }
+
+    unsupported! {
+        String;
+        serialize_bool(bool),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit_variant(
+        self, _name: &'static str, _variant_index: u32, variant: &'static str,
+    ) -> Result<String> {
+        Ok(variant.into())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self, _name: &'static str, value: &T,
+    ) -> Result<String> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T,
+    ) -> Result<String> {
+        fail!("map keys must be strings or integers")
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        fail!("map keys must be strings or integers")
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        fail!("map keys must be strings or integers")
+    }
+
+    fn serialize_tuple_struct(
+        self, _name: &'static str, _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        fail!("map keys must be strings or integers")
+    }
+
+    fn serialize_tuple_variant(
+        self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        fail!("map keys must be strings or integers")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        fail!("map keys must be strings or integers")
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        fail!("map keys must be strings or integers")
+    }
+
+    fn serialize_struct_variant(
+        self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        fail!("map keys must be strings or integers")
+    }
+}
+
+/// Serializer of structs and maps into an HDF5 group.
+///
+/// Struct fields and map entries are written as members or attributes of the group; see
+/// the [module-level documentation](super) for details on how values are mapped.
+#[derive(Clone, Debug)]
+pub struct Serializer<'a> {
+    group: &'a Group,
+    placement: Placement,
+}
+
+impl<'a> Serializer<'a> {
+    /// Creates a new serializer writing into an existing group.
+    pub fn new(group: &'a Group) -> Self {
+        Self { group, placement: Placement::default() }
+    }
+
+    /// Sets where scalar values and strings are stored (attributes by default).
+    #[must_use]
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+}
+
+/// Collects struct fields or map entries and writes them into the group.
+pub struct GroupSerializer<'a> {
+    group: &'a Group,
+    writer: NodeWriter,
+    map: MapSerializer,
+}
+
+impl GroupSerializer<'_> {
+    fn finish(self) -> Result<()> {
+        self.writer.write_entries(self.group, self.map.entries)
+    }
+}
+
+macro_rules! not_a_group {
+    ($($method:ident($($arg:ty),*)),* $(,)?) => {$(
+        fn $method(self, $(_: $arg),*) -> Result<()> {
+            fail!("only structs and maps can be serialized into a group")
+        }
+    )*};
+}
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = GroupSerializer<'a>;
+    type SerializeStruct = GroupSerializer<'a>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    not_a_group! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self, _name: &'static str, value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T,
+    ) -> Result<()> {
+        fail!("only structs and maps can be serialized into a group")
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        fail!("only structs and maps can be serialized into a group")
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        fail!("only structs and maps can be serialized into a group")
+    }
+
+    fn serialize_tuple_struct(
+        self, _name: &'static str, _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        fail!("only structs and maps can be serialized into a group")
+    }
+
+    fn serialize_tuple_variant(
+        self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        fail!("only structs and maps can be serialized into a group")
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<GroupSerializer<'a>> {
+        let writer = NodeWriter { placement: self.placement };
+        Ok(GroupSerializer { group: self.group, writer, map: MapSerializer::new(len.unwrap_or(0)) })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<GroupSerializer<'a>> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        fail!("only structs and maps can be serialized into a group")
+    }
+}
+
+impl SerializeMap for GroupSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.map.serialize_key(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.map.serialize_value(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl SerializeStruct for GroupSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self, key: &'static str, value: &T,
+    ) -> Result<()> {
+        SerializeStruct::serialize_field(&mut self.map, key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
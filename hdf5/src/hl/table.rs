@@ -0,0 +1,365 @@
+//! Tables of records stored in one-dimensional compound datasets.
+//!
+//! A [`Table`] follows the conventions of the `HDF5` high-level table API (`H5TB`): the records
+//! are stored in a chunked, resizable one-dimensional dataset with a compound datatype, and the
+//! dataset is tagged with the attributes `CLASS = "TABLE"`, `VERSION`, `TITLE` and
+//! `FIELD_<n>_NAME` for each of the fields, so that the tables can be read by `PyTables` and
+//! other `H5TB`-based tools.
+//!
+//! Records are read and written via types that implement `H5Type`; single columns can be
+//! accessed by field name, in which case `HDF5` converts the values between the memory and
+//! file types by member name.
+
+use std::ops::{Bound, Deref, RangeBounds};
+
+use hdf5_types::{CompoundField, CompoundType, StringPadding, TypeDescriptor as TD};
+
+use crate::internal_prelude::*;
+
+/// Value of the `CLASS` attribute of tables.
+const TABLE_CLASS: &str = "TABLE";
+/// Version of the `H5TB` conventions written to the `VERSION` attribute.
+const TABLE_VERSION: &str = "3.0";
+
+/// A table of records stored in a one-dimensional compound dataset (see [`crate::table`]).
+#[derive(Clone, Debug)]
+pub struct Table {
+    group: Group,
+    name: String,
+    ds: Dataset,
+}
+
+impl Deref for Table {
+    type Target = Dataset;
+
+    fn deref(&self) -> &Dataset {
+        &self.ds
+    }
+}
+
+impl Table {
+    pub(crate) fn create(
+        group: &Group, name: &str, desc: &TD, title: &str, chunk_size: usize,
+    ) -> Result<Self> {
+        ensure!(chunk_size > 0, "table chunk size must be positive");
+        let fields = compound_fields(desc)?;
+        let ds =
+            group.new_dataset_builder().empty_as(desc).shape(0..).chunk(chunk_size).create(name)?;
+        write_table_attrs(&ds, title, fields)?;
+        Ok(Self { group: group.clone(), name: name.into(), ds })
+    }
+
+    pub(crate) fn open(group: &Group, name: &str) -> Result<Self> {
+        let ds = group.dataset(name)?;
+        ensure!(ds.attr_exists("CLASS"), "dataset is not a table: {:?}", name);
+        let class = ds.attr("CLASS")?.read_strings()?;
+        ensure!(class == [TABLE_CLASS], "dataset is not a table: {:?} (class {:?})", name, class);
+        ensure!(ds.ndim() == 1, "expected a one-dimensional table, got {} dimensions", ds.ndim());
+        compound_fields(&ds.dtype()?.to_descriptor()?)?;
+        Ok(Self { group: group.clone(), name: name.into(), ds })
+    }
+
+    /// Returns the underlying dataset.
+    pub fn dataset(&self) -> &Dataset {
+        &self.ds
+    }
+
+    /// Returns the number of records in the table.
+    pub fn num_records(&self) -> usize {
+        self.ds.size()
+    }
+
+    /// Returns the title of the table (empty if it has none).
+    pub fn title(&self) -> Result<String> {
+        if !self.ds.attr_exists("TITLE") {
+            return Ok(String::new());
+        }
+        Ok(self.ds.attr("TITLE")?.read_strings()?.into_iter().next().unwrap_or_default())
+    }
+
+    /// Replaces the title of the table.
+    pub fn set_title(&self, title: &str) -> Result<()> {
+        write_string_attr(&self.ds, "TITLE", title)
+    }
+
+    /// Returns the names of the fields in the order of the compound members.
+    pub fn field_names(&self) -> Result<Vec<String>> {
+        let desc = self.ds.dtype()?.to_descriptor()?;
+        Ok(compound_fields(&desc)?.iter().map(|f| f.name.clone()).collect())
+    }
+
+    /// Appends the records to the end of the table; the table is left unchanged if they
+    /// cannot be written.
+    pub fn append_records<T: H5Type>(&self, records: &[T]) -> Result<()> {
+        let start = self.num_records();
+        self.ds.resize(start + records.len())?;
+        self.write_records(start, records).map_err(|err| self.truncate_after_error(start, err))
+    }
+
+    /// Reads a range of records from the table.
+    pub fn read_records<T: H5Type, R: RangeBounds<usize>>(&self, range: R) -> Result<Vec<T>> {
+        let (start, end) = self.bounds(range)?;
+        if start == end {
+            return Ok(vec![]);
+        }
+        Ok(self.ds.read_slice_1d::<T, _>(start..end)?.into_raw_vec())
+    }
+
+    /// Overwrites the records starting at `start`; the table is not resized.
+    pub fn write_records<T: H5Type>(&self, start: usize, records: &[T]) -> Result<()> {
+        let (start, end) = self.bounds(start..start + records.len())?;
+        if start == end {
+            return Ok(());
+        }
+        self.ds.write_slice(records, start..end)
+    }
+
+    /// Inserts the records before the record at position `start`, shifting the following
+    /// records towards the end of the table; the table is left unchanged if they cannot be
+    /// written.
+    pub fn insert_records<T: H5Type>(&self, start: usize, records: &[T]) -> Result<()> {
+        let len = self.num_records();
+        ensure!(start <= len, "insert position {} out of bounds for {} records", start, len);
+        if records.is_empty() {
+            return Ok(());
+        }
+        let end = start + records.len();
+        self.ds.resize(len + records.len())?;
+        self.move_records(start..len, end)
+            .and_then(|()| {
+                self.write_records(start, records).map_err(|err| {
+                    let _ = self.move_records(end..len + records.len(), start);
+                    err
+                })
+            })
+            .map_err(|err| self.truncate_after_error(len, err))
+    }
+
+    /// Shrinks the table back to `len` records after a failed write, returning the error.
+    fn truncate_after_error(&self, len: usize, err: Error) -> Error {
+        let _ = self.ds.resize(len);
+        err
+    }
+
+    /// Deletes a range of records, shifting the following records towards the start of the
+    /// table.
+    pub fn delete_records<R: RangeBounds<usize>>(&self, range: R) -> Result<()> {
+        let (start, end) = self.bounds(range)?;
+        if start == end {
+            return Ok(());
+        }
+        let len = self.num_records();
+        self.move_records(end..len, start)?;
+        self.ds.resize(len - (end - start))
+    }
+
+    /// Reads a single column of the table.
+    ///
+    /// The values are converted from the type of the field to `T`.
+    pub fn read_column<T: H5Type>(&self, name: &str) -> Result<Vec<T>> {
+        let mem_dtype = self.column_dtype::<T>(name)?;
+        let len = self.num_records();
+        let mut buf: Vec<T> = Vec::with_capacity(len);
+        if len != 0 {
+            self.ds.as_reader().read_into_raw(&mem_dtype, buf.as_mut_ptr().cast(), None, None)?;
+            unsafe {
+                buf.set_len(len);
+            }
+        }
+        Ok(buf)
+    }
+
+    /// Overwrites a single column of the table, leaving the other fields unchanged.
+    ///
+    /// The number of values must match the number of records.
+    pub fn write_column<T: H5Type>(&self, name: &str, values: &[T]) -> Result<()> {
+        let mem_dtype = self.column_dtype::<T>(name)?;
+        let len = self.num_records();
+        ensure!(values.len() == len, "expected {} values, got {}", len, values.len());
+        if len == 0 {
+            return Ok(());
+        }
+        self.ds.as_writer().write_from_raw(&mem_dtype, values.as_ptr().cast(), None, None)
+    }
+
+    /// Adds a field with the given values after the existing fields.
+    ///
+    /// The table is rewritten into a new dataset with the extended datatype, which replaces
+    /// the existing one; the datatype of the new field is that of `T`.
+    pub fn add_field<T: H5Type>(&mut self, name: &str, values: &[T]) -> Result<()> {
+        let len = self.num_records();
+        ensure!(values.len() == len, "expected {} values, got {}", len, values.len());
+        let desc = self.ds.dtype()?.to_descriptor()?;
+        let mut fields = compound_fields(&desc)?.to_vec();
+        ensure!(fields.iter().all(|f| f.name != name), "field already exists: {:?}", name);
+        fields.push(CompoundField::new(name, T::type_descriptor(), 0, fields.len()));
+        self.rewrite(&TD::Compound(CompoundType { fields, size: 0 }.to_c_repr()))?;
+        self.write_column(name, values)
+    }
+
+    /// Removes a field from the table.
+    ///
+    /// The table is rewritten into a new dataset without the field, which replaces the
+    /// existing one. The last remaining field cannot be removed.
+    pub fn drop_field(&mut self, name: &str) -> Result<()> {
+        let desc = self.ds.dtype()?.to_descriptor()?;
+        let mut fields = compound_fields(&desc)?.to_vec();
+        let pos = fields.iter().position(|f| f.name == name);
+        let pos = pos.ok_or_else(|| format!("field not found: {:?}", name))?;
+        ensure!(fields.len() > 1, "cannot drop the only field of a table: {:?}", name);
+        fields.remove(pos);
+        for (index, field) in fields.iter_mut().enumerate() {
+            field.index = index;
+        }
+        self.rewrite(&TD::Compound(CompoundType { fields, size: 0 }.to_c_repr()))
+    }
+
+    fn bounds<R: RangeBounds<usize>>(&self, range: R) -> Result<(usize, usize)> {
+        let len = self.num_records();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        ensure!(
+            start <= end && end <= len,
+            "record range {}..{} out of bounds for {} records",
+            start,
+            end,
+            len
+        );
+        Ok((start, end))
+    }
+
+    /// Creates a single-field compound memory type for accessing a column as `T`.
+    fn column_dtype<T: H5Type>(&self, name: &str) -> Result<Datatype> {
+        let names = self.field_names()?;
+        ensure!(names.iter().any(|n| n == name), "field not found: {:?}", name);
+        let ty = T::type_descriptor();
+        let size = ty.size();
+        let fields = vec![CompoundField::new(name, ty, 0, 0)];
+        Datatype::from_descriptor(&TD::Compound(CompoundType { fields, size }))
+    }
+
+    /// Copies the records in `src` so that they start at `dst` (the ranges may overlap).
+    fn move_records(&self, src: std::ops::Range<usize>, dst: usize) -> Result<()> {
+        if src.is_empty() {
+            return Ok(());
+        }
+        let desc = self.ds.dtype()?.to_descriptor()?.to_native_repr().to_c_repr();
+        let mem_dtype = Datatype::from_descriptor(&desc)?;
+        let count = src.len();
+        let space = self.ds.space()?;
+        let mspace = Dataspace::try_new(count)?;
+        let (src_space, dst_space) = (space.select(src)?, space.select(dst..dst + count)?);
+        let reader = self.ds.as_reader();
+        reader.read_reclaim(&mem_dtype, count * desc.size(), Some((&src_space, &mspace)), {
+            |buf: &[u8]| {
+                let writer = self.ds.as_writer();
+                writer.write_from_raw(
+                    &mem_dtype,
+                    buf.as_ptr().cast(),
+                    Some(&dst_space),
+                    Some(&mspace),
+                )
+            }
+        })?
+    }
+
+    /// Rewrites the table into a new dataset with the given datatype and replaces the current
+    /// dataset with it. Fields are matched by name; the chunking, filters, title and
+    /// non-`H5TB` attributes (including empty and null ones) are preserved.
+    ///
+    /// The new dataset is written under a temporary name first and the original dataset is
+    /// only unlinked once the new one has been moved into its place, so the table is left
+    /// unchanged if any of the steps fails.
+    fn rewrite(&mut self, desc: &TD) -> Result<()> {
+        let len = self.num_records();
+        let (tmp_name, backup_name) =
+            (format!("{}.rewrite", self.name), format!("{}.old", self.name));
+        for name in [&tmp_name, &backup_name] {
+            ensure!(!self.group.link_exists(name), "link already exists: {:?}", name);
+        }
+        let chunk = self.ds.chunk().and_then(|c| c.first().copied()).unwrap_or(len.max(1));
+        let ds = (self.group.new_dataset_builder().empty_as(desc).shape(len..))
+            .chunk(chunk)
+            .set_filters(&self.ds.filters())
+            .create(tmp_name.as_str())?;
+        if let Err(err) = self.copy_into(&ds, desc) {
+            let _ = self.group.unlink(&tmp_name);
+            return Err(err);
+        }
+
+        if let Err(err) = self.group.relink(&self.name, &backup_name) {
+            let _ = self.group.unlink(&tmp_name);
+            return Err(err);
+        }
+        if let Err(err) = self.group.relink(&tmp_name, &self.name) {
+            let _ = self.group.relink(&backup_name, &self.name);
+            let _ = self.group.unlink(&tmp_name);
+            return Err(err);
+        }
+        self.ds = ds;
+        self.group.unlink(&backup_name)
+    }
+
+    /// Copies the records and the non-`H5TB` attributes of the table into a new dataset and
+    /// writes the `H5TB` attributes for the given datatype.
+    fn copy_into(&self, ds: &Dataset, desc: &TD) -> Result<()> {
+        let len = self.num_records();
+        if len != 0 {
+            let src_desc = self.ds.dtype()?.to_descriptor()?.to_native_repr().to_c_repr();
+            let mem_dtype = Datatype::from_descriptor(&src_desc)?;
+            let reader = self.ds.as_reader();
+            reader.read_reclaim(&mem_dtype, len * src_desc.size(), None, |buf: &[u8]| {
+                ds.as_writer().write_from_raw(&mem_dtype, buf.as_ptr().cast(), None, None)
+            })??;
+        }
+        let mut attrs = self.ds.read_attrs()?;
+        attrs.retain(|name, _| {
+            !matches!(name.as_str(), "CLASS" | "VERSION" | "TITLE") && !name.starts_with("FIELD_")
+        });
+        ds.write_attrs(&attrs)?;
+        write_table_attrs(ds, &self.title()?, compound_fields(desc)?)
+    }
+}
+
+fn compound_fields(desc: &TD) -> Result<&[CompoundField]> {
+    match desc {
+        TD::Compound(tp) if !tp.fields.is_empty() => Ok(&tp.fields),
+        TD::Compound(_) => fail!("compound types must have at least one field"),
+        _ => fail!("expected a compound datatype for a table, got {}", desc),
+    }
+}
+
+/// Writes the `H5TB` attributes of a table.
+fn write_table_attrs(ds: &Dataset, title: &str, fields: &[CompoundField]) -> Result<()> {
+    write_string_attr(ds, "CLASS", TABLE_CLASS)?;
+    write_string_attr(ds, "VERSION", TABLE_VERSION)?;
+    write_string_attr(ds, "TITLE", title)?;
+    for (i, field) in fields.iter().enumerate() {
+        write_string_attr(ds, &format!("FIELD_{i}_NAME"), &field.name)?;
+    }
+    Ok(())
+}
+
+/// Writes a scalar null-terminated fixed-length string attribute, like `H5LTset_attribute_string`.
+fn write_string_attr(ds: &Dataset, name: &str, value: &str) -> Result<()> {
+    let len = value.len() + 1;
+    let desc = if value.is_ascii() {
+        TD::FixedAscii(len, StringPadding::NullTerm)
+    } else {
+        TD::FixedUnicode(len, StringPadding::NullTerm)
+    };
+    h5lock!({
+        if ds.attr_exists(name) {
+            ds.delete_attr(name)?;
+        }
+        ds.new_attr_builder().empty_as(&desc).create(name)?.write_raw(&[value])
+    })
+}
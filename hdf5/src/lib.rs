@@ -58,19 +58,21 @@ mod export {
         hl::extents::{Extent, Extents, SimpleExtents},
         hl::selection::{Hyperslab, Selection, SliceOrIndex},
         hl::{
-            Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
-            AttributeBuilderEmptyShape, ByteReader, Container, Conversion, Dataset, DatasetBuilder,
-            DatasetBuilderData, DatasetBuilderEmpty, DatasetBuilderEmptyShape, Dataspace, Datatype,
-            File, FileBuilder, Group, LinkInfo, LinkType, Location, LocationInfo, LocationToken,
-            LocationType, Object, OpenMode, PropertyList, Reader, Writer,
+            AttrValue, Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
+            AttributeBuilderEmptyShape, AttributeInfo, AttributeIter, ByteReader, Container,
+            Conversion, Dataset, DatasetBuilder, DatasetBuilderData, DatasetBuilderEmpty,
+            DatasetBuilderEmptyShape, Dataspace, Datatype, File, FileBuilder, Group, GroupWithLapl,
+            H5Group, IterationOrder, LinkClass, LinkInfo, LinkTarget, LinkType, Location,
+            LocationInfo, LocationToken, LocationType, Object, OpenMode, PropertyList, Reader,
+            Table, TraversalOrder, Writer,
         },
     };
 
     #[doc(hidden)]
     pub use crate::error::h5check;
 
-    pub use hdf5_derive::H5Type;
-    pub use hdf5_types::H5Type;
+    pub use hdf5_derive::{H5Group, H5Proxy, H5Type};
+    pub use hdf5_types::{H5Proxy, H5Type};
 
     pub mod types {
         pub use hdf5_types::*;
@@ -81,7 +83,7 @@ mod export {
         pub use crate::hl::chunks::ChunkInfo;
         #[cfg(feature = "1.14.0")]
         pub use crate::hl::chunks::ChunkInfoRef;
-        pub use crate::hl::dataset::{Chunk, Dataset, DatasetBuilder};
+        pub use crate::hl::dataset::{Chunk, Dataset, DatasetBuilder, DatasetValue};
         pub use crate::hl::plist::dataset_access::*;
         pub use crate::hl::plist::dataset_create::*;
     }
@@ -92,6 +94,13 @@ mod export {
 
     pub mod file {
         pub use crate::hl::file::{File, FileBuilder, OpenMode};
+        #[cfg(feature = "1.10.0")]
+        pub use crate::hl::file::{
+            FileStats, FreeSection, FreeSpaceStats, MetadataCacheSize, ReadRetryStats,
+            SharedMessageStats, SuperblockStats,
+        };
+        #[cfg(feature = "1.10.1")]
+        pub use crate::hl::file::{PageBufferCounters, PageBufferingStats};
         pub use crate::hl::plist::file_access::*;
         pub use crate::hl::plist::file_create::*;
     }
@@ -101,6 +110,8 @@ mod export {
         pub use crate::hl::plist::dataset_create::{DatasetCreate, DatasetCreateBuilder};
         pub use crate::hl::plist::file_access::{FileAccess, FileAccessBuilder};
         pub use crate::hl::plist::file_create::{FileCreate, FileCreateBuilder};
+        pub use crate::hl::plist::file_mount::{FileMount, FileMountBuilder};
+        pub use crate::hl::plist::link_access::{LinkAccess, LinkAccessBuilder};
         pub use crate::hl::plist::link_create::{LinkCreate, LinkCreateBuilder};
         pub use crate::hl::plist::{PropertyList, PropertyListClass};
 
@@ -116,6 +127,12 @@ mod export {
         pub mod file_create {
             pub use crate::hl::plist::file_create::*;
         }
+        pub mod file_mount {
+            pub use crate::hl::plist::file_mount::*;
+        }
+        pub mod link_access {
+            pub use crate::hl::plist::link_access::*;
+        }
         pub mod link_create {
             pub use crate::hl::plist::link_create::*;
         }
@@ -123,6 +140,25 @@ mod export {
     pub mod filters {
         pub use crate::hl::filters::*;
     }
+
+    #[cfg(feature = "arrow")]
+    pub mod arrow {
+        pub use crate::hl::arrow::{
+            array_type_descriptor, record_batch_type_descriptor, to_arrow_schema, to_arrow_type,
+        };
+    }
+
+    pub mod table {
+        pub use crate::hl::table::Table;
+    }
+
+    #[cfg(feature = "serde")]
+    pub mod serialize {
+        pub use crate::hl::serialize::{array, Deserializer, Placement, Serializer};
+    }
+
+    #[cfg(feature = "serde")]
+    pub use crate::hl::serialize::{from_group, to_group};
 }
 
 pub use crate::export::*;
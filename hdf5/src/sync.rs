@@ -20,6 +20,8 @@ lazy_static! {
             crate::error::silence_errors_no_sync(true);
             // Register filters lzf/blosc if available
             crate::hl::filters::register_filters();
+            // Register custom datatype conversion paths (opaque tags, array shapes, bfloat16)
+            crate::hl::datatype::register_conversions();
         }
     };
 }
@@ -5,7 +5,7 @@ use std::iter;
 use hdf5::types::{FixedAscii, FixedUnicode, VarLenArray, VarLenAscii, VarLenUnicode};
 use hdf5::H5Type;
 
-use half::f16;
+use half::{bf16, f16};
 use ndarray::{ArrayD, SliceInfo, SliceInfoElem};
 use num_complex::Complex;
 use rand::distributions::Standard;
@@ -103,6 +103,12 @@ impl Gen for f16 {
     }
 }
 
+impl Gen for bf16 {
+    fn gen<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from_f32(rng.gen())
+    }
+}
+
 impl<T: Debug> Gen for Complex<T>
 where
     Standard: Distribution<T>,
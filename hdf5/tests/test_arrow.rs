@@ -0,0 +1,192 @@
+#![cfg(feature = "arrow")]
+
+#[macro_use]
+mod common;
+
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, Int32Type, UInt16Type, UInt8Type};
+use arrow_array::{ArrayRef, DictionaryArray, Float64Array, Int32Array, RecordBatch};
+use arrow_array::{BooleanArray, StringArray};
+use arrow_schema::{DataType, Field};
+
+use hdf5::arrow::{record_batch_type_descriptor, to_arrow_schema};
+use hdf5::types::{FixedAscii, TypeDescriptor as TD, VarLenArray, VarLenUnicode};
+use hdf5::H5Type;
+
+use self::common::util::new_in_memory_file;
+
+#[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+enum Color {
+    Red = 1,
+    Green = 5,
+    Blue = 10,
+}
+
+#[derive(H5Type, Clone, Debug, PartialEq)]
+#[repr(C)]
+struct Record {
+    id: i32,
+    value: f64,
+    flag: bool,
+    name: VarLenUnicode,
+    code: FixedAscii<4>,
+    color: Color,
+    pos: [f32; 2],
+    tags: VarLenArray<u16>,
+}
+
+fn records() -> Vec<Record> {
+    let record = |id, name: &str, code: &str, color, tags: &[u16]| Record {
+        id,
+        value: f64::from(id) / 2.,
+        flag: id % 2 == 0,
+        name: name.parse().unwrap(),
+        code: FixedAscii::from_ascii(code).unwrap(),
+        color,
+        pos: [id as f32, -id as f32],
+        tags: VarLenArray::from_slice(tags),
+    };
+    vec![
+        record(1, "one", "a", Color::Blue, &[1, 2]),
+        record(2, "two", "bb", Color::Red, &[]),
+        record(3, "three", "cccc", Color::Green, &[3]),
+        record(4, "", "", Color::Blue, &[4, 5, 6]),
+    ]
+}
+
+#[test]
+fn test_arrow_schema() {
+    let schema = to_arrow_schema(&Record::type_descriptor()).unwrap();
+    let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(names, ["id", "value", "flag", "name", "code", "color", "pos", "tags"]);
+    let item = |dtype| Arc::new(Field::new("item", dtype, false));
+    let types: Vec<_> = schema.fields().iter().map(|f| f.data_type().clone()).collect();
+    assert_eq!(
+        types,
+        [
+            DataType::Int32,
+            DataType::Float64,
+            DataType::Boolean,
+            DataType::Utf8,
+            DataType::Utf8,
+            DataType::Dictionary(Box::new(DataType::UInt8), Box::new(DataType::Utf8)),
+            DataType::FixedSizeList(item(DataType::Float32), 2),
+            DataType::List(item(DataType::UInt16)),
+        ]
+    );
+    assert!(schema.fields().iter().all(|f| !f.is_nullable()));
+    assert!(to_arrow_schema(&TD::Integer(hdf5::types::IntSize::U4)).is_err());
+    assert!(to_arrow_schema(&<(i128, u8)>::type_descriptor()).is_err());
+}
+
+#[test]
+fn test_read_record_batch() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let data = records();
+    let ds = file.new_dataset_builder().with_data(&data).create("records")?;
+
+    let batch = ds.read_record_batch()?;
+    assert_eq!(*batch.schema(), to_arrow_schema(&Record::type_descriptor())?);
+    assert_eq!(batch.num_rows(), 4);
+    assert_eq!(batch["id"].as_primitive::<Int32Type>().values(), &[1, 2, 3, 4]);
+    assert_eq!(batch["value"].as_primitive::<Float64Type>().value(2), 1.5);
+    assert_eq!(batch["flag"].as_boolean(), &BooleanArray::from(vec![false, true, false, true]));
+    assert_eq!(
+        batch["name"].as_string::<i32>(),
+        &StringArray::from(vec!["one", "two", "three", ""])
+    );
+    assert_eq!(batch["code"].as_string::<i32>(), &StringArray::from(vec!["a", "bb", "cccc", ""]));
+
+    let colors = batch["color"].as_dictionary::<UInt8Type>();
+    assert_eq!(
+        colors.values().as_string::<i32>(),
+        &StringArray::from(vec!["Red", "Green", "Blue"])
+    );
+    assert_eq!(colors.keys().values(), &[2, 0, 1, 2]);
+
+    let pos = batch["pos"].as_fixed_size_list();
+    assert_eq!(pos.value_length(), 2);
+    assert_eq!(
+        pos.values().as_primitive::<Float32Type>().values(),
+        &[1., -1., 2., -2., 3., -3., 4., -4.]
+    );
+
+    let tags = batch["tags"].as_list::<i32>();
+    assert_eq!(tags.value_offsets(), &[0, 2, 2, 3, 6]);
+    assert_eq!(tags.values().as_primitive::<UInt16Type>().values(), &[1, 2, 3, 4, 5, 6]);
+
+    let slice = ds.read_record_batch_slice(1..3)?;
+    assert_eq!(slice, batch.slice(1, 2));
+    assert_eq!(ds.read_record_batch_slice(2..2)?.num_rows(), 0);
+
+    let ds = file.new_dataset_builder().with_data(&[1_i32, 2]).create("ints")?;
+    assert!(ds.read_record_batch().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_write_record_batch() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let data = records();
+    let batch =
+        file.new_dataset_builder().with_data(&data).create("records")?.read_record_batch()?;
+
+    // the batch is written by name into the existing datatype
+    let ds = file.new_dataset::<Record>().shape(0..).chunk(2).create("appended")?;
+    ds.append_record_batch(&batch.slice(0, 3))?;
+    ds.append_record_batch(&batch.slice(3, 1))?;
+    assert_eq!(ds.read_raw::<Record>()?, data);
+    assert!(ds.write_record_batch(&batch.slice(0, 3)).is_err());
+
+    let reversed: Vec<_> = data.iter().rev().cloned().collect();
+    let ds = file.new_dataset_builder().with_data(&reversed).create("overwritten")?;
+    ds.write_record_batch(&batch)?;
+    assert_eq!(ds.read_raw::<Record>()?, data);
+    assert!(ds.append_record_batch(&batch).is_err());
+    let codes: ArrayRef = Arc::new(StringArray::from(vec!["x", "y", "z", "long"]));
+    ds.write_record_batch(&RecordBatch::try_from_iter([("code", codes)])?)?;
+    assert_eq!(ds.read_raw::<Record>()?[3].code.as_str(), "long");
+    let codes: ArrayRef = Arc::new(StringArray::from(vec!["x", "y", "z", "longer"]));
+    assert!(ds.write_record_batch(&RecordBatch::try_from_iter([("code", codes)])?).is_err());
+
+    // new datasets can be created from the datatype of the batch
+    let ids: ArrayRef = Arc::new(Int32Array::from(vec![7, 8, 9]));
+    let values: ArrayRef = Arc::new(Float64Array::from(vec![0.5, 1.5, 2.5]));
+    let labels: DictionaryArray<Int32Type> = vec!["b", "a", "b"].into_iter().collect();
+    let batch = RecordBatch::try_from_iter([
+        ("id", ids),
+        ("value", values),
+        ("label", Arc::new(labels) as ArrayRef),
+    ])?;
+    let desc = record_batch_type_descriptor(&batch)?;
+    let ds = file.new_dataset_builder().empty_as(&desc).shape(0..).chunk(4).create("new")?;
+    ds.append_record_batch(&batch)?;
+    ds.append_record_batch(&batch.slice(1, 2))?;
+    let unknown: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+    assert!(ds.append_record_batch(&RecordBatch::try_from_iter([("unknown", unknown)])?).is_err());
+    assert_eq!(ds.size(), 5);
+    let read = ds.read_record_batch()?;
+    assert_eq!(read["id"].as_primitive::<Int32Type>().values(), &[7, 8, 9, 8, 9]);
+    let labels = read["label"].as_dictionary::<Int32Type>();
+    assert_eq!(labels.values().as_string::<i32>(), &StringArray::from(vec!["b", "a"]));
+    assert_eq!(labels.keys().values(), &[0, 1, 0, 1, 0]);
+
+    // columns are matched by name, fields missing from the batch are left unchanged
+    let ds = file.new_dataset_builder().empty_as(&desc).shape(3).create("partial")?;
+    ds.write_record_batch(&batch)?;
+    let doubled: ArrayRef = Arc::new(Float64Array::from(vec![1., 3., 5.]));
+    ds.write_record_batch(&RecordBatch::try_from_iter([("value", doubled)])?)?;
+    let read = ds.read_record_batch()?;
+    assert_eq!(read["id"].as_primitive::<Int32Type>().values(), &[7, 8, 9]);
+    assert_eq!(read["value"].as_primitive::<Float64Type>().values(), &[1., 3., 5.]);
+
+    // unknown columns and nulls are rejected
+    let unknown: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+    assert!(ds.write_record_batch(&RecordBatch::try_from_iter([("other", unknown)])?).is_err());
+    let nulls: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]));
+    assert!(ds.write_record_batch(&RecordBatch::try_from_iter([("id", nulls)])?).is_err());
+    Ok(())
+}
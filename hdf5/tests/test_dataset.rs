@@ -1,11 +1,12 @@
 use std::convert::TryFrom;
 use std::fmt;
 use std::io::{Read, Seek, SeekFrom};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use ndarray::{s, Array1, Array2, ArrayD, IxDyn, SliceInfo};
 use rand::prelude::{Rng, SeedableRng, SmallRng};
 
-use hdf5_types::TypeDescriptor;
+use hdf5_types::{StringPadding, TimeUnit, TimeUnits, TypeDescriptor, VarLenArray, VarLenUnicode};
 
 mod common;
 
@@ -317,6 +318,7 @@ fn test_read_write_primitive() -> hdf5::Result<()> {
 #[test]
 fn test_read_write_f16() -> hdf5::Result<()> {
     test_read_write::<::half::f16>()?;
+    test_read_write::<::half::bf16>()?;
     Ok(())
 }
 
@@ -366,6 +368,127 @@ fn test_create_on_databuilder() {
     let _ds = file.new_dataset::<i32>().shape(2).create("ds4").unwrap();
 }
 
+#[test]
+fn test_read_write_strings() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let strings = ["foo", "", "bazooka"];
+
+    let ds = file.new_dataset_builder().with_data(&strings).create("vlu")?;
+    assert_eq!(ds.dtype()?.to_descriptor()?, TypeDescriptor::VarLenUnicode);
+    assert_eq!(ds.read_strings()?, strings);
+    assert!(file.new_dataset_builder().with_data(&["a\0b"]).create("nul").is_err());
+    assert!(!file.link_exists("nul"));
+    let desc = TypeDescriptor::FixedAscii(8, StringPadding::NullTerm);
+    let ds = file.new_dataset_builder().with_data_as(&strings, &desc).create("fixed")?;
+    assert_eq!(ds.dtype()?.to_descriptor()?, desc);
+    assert_eq!(ds.read_strings()?, strings);
+    let desc = TypeDescriptor::FixedAscii(4, StringPadding::NullPad);
+    assert!(file.new_dataset_builder().with_data_as(&strings, &desc).create("short").is_err());
+    assert!(!file.link_exists("short"));
+
+    for (name, desc) in [
+        ("fa", TypeDescriptor::FixedAscii(7, StringPadding::NullPad)),
+        ("fu", TypeDescriptor::FixedUnicode(7, StringPadding::NullPad)),
+        ("vla", TypeDescriptor::VarLenAscii),
+        ("vlu2", TypeDescriptor::VarLenUnicode),
+    ] {
+        let ds = file.new_dataset_builder().empty_as(&desc).shape(3).create(name)?;
+        ds.write(&strings)?;
+        assert_eq!(ds.read_strings()?, strings);
+        let owned: Vec<String> = strings.iter().map(|&s| s.to_owned()).collect();
+        ds.write(&owned)?;
+        assert_eq!(ds.read_strings()?, owned);
+        assert!(ds.write(&["foo"]).is_err());
+        assert!(ds.write(&["a", "b", "c\0d"]).is_err());
+    }
+
+    let fa = file.dataset("fa")?;
+    assert!(fa.write(&["a", "b", "12345678"]).is_err());
+    assert!(fa.write(&["a", "b", "αβ"]).is_err());
+    let fu = file.dataset("fu")?;
+    fu.write(&["a", "b", "αβγ"])?;
+    assert_eq!(fu.read_strings()?, ["a", "b", "αβγ"]);
+    fu.as_writer().write_slice(&["xyz"], 1..2)?;
+    assert_eq!(fu.read_strings()?, ["a", "xyz", "αβγ"]);
+    assert!(file.new_dataset_builder().with_data(&[1, 2]).create("int")?.read_strings().is_err());
+
+    let attr =
+        file.new_attr_builder().empty_as(&TypeDescriptor::VarLenAscii).shape(2).create("a")?;
+    attr.write(&["x", "yz"])?;
+    assert_eq!(attr.read_strings()?, ["x", "yz"]);
+    Ok(())
+}
+
+#[test]
+fn test_read_write_ragged() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let data = vec![vec![1_i32, 2, 3], vec![], vec![4]];
+
+    let ds = file.new_dataset_builder().with_data(&data).create("ragged")?;
+    assert_eq!(ds.read_ragged::<i32>()?, data);
+    assert_eq!(ds.read_ragged::<i64>()?, vec![vec![1_i64, 2, 3], vec![], vec![4]]);
+
+    let data = [vec![5_i32, 6], vec![], vec![7, 8, 9]];
+    ds.write(&data)?;
+    assert_eq!(ds.read_ragged::<i32>()?, data);
+    assert!(ds.write(&data[..2]).is_err());
+
+    let names: Vec<Vec<VarLenUnicode>> =
+        vec![vec!["a".parse().unwrap(), "bc".parse().unwrap()], vec![], vec!["d".parse().unwrap()]];
+    let ds = file.new_dataset_builder().with_data(&names).create("names")?;
+    assert_eq!(ds.read_ragged::<VarLenUnicode>()?, names);
+    let arr = ds.read_1d::<VarLenArray<VarLenUnicode>>()?;
+    assert_eq!(arr[0].as_slice(), names[0].as_slice());
+    assert!(arr[1].is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_read_write_times() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let times = vec![UNIX_EPOCH, UNIX_EPOCH + Duration::from_micros(1_234_567_890)];
+    let ds = file.new_dataset_builder().with_data(&times).create("times")?;
+    assert_eq!(ds.dtype()?.to_descriptor()?, TypeDescriptor::Integer(hdf5_types::IntSize::U8));
+    let units = ds.time_units()?.unwrap();
+    assert_eq!(units, TimeUnits::since_unix_epoch(TimeUnit::Microseconds));
+    assert_eq!(ds.attr("units")?.read_strings()?, vec!["microseconds since 1970-01-01 00:00:00"]);
+    assert_eq!(ds.read_times::<SystemTime>()?, times);
+    assert_eq!(ds.read_raw::<SystemTime>()?, times);
+    assert_eq!(ds.read_raw::<i64>()?, vec![0, 1_234_567_890]);
+    assert!(ds.read_times::<Duration>().is_err());
+    assert!(ds.read_raw::<Duration>().is_err());
+
+    let durations = vec![Duration::from_secs(1), Duration::from_millis(1500)];
+    let ds = file.new_dataset_builder().with_data(&durations).create("durations")?;
+    assert_eq!(ds.time_units()?.unwrap(), TimeUnits::duration(TimeUnit::Microseconds));
+    assert_eq!(ds.read_times::<Duration>()?, durations);
+    assert!(ds.read_times::<SystemTime>().is_err());
+    ds.write_raw(&[Duration::from_secs(2), Duration::ZERO])?;
+    assert_eq!(ds.read_raw::<i64>()?, vec![2_000_000, 0]);
+    assert!(ds.write_raw(&[Duration::MAX, Duration::ZERO]).is_err());
+
+    // existing units are respected on both read and write
+    let hours = UNIX_EPOCH + Duration::from_secs(946_684_800);
+    let ds = file.new_dataset_builder().with_data(&[0.0_f64, 1.5]).create("hours")?;
+    let units: VarLenUnicode = "hours since 2000-01-01".parse().unwrap();
+    ds.new_attr::<VarLenUnicode>().create("units")?.write_scalar(&units)?;
+    assert_eq!(ds.read_times::<SystemTime>()?, vec![hours, hours + Duration::from_secs(5400)]);
+    ds.write_times(&[hours + Duration::from_secs(1800), hours])?;
+    assert_eq!(ds.read_raw::<f64>()?, vec![0.5, 0.0]);
+    assert!(ds.read_raw::<SystemTime>().is_err());
+
+    let ds = file.new_dataset_builder().with_data(&[0_i32, 2]).create("hours_int")?;
+    ds.new_attr::<VarLenUnicode>().create("units")?.write_scalar(&units)?;
+    assert_eq!(ds.read_times::<SystemTime>()?, vec![hours, hours + Duration::from_secs(7200)]);
+    ds.write_times(&[hours + Duration::from_secs(3600), hours])?;
+    assert_eq!(ds.read_raw::<i32>()?, vec![1, 0]);
+
+    let calendar: VarLenUnicode = "noleap".parse().unwrap();
+    ds.new_attr::<VarLenUnicode>().create("calendar")?.write_scalar(&calendar)?;
+    assert!(ds.read_times::<SystemTime>().is_err());
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "have-filter-deflate")]
 fn test_issue_223() {
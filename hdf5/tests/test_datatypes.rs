@@ -4,6 +4,8 @@ mod common;
 use hdf5::types::{TypeDescriptor as TD, *};
 use hdf5::{from_id, Datatype, H5Type};
 
+use self::common::util::new_in_memory_file;
+
 use hdf5_sys::h5i::H5I_INVALID_HID;
 
 macro_rules! check_roundtrip {
@@ -26,17 +28,22 @@ pub fn test_datatype_roundtrip() {
     check_roundtrip!(u16, TD::Unsigned(IntSize::U2));
     check_roundtrip!(u32, TD::Unsigned(IntSize::U4));
     check_roundtrip!(u64, TD::Unsigned(IntSize::U8));
+    check_roundtrip!(i128, TD::Integer(IntSize::U16));
+    check_roundtrip!(u128, TD::Unsigned(IntSize::U16));
     #[cfg(feature = "f16")]
     check_roundtrip!(::half::f16, TD::Float(FloatSize::U2));
+    #[cfg(feature = "f16")]
+    check_roundtrip!(::half::bf16, TD::CustomFloat(CustomFloatType::bfloat16()));
     check_roundtrip!(f32, TD::Float(FloatSize::U4));
     check_roundtrip!(f64, TD::Float(FloatSize::U8));
     check_roundtrip!(bool, TD::Boolean);
     check_roundtrip!([bool; 5], TD::FixedArray(Box::new(TD::Boolean), 5));
     check_roundtrip!(VarLenArray<bool>, TD::VarLenArray(Box::new(TD::Boolean)));
-    check_roundtrip!(FixedAscii<5>, TD::FixedAscii(5));
-    check_roundtrip!(FixedUnicode<5>, TD::FixedUnicode(5));
+    check_roundtrip!(FixedAscii<5>, TD::FixedAscii(5, StringPadding::NullPad));
+    check_roundtrip!(FixedUnicode<5>, TD::FixedUnicode(5, StringPadding::NullPad));
     check_roundtrip!(VarLenAscii, TD::VarLenAscii);
     check_roundtrip!(VarLenUnicode, TD::VarLenUnicode);
+    check_roundtrip!(Opaque<7>, TD::Opaque { size: 7, tag: String::new() });
 
     #[allow(dead_code)]
     #[derive(H5Type)]
@@ -135,6 +142,414 @@ pub fn test_datatype_roundtrip() {
     check_roundtrip!(E, e_desc);
 }
 
+#[test]
+pub fn test_multidim_array() {
+    use hdf5_sys::h5t::{H5Tarray_create2, H5Tget_array_dims2, H5Tget_array_ndims};
+
+    type M = [[f32; 3]; 2];
+    let m_desc = TD::FixedArray(Box::new(TD::FixedArray(Box::new(TD::Float(FloatSize::U4)), 3)), 2);
+    check_roundtrip!(M, m_desc);
+    let dt = Datatype::from_type::<M>().unwrap();
+    let mut dims = [0; 2];
+    hdf5::sync::sync(|| unsafe {
+        assert_eq!(H5Tget_array_ndims(dt.id()), 2);
+        H5Tget_array_dims2(dt.id(), dims.as_mut_ptr());
+    });
+    assert_eq!(dims, [2, 3]);
+
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Tensor {
+        id: u32,
+        m: [[f64; 3]; 3],
+    }
+    let t = Tensor { id: 7, m: [[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]] };
+    let file = new_in_memory_file().unwrap();
+    let ds = file.new_dataset_builder().with_data(&[t, t]).create("t").unwrap();
+    assert_eq!(ds.read_raw::<Tensor>().unwrap(), vec![t, t]);
+    let ds_desc = ds.dtype().unwrap().to_descriptor().unwrap();
+    assert_eq!(ds_desc, Tensor::type_descriptor());
+
+    // nested one-dimensional arrays (as written by older versions) can still be read
+    let nested = hdf5::sync::sync(|| unsafe {
+        let (inner_dims, outer_dims) = (3, 2);
+        let f32_dt = Datatype::from_type::<f32>().unwrap();
+        let inner = from_id::<Datatype>(H5Tarray_create2(f32_dt.id(), 1, &inner_dims)).unwrap();
+        from_id::<Datatype>(H5Tarray_create2(inner.id(), 1, &outer_dims)).unwrap()
+    });
+    assert_ne!(nested, dt);
+    assert_eq!(nested.to_descriptor().unwrap(), M::type_descriptor());
+    assert!(nested.conv_to::<M>().is_some());
+    assert!(nested.conv_to::<[[[f32; 3]; 2]; 1]>().is_some());
+    assert!(nested.conv_to::<[f32; 6]>().is_none());
+    assert!(nested.conv_to::<[[f32; 2]; 3]>().is_none());
+    assert!(nested.conv_to::<[[f32; 2]; 2]>().is_none());
+    assert!(dt.conv_to::<[f32; 6]>().is_none());
+    let data: [M; 2] = [[[1., 2., 3.], [4., 5., 6.]], [[7., 8., 9.], [10., 11., 12.]]];
+    let ds = file.new_dataset_builder().with_data(&data).create("m").unwrap();
+    let nested_ds = file
+        .new_dataset_builder()
+        .committed_type(&nested)
+        .with_data(&data)
+        .create("nested")
+        .unwrap();
+    assert_ne!(nested_ds.dtype().unwrap(), ds.dtype().unwrap());
+    assert_eq!(nested_ds.read_raw::<M>().unwrap(), data);
+}
+
+#[test]
+pub fn test_opaque_bitfield() {
+    for desc in [
+        TD::Opaque { size: 16, tag: "frame".into() },
+        TD::Bitfield(IntSize::U1),
+        TD::Bitfield(IntSize::U2),
+        TD::Bitfield(IntSize::U4),
+        TD::Bitfield(IntSize::U8),
+    ] {
+        let dt = Datatype::from_descriptor(&desc).unwrap();
+        assert_eq!(dt.to_descriptor().unwrap(), desc);
+        assert_eq!(dt.size(), desc.size());
+    }
+    let tagged = Datatype::from_descriptor(&TD::Opaque { size: 4, tag: "a".into() }).unwrap();
+    let other = Datatype::from_descriptor(&TD::Opaque { size: 4, tag: "b".into() }).unwrap();
+    assert_ne!(tagged, other);
+    assert!(tagged.conv_path(&other).is_none());
+    assert!(tagged.conv_to::<Opaque<4>>().is_none());
+    Datatype::register_opaque_conversion("a", "");
+    assert!(tagged.conv_to::<Opaque<4>>().is_some());
+    assert!(tagged.conv_from::<Opaque<4>>().is_some());
+    assert!(tagged.conv_to::<Opaque<5>>().is_none());
+    assert!(tagged.conv_path(&other).is_none());
+
+    let file = new_in_memory_file().unwrap();
+    let frames = [Opaque::new([1, 2, 3, 4]), Opaque::new([5, 6, 7, 8])];
+    Datatype::register_opaque_conversion("", "frame");
+    let ds = file
+        .new_dataset_builder()
+        .with_data_as(&frames, &TD::Opaque { size: 4, tag: "frame".into() })
+        .create("frames")
+        .unwrap();
+    assert_eq!(
+        ds.dtype().unwrap().to_descriptor().unwrap(),
+        TD::Opaque { size: 4, tag: "frame".into() }
+    );
+    assert_eq!(ds.read_raw::<Opaque<4>>().unwrap(), frames);
+    assert!(ds.read_raw::<Opaque<3>>().is_err());
+}
+
+#[test]
+pub fn test_byte_order() {
+    use hdf5::datatype::ByteOrder;
+
+    for order in [Endianness::Little, Endianness::Big] {
+        for desc in [
+            TD::Integer(IntSize::U2),
+            TD::Unsigned(IntSize::U8),
+            TD::Float(FloatSize::U4),
+            TD::Float(FloatSize::U8),
+            TD::Bitfield(IntSize::U4),
+        ] {
+            let desc = desc.with_byte_order(order);
+            let dt = Datatype::from_descriptor(&desc).unwrap();
+            assert_eq!(dt.to_descriptor().unwrap(), desc);
+            let expected = match order {
+                Endianness::Little => ByteOrder::LittleEndian,
+                Endianness::Big => ByteOrder::BigEndian,
+            };
+            assert_eq!(dt.byte_order(), expected);
+        }
+    }
+    assert!(Datatype::from_descriptor(&TD::Endian(Box::new(TD::Boolean), Endianness::Big)).is_err());
+
+    #[allow(dead_code)]
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(i16)]
+    enum Level {
+        Low = -300,
+        High = 300,
+    }
+    for order in [Endianness::Little, Endianness::Big] {
+        let desc = Level::type_descriptor().with_byte_order(order);
+        let dt = Datatype::from_descriptor(&desc).unwrap();
+        assert_eq!(dt.to_descriptor().unwrap(), desc);
+        assert!(dt.conv_to::<Level>().is_some());
+    }
+
+    let file = new_in_memory_file().unwrap();
+    let data = [1.5_f64, -2.0, 0.25];
+    let desc = f64::type_descriptor().with_byte_order(Endianness::Big);
+    let ds = file.new_dataset_builder().with_data_as(&data, &desc).create("be").unwrap();
+    assert_eq!(ds.dtype().unwrap().byte_order(), ByteOrder::BigEndian);
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), desc);
+    assert_eq!(ds.read_raw::<f64>().unwrap(), data);
+    assert_eq!(ds.read_raw::<f32>().unwrap(), [1.5_f32, -2.0, 0.25]);
+
+    type T = (u16, [i32; 2]);
+    let records = [(1_u16, [2_i32, -3]), (4, [5, 6])];
+    let desc = T::type_descriptor().with_byte_order(Endianness::Big);
+    let ds = file.new_dataset_builder().with_data_as(&records, &desc).create("rec").unwrap();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), desc);
+    assert_eq!(ds.read_raw::<T>().unwrap(), records);
+
+    let levels = [Level::High, Level::Low];
+    let desc = Level::type_descriptor().with_byte_order(Endianness::Big);
+    let ds = file.new_dataset_builder().with_data_as(&levels, &desc).create("enum").unwrap();
+    assert_eq!(ds.dtype().unwrap().byte_order(), ByteOrder::BigEndian);
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), desc);
+    assert_eq!(ds.read_raw::<Level>().unwrap(), levels);
+
+    let attr = file
+        .new_attr_builder()
+        .with_data_as(&[1000_u32], &TD::Unsigned(IntSize::U4).with_byte_order(Endianness::Big))
+        .create("attr")
+        .unwrap();
+    let value = attr.read_value().unwrap();
+    let value = value.iter().next().unwrap();
+    assert_eq!(value.type_descriptor(), &TD::Unsigned(IntSize::U4));
+    assert_eq!(value.clone().cast::<u32>().unwrap(), 1000);
+}
+
+#[test]
+pub fn test_int128() {
+    #[allow(dead_code)]
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(i128)]
+    enum Kind {
+        Low = -(1 << 40),
+        High = 1 << 62,
+    }
+    check_roundtrip!(
+        Kind,
+        TD::Enum(EnumType {
+            size: IntSize::U16,
+            signed: true,
+            members: vec![
+                EnumMember { name: "Low".into(), value: -(1i64 << 40) as _ },
+                EnumMember { name: "High".into(), value: 1 << 62 },
+            ],
+        })
+    );
+
+    #[allow(dead_code)]
+    #[derive(H5Type)]
+    #[repr(i16)]
+    enum Small {
+        A = -10_000,
+        B = 10_000,
+    }
+    check_roundtrip!(
+        Small,
+        TD::Enum(EnumType {
+            size: IntSize::U2,
+            signed: true,
+            members: vec![
+                EnumMember { name: "A".into(), value: -10_000i64 as _ },
+                EnumMember { name: "B".into(), value: 10_000 },
+            ],
+        })
+    );
+
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Record {
+        id: u128,
+        hash: i128,
+        kind: Kind,
+    }
+    let records = [
+        Record { id: u128::MAX, hash: i128::MIN, kind: Kind::Low },
+        Record { id: 1 << 100, hash: -1, kind: Kind::High },
+    ];
+    let file = new_in_memory_file().unwrap();
+    let ds = file.new_dataset_builder().with_data(&records).create("records").unwrap();
+    assert_eq!(ds.read_raw::<Record>().unwrap(), records);
+
+    let ds = file.new_dataset_builder().with_data(&[1_u64, u64::MAX]).create("u64").unwrap();
+    assert_eq!(ds.read_raw::<u128>().unwrap(), [1, u128::from(u64::MAX)]);
+    assert_eq!(ds.read_raw::<i128>().unwrap(), [1, i128::from(u64::MAX)]);
+}
+
+#[test]
+pub fn test_custom_precision() {
+    let int12 = CustomIntType { size: 2, precision: 12, offset: 0, signed: true };
+    let bf16 = CustomFloatType::bfloat16();
+    for desc in [
+        TD::CustomInteger(int12),
+        TD::CustomInteger(CustomIntType { size: 4, precision: 24, offset: 8, signed: false }),
+        TD::CustomInteger(CustomIntType { size: 3, precision: 24, offset: 0, signed: false }),
+        TD::CustomFloat(bf16),
+        TD::CustomFloat(CustomFloatType {
+            size: 4,
+            precision: 16,
+            offset: 8,
+            sign_pos: 23,
+            exp_pos: 15,
+            exp_size: 8,
+            exp_bias: 127,
+            mant_pos: 8,
+            mant_size: 7,
+        }),
+        TD::CustomFloat(bf16).with_byte_order(Endianness::Big),
+        TD::CustomInteger(int12).with_byte_order(Endianness::Big),
+    ] {
+        let dt = Datatype::from_descriptor(&desc).unwrap();
+        assert_eq!(dt.to_descriptor().unwrap(), desc);
+        assert_eq!(dt.size(), desc.size());
+    }
+    assert!(Datatype::from_descriptor(&TD::CustomInteger(CustomIntType {
+        size: 1,
+        precision: 12,
+        offset: 0,
+        signed: true
+    }))
+    .is_err());
+
+    let file = new_in_memory_file().unwrap();
+    let samples: Vec<i16> = (0..1000).map(|i| (i * 7) % 4000 - 2000).collect();
+    let ds = file
+        .new_dataset_builder()
+        .with_data_as(&samples, &TD::CustomInteger(int12))
+        .chunk(1000)
+        .nbit()
+        .create("samples")
+        .unwrap();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), TD::CustomInteger(int12));
+    assert_eq!(ds.read_raw::<i16>().unwrap(), samples);
+    assert_eq!(
+        ds.read_raw::<i32>().unwrap(),
+        samples.iter().map(|&x| x.into()).collect::<Vec<i32>>()
+    );
+    assert!(ds.storage_size() < 2 * samples.len() as u64);
+
+    // non-native byte order read back from a file can be used to create new datasets
+    let desc = TD::CustomInteger(int12).with_byte_order(Endianness::Big);
+    let ds = file.new_dataset_builder().with_data_as(&samples, &desc).create("be").unwrap();
+    let desc = ds.dtype().unwrap().to_descriptor().unwrap();
+    assert!(
+        matches!(desc, TD::Endian(ref ty, Endianness::Big) if **ty == TD::CustomInteger(int12))
+    );
+    let ds = file.new_dataset_builder().with_data_as(&samples, &desc).create("be2").unwrap();
+    assert_eq!(ds.read_raw::<i16>().unwrap(), samples);
+
+    let values = [1.5_f32, -0.125, 3.0, 1e30];
+    let ds = file
+        .new_dataset_builder()
+        .with_data_as(&values, &TD::CustomFloat(bf16))
+        .create("bf16")
+        .unwrap();
+    assert_eq!(ds.dtype().unwrap().size(), 2);
+    let read = ds.read_raw::<f32>().unwrap();
+    assert_eq!(read[..3], values[..3]);
+    assert!((read[3] / 1e30 - 1.).abs() < 1e-2);
+
+    let attr = file
+        .new_attr_builder()
+        .with_data_as(&[-5_i16], &TD::CustomInteger(int12))
+        .create("int12")
+        .unwrap();
+    let value = attr.read_value().unwrap();
+    let value = value.iter().next().unwrap();
+    assert_eq!(value.type_descriptor(), &TD::Integer(IntSize::U2));
+    assert_eq!(value.clone().cast::<i16>().unwrap(), -5);
+}
+
+#[test]
+#[cfg(feature = "f16")]
+pub fn test_bfloat16() {
+    use ::half::{bf16, f16};
+    use hdf5::Conversion;
+
+    let dt = Datatype::from_type::<bf16>().unwrap();
+    assert_eq!(dt.size(), 2);
+    assert_ne!(dt, Datatype::from_type::<f16>().unwrap());
+    assert_eq!(
+        Datatype::from_type::<f16>().unwrap().to_descriptor().unwrap(),
+        TD::Float(FloatSize::U2)
+    );
+    for conv in
+        [dt.conv_to::<f32>(), dt.conv_to::<f64>(), dt.conv_from::<f32>(), dt.conv_from::<f64>()]
+    {
+        assert_eq!(conv, Some(Conversion::Hard));
+    }
+    assert_eq!(dt.conv_to::<f16>(), Some(Conversion::Soft));
+
+    let values: Vec<bf16> = [1.5_f32, -0.125, 3.0, 1e30, f32::INFINITY, 1e-40]
+        .iter()
+        .map(|&x| bf16::from_f32(x))
+        .collect();
+    let file = new_in_memory_file().unwrap();
+    let ds = file.new_dataset_builder().with_data(&values).create("bf16").unwrap();
+    assert_eq!(
+        ds.dtype().unwrap().to_descriptor().unwrap(),
+        TD::CustomFloat(CustomFloatType::bfloat16())
+    );
+    assert_eq!(ds.read_raw::<bf16>().unwrap(), values);
+    let expected: Vec<f32> = values.iter().map(|x| x.to_f32()).collect();
+    assert_eq!(ds.as_reader().conversion(Conversion::Hard).read_raw::<f32>().unwrap(), expected);
+    let expected: Vec<f64> = values.iter().map(|x| x.to_f64()).collect();
+    assert_eq!(ds.as_reader().conversion(Conversion::Hard).read_raw::<f64>().unwrap(), expected);
+    assert!(ds.as_reader().conversion(Conversion::Hard).read_raw::<f16>().is_err());
+    assert_eq!(
+        ds.read_raw::<f16>().unwrap()[..3],
+        [f16::from_f32(1.5), f16::from_f32(-0.125), f16::from_f32(3.0)]
+    );
+
+    // rounding to nearest, ties to even
+    let inputs = [
+        1.0 + 2_f32.powi(-8),
+        1.0 + 3.0 * 2_f32.powi(-8),
+        1.0 + 2_f32.powi(-8) + 2_f32.powi(-20),
+        f32::NAN,
+    ];
+    ds.as_writer()
+        .conversion(Conversion::Hard)
+        .write_raw(&[inputs[0], inputs[1], inputs[2], inputs[3], 0., 0.])
+        .unwrap();
+    let read = ds.read_raw::<bf16>().unwrap();
+    let up = bf16::from_f32(1.0 + 2_f32.powi(-7));
+    assert_eq!(read[..3], [bf16::ONE, bf16::from_f32(1.0 + 2_f32.powi(-6)), up]);
+    assert!(read[3].is_nan());
+    let inputs = [1.0 + 2_f64.powi(-8) + 2_f64.powi(-40), -2.5e38, 1e39];
+    ds.as_writer()
+        .conversion(Conversion::Hard)
+        .write_raw(&[inputs[0], inputs[1], inputs[2], 0., 0., 0.])
+        .unwrap();
+    let read = ds.read_raw::<bf16>().unwrap();
+    assert_eq!(read[..3], [up, bf16::from_f32(-2.5e38), bf16::INFINITY]);
+}
+
+#[test]
+pub fn test_string_padding() {
+    for pad in [StringPadding::NullTerm, StringPadding::NullPad, StringPadding::SpacePad] {
+        for desc in [TD::FixedAscii(6, pad), TD::FixedUnicode(6, pad)] {
+            assert_eq!(Datatype::from_descriptor(&desc).unwrap().to_descriptor().unwrap(), desc);
+        }
+    }
+
+    let file = new_in_memory_file().unwrap();
+    let desc = TD::FixedAscii(6, StringPadding::SpacePad);
+    let ds = file.new_dataset_builder().empty_as(&desc).shape(2).create("space").unwrap();
+    ds.write(&["ab", "abcdef"]).unwrap();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), desc);
+    assert_eq!(ds.read_strings().unwrap(), ["ab", "abcdef"]);
+    assert_eq!(ds.as_reader().trim_padding(false).read_strings().unwrap(), ["ab    ", "abcdef"]);
+
+    let desc = TD::FixedAscii(4, StringPadding::NullTerm);
+    let ds = file.new_dataset_builder().empty_as(&desc).shape(1).create("nullterm").unwrap();
+    assert!(ds.write(&["abcd"]).is_err());
+    ds.as_writer().truncate(true).write(&["abcd"]).unwrap();
+    assert_eq!(ds.read_strings().unwrap(), ["abc"]);
+
+    let desc = TD::FixedUnicode(4, StringPadding::NullPad);
+    let ds = file.new_dataset_builder().empty_as(&desc).shape(1).create("nullpad").unwrap();
+    assert!(ds.write(&["aβγ"]).is_err());
+    ds.as_writer().truncate(true).write(&["aβγ"]).unwrap();
+    assert_eq!(ds.read_strings().unwrap(), ["aβ"]);
+    assert!(ds.as_writer().write_slice(&["βγδ"], 0..1).is_err());
+    ds.as_writer().truncate(true).write_slice(&["βγδ"], 0..1).unwrap();
+    assert_eq!(ds.read_strings().unwrap(), ["βγ"]);
+}
+
 #[test]
 pub fn test_invalid_datatype() {
     assert_err!(from_id::<Datatype>(H5I_INVALID_HID), "Invalid handle id");
@@ -150,3 +565,65 @@ pub fn test_eq() {
 pub fn test_debug() {
     assert_eq!(format!("{:?}", Datatype::from_type::<u32>().unwrap()), "<HDF5 datatype>");
 }
+
+#[cfg(feature = "complex")]
+#[test]
+pub fn test_complex_layouts() {
+    use num_complex::{Complex32, Complex64};
+
+    let data = [Complex64::new(1.0, -2.0), Complex64::new(0.5, 3.25)];
+    let data32 = [Complex32::new(1.0, -2.0), Complex32::new(0.5, 3.25)];
+    let file = new_in_memory_file().unwrap();
+
+    // layouts other than the default are only converted once explicitly enabled
+    let array_dt = Datatype::from_descriptor(&ComplexLayout::Array.type_descriptor::<f64>());
+    assert!(array_dt.unwrap().conv_to::<Complex64>().is_none());
+    Datatype::register_complex_conversions();
+    Datatype::register_complex_conversions();
+
+    for layout in [ComplexLayout::RI, ComplexLayout::RealImag, ComplexLayout::Array] {
+        let desc = layout.type_descriptor::<f64>();
+        let name = format!("{:?}", layout);
+        let ds = file.new_dataset_builder().with_data_as(&data, &desc).create(&*name).unwrap();
+        assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), desc);
+        assert_eq!(ds.read_raw::<Complex64>().unwrap(), data);
+        assert_eq!(ds.read_raw::<Complex32>().unwrap(), data32);
+    }
+
+    // fields are matched by name, regardless of their order
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct ImRe {
+        im: f32,
+        re: f32,
+    }
+    let ds =
+        file.new_dataset_builder().with_data(&[ImRe { im: 2.0, re: 1.0 }]).create("imre").unwrap();
+    assert_eq!(ds.read_raw::<Complex64>().unwrap(), [Complex64::new(1.0, 2.0)]);
+    let ds = file.dataset("RealImag").unwrap();
+    assert_eq!(ds.read_raw::<ImRe>().unwrap()[0], ImRe { im: -2.0, re: 1.0 });
+    assert!(Datatype::from_type::<ImRe>().unwrap().conv_to::<[f64; 2]>().is_some());
+
+    // other field names are not treated as complex numbers
+    #[allow(non_snake_case)]
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Other {
+        Re: f32,
+        Im: f32,
+    }
+    assert!(Datatype::from_type::<Other>().unwrap().conv_to::<[f64; 2]>().is_none());
+    assert!(Datatype::from_type::<[f64; 2]>().unwrap().conv_to::<Other>().is_none());
+
+    let desc = ComplexLayout::Native.type_descriptor::<f64>();
+    let res = file.new_dataset_builder().with_data_as(&data, &desc).create("native");
+    if cfg!(feature = "2.0.0") {
+        let ds = res.unwrap();
+        assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), desc);
+        assert_eq!(ds.read_raw::<Complex64>().unwrap(), data);
+        assert_eq!(ds.read_raw::<Complex32>().unwrap(), data32);
+    } else {
+        assert!(res.is_err());
+    }
+    assert!(Datatype::from_descriptor(&TD::Complex(Box::new(TD::Integer(IntSize::U4)))).is_err());
+}
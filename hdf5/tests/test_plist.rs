@@ -932,3 +932,68 @@ fn test_lcpl_char_encoding() -> hdf5::Result<()> {
     assert_eq!(LCB::from_plist(&pl)?.finish()?.get_char_encoding()?, CharEncoding::Utf8);
     Ok(())
 }
+
+type LA = LinkAccess;
+type LAB = LinkAccessBuilder;
+
+#[test]
+fn test_lapl_common() -> hdf5::Result<()> {
+    test_pl_common!(LA, PropertyListClass::LinkAccess, |b: &mut LAB| b.nlinks(3).finish());
+    Ok(())
+}
+
+#[test]
+fn test_lapl_nlinks() -> hdf5::Result<()> {
+    assert_eq!(LA::try_new()?.get_nlinks()?, 16);
+    test_pl!(LA, nlinks: 1);
+    test_pl!(LA, nlinks: 100);
+    let pl = LAB::new().nlinks(7).finish()?;
+    assert_eq!(LAB::from_plist(&pl)?.finish()?.get_nlinks()?, 7);
+    Ok(())
+}
+
+#[test]
+fn test_lapl_elink_prefix() -> hdf5::Result<()> {
+    assert_eq!(LA::try_new()?.get_elink_prefix()?, "");
+    assert_eq!(LA::try_new()?.elink_prefix(), "");
+    assert_eq!(LAB::new().elink_prefix("foo").finish()?.get_elink_prefix()?, "foo");
+    assert_eq!(LAB::new().elink_prefix("foo/bar").finish()?.elink_prefix(), "foo/bar");
+    let pl = LAB::new().elink_prefix("foo").finish()?;
+    assert_eq!(LAB::from_plist(&pl)?.finish()?.get_elink_prefix()?, "foo");
+    Ok(())
+}
+
+#[test]
+fn test_lapl_elink_fapl() -> hdf5::Result<()> {
+    assert!(LA::try_new()?.get_elink_fapl()?.is_none());
+    let fapl = FileAccess::build().sieve_buf_size(1234).finish()?;
+    let pl = LAB::new().elink_fapl(&fapl).finish()?;
+    assert_eq!(pl.get_elink_fapl()?.unwrap().sieve_buf_size(), 1234);
+    assert_eq!(LAB::from_plist(&pl)?.finish()?.elink_fapl().unwrap().sieve_buf_size(), 1234);
+    Ok(())
+}
+
+#[test]
+fn test_lapl_elink_cb() -> hdf5::Result<()> {
+    use hdf5::plist::link_access::ElinkTraverse;
+
+    fn cb(_info: &mut ElinkTraverse) -> hdf5::Result<()> {
+        Ok(())
+    }
+
+    assert!(LA::try_new()?.get_elink_cb()?.is_none());
+    let pl = LAB::new().elink_cb(cb).finish()?;
+    assert!(pl.get_elink_cb()?.is_some());
+    assert!(LAB::from_plist(&pl)?.finish()?.elink_cb().is_some());
+    Ok(())
+}
+
+#[test]
+fn test_fmpl_common() -> hdf5::Result<()> {
+    let pl = FileMount::try_new()?;
+    assert_eq!(pl.class()?, PropertyListClass::FileMount);
+    assert!(format!("{:?}", pl).starts_with("FileMount"));
+    assert_eq!(pl.copy(), pl);
+    assert_eq!(FileMountBuilder::from_plist(&pl)?.finish()?, pl);
+    Ok(())
+}
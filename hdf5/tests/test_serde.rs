@@ -0,0 +1,163 @@
+#![cfg(feature = "serde")]
+
+#[macro_use]
+mod common;
+
+use std::collections::BTreeMap;
+
+use ndarray::{arr2, Array2, ArrayD, IxDyn};
+use serde::{Deserialize, Serialize};
+
+use hdf5::serialize::{Placement, Serializer};
+use hdf5::types::{TypeDescriptor, VarLenUnicode};
+use hdf5::{from_group, to_group, LocationType};
+
+use self::common::util::new_in_memory_file;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Circle(f64),
+    Rect { w: f64, h: f64 },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    id: u32,
+    weights: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Outer {
+    name: String,
+    count: i64,
+    ratio: f64,
+    flag: bool,
+    color: Color,
+    colors: Vec<Color>,
+    values: Vec<f64>,
+    matrix: Vec<Vec<i32>>,
+    ragged: Vec<Vec<i32>>,
+    inner: Inner,
+    shape: Shape,
+    missing: Option<u8>,
+    present: Option<u8>,
+    map: BTreeMap<String, u16>,
+}
+
+fn outer() -> Outer {
+    Outer {
+        name: "foo".into(),
+        count: -3,
+        ratio: 0.5,
+        flag: true,
+        color: Color::Green,
+        colors: vec![Color::Blue, Color::Red],
+        values: vec![1., 2., 3.],
+        matrix: vec![vec![1, 2, 3], vec![4, 5, 6]],
+        ragged: vec![vec![1], vec![2, 3]],
+        inner: Inner { id: 7, weights: vec![0.25, 0.75] },
+        shape: Shape::Rect { w: 1., h: 2. },
+        missing: None,
+        present: Some(9),
+        map: [("a".to_owned(), 1), ("b".to_owned(), 2)].into_iter().collect(),
+    }
+}
+
+#[test]
+fn test_serde_roundtrip() {
+    let file = new_in_memory_file().unwrap();
+    let group = file.create_group("outer").unwrap();
+    let value = outer();
+    to_group(&value, &group).unwrap();
+
+    assert_eq!(group.attr("name").unwrap().read_scalar::<VarLenUnicode>().unwrap(), "foo");
+    assert_eq!(group.attr("count").unwrap().read_scalar::<i64>().unwrap(), -3);
+    assert!(!group.attr_names().unwrap().contains(&"missing".to_owned()));
+    assert_eq!(group.dataset("values").unwrap().read_raw::<f64>().unwrap(), vec![1., 2., 3.]);
+    let matrix = group.dataset("matrix").unwrap();
+    assert_eq!(matrix.shape(), vec![2, 3]);
+    assert_eq!(matrix.read_raw::<i32>().unwrap(), vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(group.loc_type_by_name("ragged").unwrap(), LocationType::Group);
+    assert_eq!(group.loc_type_by_name("inner").unwrap(), LocationType::Group);
+    assert_eq!(
+        group.dataset("inner/weights").unwrap().read_raw::<f32>().unwrap(),
+        vec![0.25, 0.75]
+    );
+    let color = group.attr("color").unwrap().dtype().unwrap().to_descriptor().unwrap();
+    assert!(matches!(color, TypeDescriptor::Enum(_)));
+
+    assert_eq!(from_group::<Outer>(&group).unwrap(), value);
+}
+
+#[test]
+fn test_serde_placement() {
+    let file = new_in_memory_file().unwrap();
+    let group = file.create_group("outer").unwrap();
+    let value = outer();
+    value.serialize(Serializer::new(&group).placement(Placement::Dataset)).unwrap();
+
+    assert!(group.attr_names().unwrap().is_empty());
+    assert_eq!(group.dataset("count").unwrap().read_scalar::<i64>().unwrap(), -3);
+    assert_eq!(group.dataset("inner/id").unwrap().read_scalar::<u32>().unwrap(), 7);
+    assert_eq!(from_group::<Outer>(&group).unwrap(), value);
+
+    let map: BTreeMap<u32, String> = [(1, "x".to_owned()), (10, "y".to_owned())].into();
+    let group = file.create_group("map").unwrap();
+    to_group(&map, &group).unwrap();
+    assert_eq!(from_group::<BTreeMap<u32, String>>(&group).unwrap(), map);
+
+    let err = to_group(&[1, 2, 3], &file.create_group("seq").unwrap()).unwrap_err();
+    assert!(err.to_string().contains("only structs and maps"));
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Arrays {
+    #[serde(with = "hdf5::serialize::array")]
+    matrix: Array2<f64>,
+    #[serde(with = "hdf5::serialize::array")]
+    cube: ArrayD<i32>,
+    empty: Vec<u8>,
+    other: Array,
+}
+
+/// Unrelated struct which happens to be named like the one from `ndarray`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Array {
+    v: u8,
+    dim: Vec<usize>,
+    data: Vec<i32>,
+}
+
+#[test]
+fn test_serde_arrays() {
+    let file = new_in_memory_file().unwrap();
+    let group = file.create_group("arrays").unwrap();
+    let value = Arrays {
+        matrix: arr2(&[[1., 2., 3.], [4., 5., 6.]]),
+        cube: ArrayD::from_shape_fn(IxDyn(&[2, 1, 3]), |idx| (idx[0] * 10 + idx[2]) as _),
+        empty: vec![],
+        other: Array { v: 1, dim: vec![2], data: vec![3, 4] },
+    };
+    to_group(&value, &group).unwrap();
+
+    let matrix = group.dataset("matrix").unwrap();
+    assert_eq!(matrix.shape(), vec![2, 3]);
+    assert_eq!(matrix.read_2d::<f64>().unwrap(), value.matrix);
+    assert_eq!(group.dataset("cube").unwrap().read_dyn::<i32>().unwrap(), value.cube);
+    assert_eq!(group.loc_type_by_name("empty").unwrap(), LocationType::Group);
+    assert_eq!(group.loc_type_by_name("other").unwrap(), LocationType::Group);
+    assert_eq!(group.dataset("other/data").unwrap().read_raw::<i32>().unwrap(), vec![3, 4]);
+    assert_eq!(from_group::<Arrays>(&group).unwrap(), value);
+
+    // the element type of empty arrays is unknown
+    let empty = Arrays { matrix: Array2::zeros((0, 3)), ..value };
+    let err = to_group(&empty, &file.create_group("empty").unwrap()).unwrap_err();
+    assert!(err.to_string().contains("empty array"));
+}
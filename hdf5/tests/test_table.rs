@@ -0,0 +1,188 @@
+#[macro_use]
+mod common;
+
+use hdf5::types::VarLenUnicode;
+use hdf5::{AttrValue, Extents, H5Type, Table};
+
+use self::common::util::new_in_memory_file;
+
+#[derive(H5Type, Clone, Debug, PartialEq)]
+#[repr(C)]
+struct Particle {
+    id: i32,
+    energy: f64,
+    name: VarLenUnicode,
+}
+
+#[derive(H5Type, Clone, Debug, PartialEq)]
+#[repr(C)]
+struct Named {
+    name: VarLenUnicode,
+    id: i32,
+}
+
+fn particles(ids: std::ops::Range<i32>) -> Vec<Particle> {
+    ids.map(|id| Particle {
+        id,
+        energy: f64::from(id) * 1.5,
+        name: format!("p{id}").parse().unwrap(),
+    })
+    .collect()
+}
+
+fn ids(table: &Table) -> Vec<i32> {
+    table.read_records::<Particle, _>(..).unwrap().iter().map(|r| r.id).collect()
+}
+
+#[test]
+fn test_table_attrs() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let table = file.new_table::<Particle>("particles", "Particle data", 4)?;
+    assert_eq!(table.num_records(), 0);
+    assert_eq!(table.chunk(), Some(vec![4]));
+    assert!(table.is_resizable());
+
+    let read_attr = |name: &str| table.attr(name).unwrap().read_strings().unwrap();
+    assert_eq!(read_attr("CLASS"), ["TABLE"]);
+    assert_eq!(read_attr("VERSION"), ["3.0"]);
+    assert_eq!(read_attr("TITLE"), ["Particle data"]);
+    assert_eq!(read_attr("FIELD_0_NAME"), ["id"]);
+    assert_eq!(read_attr("FIELD_1_NAME"), ["energy"]);
+    assert_eq!(read_attr("FIELD_2_NAME"), ["name"]);
+    assert!(!table.attr_exists("FIELD_3_NAME"));
+    assert_eq!(table.field_names()?, ["id", "energy", "name"]);
+
+    table.set_title("Particles")?;
+    assert_eq!(file.table("particles")?.title()?, "Particles");
+
+    // plain datasets and non-compound types are not tables
+    file.new_dataset::<Particle>().shape(2).create("plain")?;
+    assert!(file.table("plain").is_err());
+    assert!(file.new_table::<i32>("ints", "", 4).is_err());
+    assert!(file.new_table::<Particle>("empty", "", 0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_table_records() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let table = file.new_table::<Particle>("particles", "", 3)?;
+    table.append_records(&particles(0..4))?;
+    table.append_records(&particles(4..6))?;
+    table.append_records::<Particle>(&[])?;
+    assert_eq!(table.num_records(), 6);
+    assert_eq!(table.read_records::<Particle, _>(..)?, particles(0..6));
+    assert_eq!(table.read_records::<Particle, _>(2..4)?, particles(2..4));
+    assert_eq!(table.read_records::<Particle, _>(4..=5)?, particles(4..6));
+    assert!(table.read_records::<Particle, _>(3..3)?.is_empty());
+    assert!(table.read_records::<Particle, _>(5..7).is_err());
+
+    // records are converted by field name
+    let named = table.read_records::<Named, _>(1..2)?;
+    assert_eq!(named, [Named { name: "p1".parse().unwrap(), id: 1 }]);
+
+    table.write_records(1, &particles(10..12))?;
+    assert_eq!(ids(&table), [0, 10, 11, 3, 4, 5]);
+    assert!(table.write_records(5, &particles(0..2)).is_err());
+
+    table.insert_records(2, &particles(20..23))?;
+    assert_eq!(ids(&table), [0, 10, 20, 21, 22, 11, 3, 4, 5]);
+    table.insert_records(0, &particles(30..31))?;
+    table.insert_records(10, &particles(40..41))?;
+    assert_eq!(ids(&table), [30, 0, 10, 20, 21, 22, 11, 3, 4, 5, 40]);
+    assert!(table.insert_records(12, &particles(0..1)).is_err());
+    assert_eq!(table.read_records::<Particle, _>(3..4)?, particles(20..21));
+
+    // records that cannot be converted leave the table unchanged
+    assert!(table.append_records(&[1_i32, 2]).is_err());
+    assert!(table.insert_records(1, &[1_i32, 2]).is_err());
+    assert_eq!(ids(&table), [30, 0, 10, 20, 21, 22, 11, 3, 4, 5, 40]);
+
+    table.delete_records(1..3)?;
+    assert_eq!(ids(&table), [30, 20, 21, 22, 11, 3, 4, 5, 40]);
+    table.delete_records(7..)?;
+    table.delete_records(..=0)?;
+    table.delete_records(2..2)?;
+    assert_eq!(ids(&table), [20, 21, 22, 11, 3, 4]);
+    assert!(table.delete_records(5..7).is_err());
+    table.delete_records(..)?;
+    assert_eq!(table.num_records(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_table_columns() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let table = file.new_table::<Particle>("particles", "", 2)?;
+    assert!(table.read_column::<i32>("id")?.is_empty());
+    table.append_records(&particles(0..3))?;
+
+    assert_eq!(table.read_column::<i32>("id")?, [0, 1, 2]);
+    assert_eq!(table.read_column::<f64>("energy")?, [0., 1.5, 3.]);
+    assert_eq!(table.read_column::<i64>("id")?, [0, 1, 2]);
+    let names = table.read_column::<VarLenUnicode>("name")?;
+    assert_eq!(names.iter().map(|s| s.as_str()).collect::<Vec<_>>(), ["p0", "p1", "p2"]);
+    assert!(table.read_column::<i32>("other").is_err());
+
+    table.write_column("energy", &[-1_f32, -2., -3.])?;
+    let records = table.read_records::<Particle, _>(..)?;
+    assert_eq!(ids(&table), [0, 1, 2]);
+    assert_eq!(records.iter().map(|r| r.energy).collect::<Vec<_>>(), [-1., -2., -3.]);
+    assert_eq!(records[2].name.as_str(), "p2");
+    assert!(table.write_column("energy", &[1_f64, 2.]).is_err());
+    assert!(table.write_column("other", &[1_f64, 2., 3.]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_table_fields() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let mut table = file.new_table::<Particle>("particles", "Particles", 2)?;
+    table.append_records(&particles(0..5))?;
+    table.new_attr::<u32>().create("run")?.write_scalar(&7_u32)?;
+    table.new_attr::<u16>().shape([2, 0]).create("none")?;
+    table.new_attr::<f32>().shape(Extents::Null).create("null")?;
+
+    table.add_field("flag", &[true, false, true, false, true])?;
+    assert_eq!(table.field_names()?, ["id", "energy", "name", "flag"]);
+    assert_eq!(table.read_column::<bool>("flag")?, [true, false, true, false, true]);
+    assert_eq!(table.read_records::<Particle, _>(..)?, particles(0..5));
+    assert_eq!(table.attr("FIELD_3_NAME")?.read_strings()?, ["flag"]);
+    assert_eq!(table.title()?, "Particles");
+    assert_eq!(table.attr("run")?.read_scalar::<u32>()?, 7);
+    let attrs = table.read_attrs()?;
+    assert_eq!(attrs["none"], AttrValue::Empty(u16::type_descriptor(), vec![2, 0]));
+    assert_eq!(attrs["null"], AttrValue::Null(f32::type_descriptor()));
+    assert_eq!(table.chunk(), Some(vec![2]));
+    assert!(table.add_field("id", &[0_i32; 5]).is_err());
+    assert!(table.add_field("short", &[0_i32; 4]).is_err());
+
+    table.drop_field("energy")?;
+    assert_eq!(table.field_names()?, ["id", "name", "flag"]);
+    assert_eq!(table.attr("FIELD_1_NAME")?.read_strings()?, ["name"]);
+    assert_eq!(table.attr("FIELD_2_NAME")?.read_strings()?, ["flag"]);
+    assert!(!table.attr_exists("FIELD_3_NAME"));
+    assert_eq!(table.read_column::<i32>("id")?, [0, 1, 2, 3, 4]);
+    let named = table.read_records::<Named, _>(4..)?;
+    assert_eq!(named, [Named { name: "p4".parse().unwrap(), id: 4 }]);
+    assert!(table.drop_field("energy").is_err());
+
+    // the rewritten table replaces the original dataset
+    let reopened = file.table("particles")?;
+    assert_eq!(reopened.field_names()?, ["id", "name", "flag"]);
+    assert_eq!(file.member_names()?, ["particles"]);
+    reopened.append_records(&table.read_records::<Named, _>(0..1)?)?;
+    assert_eq!(table.num_records(), 6);
+
+    // the table is left unchanged if it cannot be rewritten
+    file.create_group("particles.old")?;
+    assert!(table.drop_field("flag").is_err());
+    assert_eq!(file.member_names()?, ["particles", "particles.old"]);
+    assert_eq!(file.table("particles")?.field_names()?, ["id", "name", "flag"]);
+    file.unlink("particles.old")?;
+
+    table.drop_field("flag")?;
+    table.drop_field("name")?;
+    assert!(table.drop_field("id").is_err());
+    Ok(())
+}
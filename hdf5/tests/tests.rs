@@ -12,3 +12,81 @@ fn roundtrip_compound_type() {
     let td = dt.to_descriptor().unwrap();
     assert_eq!(td, Compound::type_descriptor());
 }
+
+#[test]
+fn roundtrip_group_layout() {
+    use hdf5::types::VarLenUnicode;
+    use hdf5::H5Group;
+    use ndarray::{arr2, Array2};
+
+    #[derive(H5Group, Debug, PartialEq)]
+    struct Meta {
+        #[hdf5(attr)]
+        version: u32,
+        #[hdf5(attr, rename = "operator")]
+        user: VarLenUnicode,
+    }
+
+    #[derive(H5Group, Debug, PartialEq)]
+    struct Experiment {
+        #[hdf5(attr)]
+        temperature: f64,
+        #[hdf5(dataset, chunk = 2, deflate = 4, shuffle)]
+        samples: Vec<i32>,
+        #[hdf5(dataset, rename = "image")]
+        frame: Array2<u16>,
+        #[hdf5(group)]
+        meta: Meta,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = hdf5::File::create(dir.path().join("group.h5")).unwrap();
+    let group = file.create_group("experiment").unwrap();
+    let exp = Experiment {
+        temperature: 293.5,
+        samples: vec![1, 2, 3, 4, 5],
+        frame: arr2(&[[1, 2], [3, 4]]),
+        meta: Meta { version: 3, user: "jdoe".parse().unwrap() },
+    };
+    exp.write_to(&group).unwrap();
+
+    let samples = group.dataset("samples").unwrap();
+    assert_eq!(samples.chunk(), Some(vec![2]));
+    assert_eq!(samples.filters().len(), 2);
+    assert_eq!(group.dataset("image").unwrap().shape(), vec![2, 2]);
+    assert_eq!(group.attr("temperature").unwrap().read_scalar::<f64>().unwrap(), 293.5);
+    let user = group.group("meta").unwrap().attr("operator").unwrap();
+    assert_eq!(user.read_scalar::<VarLenUnicode>().unwrap().as_str(), "jdoe");
+    assert_eq!(Experiment::read_from(&group).unwrap(), exp);
+}
+
+#[test]
+fn roundtrip_proxy_type() {
+    use hdf5::{H5Proxy, H5Type};
+
+    #[derive(H5Proxy, Clone, Debug, PartialEq)]
+    struct Record {
+        id: u32,
+        #[hdf5(with = "hdf5::types::VarLenUnicode")]
+        label: String,
+        #[hdf5(skip)]
+        cache: Vec<u8>,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = hdf5::File::create(dir.path().join("proxy.h5")).unwrap();
+    let records = vec![
+        Record { id: 1, label: "a".into(), cache: vec![1] },
+        Record { id: 2, label: "bc".into(), cache: vec![] },
+    ];
+    let ds = file.new_dataset::<<Record as H5Proxy>::Proxy>().shape(2).create("records").unwrap();
+    ds.write_proxy(&records).unwrap();
+    let read = ds.read_raw_proxy::<Record>().unwrap();
+    assert_eq!(
+        read,
+        records.iter().map(|r| Record { cache: vec![], ..r.clone() }).collect::<Vec<_>>()
+    );
+    assert_eq!(ds.read_1d::<<Record as H5Proxy>::Proxy>().unwrap().len(), 2);
+    let td = ds.dtype().unwrap().to_descriptor().unwrap();
+    assert_eq!(td, <Record as H5Proxy>::Proxy::type_descriptor());
+}